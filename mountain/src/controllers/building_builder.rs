@@ -5,16 +5,21 @@ use commons::geometry::{xy, xyz, XYRectangle, XY};
 use commons::grid::Grid;
 use commons::unsafe_ordering::unsafe_ordering;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{thread_rng, Rng};
 
 use crate::controllers::Result::{self, Action, NoAction};
 use crate::model::ability::Ability;
-use crate::model::building::{Building, Roof, Window};
+use crate::model::building::{Building, Kind, Roof, Window};
+use crate::model::construction;
 use crate::model::direction::Direction;
+use crate::model::group::Group;
+use crate::model::piste::{self, Piste};
+use crate::model::satisfaction::Rating;
 use crate::model::selection::Selection;
-use crate::model::skier::{Clothes, Color, Skier};
-use crate::services::id_allocator;
+use crate::model::skier::{Clothes, Color, Equipment, Skier};
+use crate::services::{economy, id_allocator};
 use crate::systems::{building_artist, messenger, tree_artist, window_artist};
+use crate::utils;
 
 pub const HEIGHT_MIN: u32 = 3;
 pub const HEIGHT_MAX: u32 = 60;
@@ -23,6 +28,11 @@ pub const HEIGHT_INTERVAL: u32 = 3;
 pub const WINDOW_INTERVAL: f32 = 3.0;
 pub const WINDOW_FREE_LENGTH: f32 = 2.0;
 
+const GROUP_SIZE_MIN: usize = 2;
+const GROUP_SIZE_MAX: usize = 6;
+/// Share of new guests who ride snowboards rather than skis
+const SNOWBOARDER_SHARE: f32 = 0.3;
+
 const ABILITIES: [Ability; 3] = [Ability::Intermediate, Ability::Advanced, Ability::Expert];
 
 const SKI_COLORS: [Color; 5] = [
@@ -64,11 +74,23 @@ pub struct SelectParameters<'a> {
 }
 
 pub struct FinalizeParameters<'a> {
+    pub pistes: &'a HashMap<usize, Piste>,
+    pub piste_map: &'a Grid<Option<usize>>,
+    pub buildings: &'a mut HashMap<usize, Building>,
+    pub constructions: &'a mut construction::Queue,
+    pub building_artist: &'a mut building_artist::System,
+    pub economy: &'a mut economy::Service,
+    pub messenger: &'a mut messenger::System,
+}
+
+pub struct CompleteParameters<'a> {
     pub terrain: &'a Grid<f32>,
     pub id_allocator: &'a mut id_allocator::Service,
     pub buildings: &'a mut HashMap<usize, Building>,
     pub locations: &'a mut HashMap<usize, usize>,
     pub skiers: &'a mut HashMap<usize, Skier>,
+    pub groups: &'a mut HashMap<usize, Group>,
+    pub rating: &'a Rating,
     pub building_artist: &'a mut building_artist::System,
     pub window_artist: &'a mut window_artist::System,
     pub messenger: &'a mut messenger::System,
@@ -119,6 +141,7 @@ impl Controller {
 
         let building_id = id_allocator.next_id();
         let building = Building {
+            kind: Kind::Hotel,
             footprint: rectangle,
             height: HEIGHT_MIN,
             roof: Roof::Peaked,
@@ -143,13 +166,12 @@ impl Controller {
     pub fn finalize(
         &mut self,
         FinalizeParameters {
-            terrain,
-            id_allocator,
+            pistes,
+            piste_map,
             buildings,
-            locations,
-            skiers,
+            constructions,
             building_artist,
-            window_artist,
+            economy,
             messenger,
         }: FinalizeParameters<'_>,
     ) -> Result {
@@ -166,43 +188,166 @@ impl Controller {
             return NoAction;
         }
 
-        // creating skiers
+        if building.kind.is_terminal() && !is_terminal_site(&building.footprint, pistes, piste_map)
+        {
+            building.under_construction = true;
+            messenger.send(format!(
+                "{} must be at the edge of the map or next to a path",
+                building.kind.name()
+            ));
+            return NoAction;
+        }
+
+        // paying for the building, which stays in the editor if it cannot be afforded
 
-        building.windows = windows(terrain, &building.footprint, building.height);
+        let price = utils::construction::building_price(&building.footprint, building.height);
+        building.under_construction = true;
+        if !utils::construction::try_to_pay(
+            economy,
+            messenger,
+            &building.kind.name().to_lowercase(),
+            price,
+        ) {
+            return NoAction;
+        }
+
+        // queueing works, guests arrive once they complete
+
+        constructions.enqueue(
+            building_id,
+            construction::Kind::Building,
+            utils::construction::work_micros(price),
+        );
+        building_artist.redraw(building_id);
 
-        let capacity = building.windows.len();
-        messenger.send(format!("Spawing {} skiers", capacity));
+        self.state = State::Selecting;
+        Action
+    }
+}
 
-        let mut rng = thread_rng();
-        for _ in 0..capacity {
+/// Opens a building once its works complete
+///
+/// Hotels fill as many rooms with guests as the rating attracts
+pub fn complete(
+    building_id: &usize,
+    CompleteParameters {
+        terrain,
+        id_allocator,
+        buildings,
+        locations,
+        skiers,
+        groups,
+        rating,
+        building_artist,
+        window_artist,
+        messenger,
+    }: CompleteParameters<'_>,
+) {
+    let Some(building) = buildings.get_mut(building_id) else {
+        return;
+    };
+
+    building.under_construction = false;
+    building_artist.redraw(*building_id);
+
+    if building.kind != Kind::Hotel {
+        return;
+    }
+
+    // creating skiers
+
+    building.windows = windows(terrain, &building.footprint, building.height);
+    window_artist.update();
+
+    let capacity = (building.windows.len() as f32 * rating.occupancy()).round() as usize;
+    messenger.send(format!("Spawing {} skiers", capacity));
+
+    spawn_skiers(
+        building_id,
+        capacity,
+        id_allocator,
+        locations,
+        skiers,
+        groups,
+    );
+
+    messenger.send(format!("{} total skiers", skiers.len()));
+}
+
+/// Creates `count` skiers in groups inside the building
+pub fn spawn_skiers(
+    building_id: &usize,
+    count: usize,
+    id_allocator: &mut id_allocator::Service,
+    locations: &mut HashMap<usize, usize>,
+    skiers: &mut HashMap<usize, Skier>,
+    groups: &mut HashMap<usize, Group>,
+) {
+    let mut rng = thread_rng();
+    let mut remaining = count;
+    while remaining > 0 {
+        let group_size = rng
+            .gen_range(GROUP_SIZE_MIN..=GROUP_SIZE_MAX)
+            .min(remaining);
+        remaining -= group_size;
+
+        let group_id = id_allocator.next_id();
+        let mut member_ids = Vec::with_capacity(group_size);
+
+        for _ in 0..group_size {
             let skier_id = id_allocator.next_id();
 
-            locations.insert(skier_id, building_id);
+            locations.insert(skier_id, *building_id);
 
             skiers.insert(
                 skier_id,
                 Skier {
                     ability: *ABILITIES.choose(&mut rng).unwrap(),
+                    equipment: if rng.gen::<f32>() < SNOWBOARDER_SHARE {
+                        Equipment::Snowboard
+                    } else {
+                        Equipment::Skis
+                    },
                     clothes: Clothes {
                         skis: *SKI_COLORS.choose(&mut rng).unwrap(),
                         trousers: *SUIT_COLORS.choose(&mut rng).unwrap(),
                         jacket: *SUIT_COLORS.choose(&mut rng).unwrap(),
                         helmet: *HELMET_COLORS.choose(&mut rng).unwrap(),
                     },
-                    hotel_id: building_id,
+                    hotel_id: *building_id,
+                    group_id,
                 },
             );
+
+            member_ids.push(skier_id);
         }
 
-        messenger.send(format!("{} total skiers", skiers.len()));
+        groups.insert(group_id, Group { member_ids });
+    }
+}
 
-        building.under_construction = false;
-        building_artist.redraw(building_id);
-        window_artist.update();
+/// Whether day visitors could reach a building with this footprint, from beyond the map or along a path
+pub fn is_terminal_site(
+    footprint: &XYRectangle<u32>,
+    pistes: &HashMap<usize, Piste>,
+    piste_map: &Grid<Option<usize>>,
+) -> bool {
+    let XYRectangle { from, to } = footprint;
+    if from.x == 0 || from.y == 0 || to.x + 1 >= piste_map.width() || to.y + 1 >= piste_map.height()
+    {
+        return true;
+    }
 
-        self.state = State::Selecting;
-        Action
+    // cells touching the footprint
+    XYRectangle {
+        from: xy(from.x - 1, from.y - 1),
+        to: *to,
     }
+    .iter()
+    .filter(|cell| piste_map.in_bounds(cell))
+    .flat_map(|cell| piste_map[cell])
+    .flat_map(|piste_id| pistes.get(&piste_id))
+    .any(|piste| piste.class == piste::Class::Path)
 }
 
 pub fn windows(terrain: &Grid<f32>, footprint: &XYRectangle<u32>, height: u32) -> Vec<Window> {
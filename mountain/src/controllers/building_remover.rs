@@ -5,6 +5,8 @@ use crate::controllers::Result::{self, Action, NoAction};
 use crate::model::building::Building;
 use crate::model::entrance::Entrance;
 use crate::model::exit::Exit;
+use crate::model::ledger::Account;
+use crate::utils::{construction, skiers};
 use crate::{Components, Systems};
 
 pub fn trigger(
@@ -56,24 +58,15 @@ pub fn remove_building(
         .map(|(skier_id, _)| *skier_id)
         .collect::<Vec<_>>();
 
-    for skier_id in skiers_to_remove.iter() {
-        components.skiers.remove(skier_id);
-        components.plans.remove(skier_id);
-        components.locations.remove(skier_id);
-        components.targets.remove(skier_id);
-        components.global_targets.remove(skier_id);
-        components.frames.remove(skier_id);
-        components.clothes.remove(skier_id);
-        remove_dynamic_drawing(graphics, components, skier_id);
-    }
+    skiers::remove(graphics, components, &skiers_to_remove);
 
-    components
-        .planning_queue
-        .retain(|skier_id| !skiers_to_remove.contains(skier_id));
+    // skiers visiting the building go back to where they are staying
 
-    for position in components.reservations.iter() {
-        let reservations = &mut components.reservations[position];
-        reservations.retain(|id, _| !skiers_to_remove.contains(id));
+    for (skier_id, skier) in components.skiers.iter() {
+        if components.locations.get(skier_id) == Some(building_id) {
+            components.locations.insert(*skier_id, skier.hotel_id);
+            components.visits.remove(skier_id);
+        }
     }
 
     // removing doors
@@ -106,9 +99,30 @@ pub fn remove_building(
 
     // removing building
 
-    components.buildings.remove(building_id);
+    let Some(building) = components.buildings.remove(building_id) else {
+        return;
+    };
     remove_drawing(graphics, components, building_id);
 
+    // refunding building and doors, nothing was paid for a building still in the editor
+
+    let is_paid_for =
+        !building.under_construction || components.constructions.contains(building_id);
+    components.constructions.remove(building_id);
+    for door_id in doors_to_remove.iter() {
+        components.constructions.remove(door_id);
+    }
+    let building_price = if is_paid_for {
+        construction::building_price(&building.footprint, building.height)
+    } else {
+        0
+    };
+    let doors_price = doors_to_remove.len() as i64 * construction::DOOR_PRICE;
+    components.services.economy.record(
+        Account::Construction,
+        construction::refund(building_price + doors_price),
+    );
+
     // updating art
 
     systems.tree_artist.update();
@@ -120,14 +134,3 @@ fn remove_drawing(graphics: &mut dyn Graphics, components: &mut Components, id:
     }
     components.drawings.remove(id);
 }
-
-fn remove_dynamic_drawing(graphics: &mut dyn Graphics, components: &mut Components, id: &usize) {
-    if let Some(drawing_id) = components.drawings.get(id) {
-        let _ = graphics.update_dynamic_triangles(
-            drawing_id,
-            engine::graphics::DrawMode::Invisible,
-            &[],
-        );
-    }
-    components.drawings.remove(id);
-}
@@ -8,14 +8,16 @@ use crate::model::direction::{Direction, DIRECTIONS};
 use crate::model::door::Door;
 
 use crate::controllers::Result::{self, Action, NoAction};
+use crate::model::construction;
 use crate::model::entrance::Entrance;
 use crate::model::exit::Exit;
 use crate::model::open;
 use crate::model::piste::Piste;
 use crate::model::selection::Selection;
 use crate::model::skiing::State;
-use crate::services::id_allocator;
+use crate::services::{economy, id_allocator};
 use crate::systems::{messenger, piste_computer};
+use crate::utils;
 
 pub struct Parameters<'a> {
     pub pistes: &'a HashMap<usize, Piste>,
@@ -29,6 +31,8 @@ pub struct Parameters<'a> {
     pub parents: &'a mut HashMap<usize, usize>,
     pub children: &'a mut HashMap<usize, Vec<usize>>,
     pub piste_computer: &'a mut piste_computer::System,
+    pub constructions: &'a mut construction::Queue,
+    pub economy: &'a mut economy::Service,
     pub messenger: &'a mut messenger::System,
 }
 
@@ -45,6 +49,8 @@ pub fn trigger(
         parents,
         children,
         piste_computer,
+        constructions,
+        economy,
         messenger,
     }: Parameters<'_>,
 ) -> Result {
@@ -108,6 +114,12 @@ pub fn trigger(
         return NoAction;
     };
 
+    if !utils::construction::try_to_pay(economy, messenger, "door", utils::construction::DOOR_PRICE)
+    {
+        selection.cells.clear();
+        return NoAction;
+    }
+
     let aperture = piste_positions
         .iter()
         .enumerate()
@@ -143,7 +155,15 @@ pub fn trigger(
             stationary_states: stationary_states(&piste_positions),
         },
     );
-    open.insert(door_id, open::Status::Open);
+
+    // opening door once works complete
+
+    open.insert(door_id, open::Status::Closed);
+    constructions.enqueue(
+        door_id,
+        construction::Kind::Door,
+        utils::construction::work_micros(utils::construction::DOOR_PRICE),
+    );
 
     selection.cells.clear();
 
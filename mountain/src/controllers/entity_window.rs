@@ -8,14 +8,19 @@ use crate::model::gate::Gate;
 use crate::model::lift::Lift;
 use crate::model::piste::Piste;
 use crate::model::skiing::{Plan, State};
+use crate::model::snowmaking::{Cannon, WaterSource};
 use crate::widgets::entity_window::EntityWindow;
 
+const SNOWMAKING_SELECTION_RADIUS: f32 = 1.5;
+
 pub struct Parameters<'a> {
     pub mouse_xy: &'a Option<XY<u32>>,
     pub plans: &'a HashMap<usize, Plan>,
     pub lifts: &'a HashMap<usize, Lift>,
     pub gates: &'a HashMap<usize, Gate>,
     pub pistes: &'a HashMap<usize, Piste>,
+    pub cannons: &'a HashMap<usize, Cannon>,
+    pub water_sources: &'a HashMap<usize, WaterSource>,
     pub buildings: &'a HashMap<usize, Building>,
     pub windows: &'a mut HashMap<usize, EntityWindow>,
     pub graphics: &'a mut dyn engine::graphics::Graphics,
@@ -28,6 +33,8 @@ pub fn trigger(
         lifts,
         gates,
         pistes,
+        cannons,
+        water_sources,
         buildings,
         windows,
         graphics,
@@ -54,7 +61,10 @@ pub fn trigger(
     }
 
     for (lift_id, lift) in lifts.iter() {
-        if lift.pick_up.state.position == position || lift.drop_off.state.position == position {
+        if lift
+            .portals()
+            .any(|portal| portal.state.position == position)
+        {
             windows.insert(*lift_id, EntityWindow::new(*lift_id, *mouse_xy));
             return Action;
         }
@@ -67,6 +77,21 @@ pub fn trigger(
         }
     }
 
+    let nodes = cannons
+        .iter()
+        .map(|(cannon_id, cannon)| (cannon_id, cannon.position))
+        .chain(
+            water_sources
+                .iter()
+                .map(|(source_id, source)| (source_id, source.position)),
+        );
+    for (node_id, node) in nodes {
+        if xy(node.x as f32 - x, node.y as f32 - y).magnitude() <= SNOWMAKING_SELECTION_RADIUS {
+            windows.insert(*node_id, EntityWindow::new(*node_id, *mouse_xy));
+            return Action;
+        }
+    }
+
     for (piste_id, piste) in pistes.iter() {
         if piste.grid.in_bounds(position) && piste.grid[position] {
             windows.insert(*piste_id, EntityWindow::new(*piste_id, *mouse_xy));
@@ -5,6 +5,7 @@ use commons::grid::Grid;
 
 use crate::controllers;
 use crate::controllers::Result::{Action, NoAction};
+use crate::model::construction;
 use crate::model::direction::DIRECTIONS;
 use crate::model::entrance::Entrance;
 use crate::model::exit::Exit;
@@ -13,8 +14,9 @@ use crate::model::open;
 use crate::model::reservation::Reservation;
 use crate::model::selection::Selection;
 use crate::model::skiing::State;
-use crate::services::id_allocator;
+use crate::services::{economy, id_allocator};
 use crate::systems::{messenger, piste_computer, terrain_artist};
+use crate::utils;
 
 const ZERO_DIMENSION_ERROR_MESSAGE: &str = "Selection must not have 0 width or 0 height";
 const WRONG_DIMENSION_ERROR_MESSAGE: &str = "Selection must be 2 wide or 2 high";
@@ -34,6 +36,8 @@ pub struct Parameters<'a> {
     pub open: &'a mut HashMap<usize, open::Status>,
     pub reservations: &'a mut Grid<HashMap<usize, Reservation>>,
     pub piste_computer: &'a mut piste_computer::System,
+    pub constructions: &'a mut construction::Queue,
+    pub economy: &'a mut economy::Service,
     pub messenger: &'a mut messenger::System,
 }
 
@@ -49,6 +53,8 @@ pub fn trigger(
         open,
         reservations,
         piste_computer,
+        constructions,
+        economy,
         messenger,
     }: Parameters<'_>,
 ) -> controllers::Result {
@@ -106,6 +112,11 @@ pub fn trigger(
         },
     };
 
+    if !utils::construction::try_to_pay(economy, messenger, "gate", utils::construction::GATE_PRICE)
+    {
+        return NoAction;
+    }
+
     // creating gate
 
     let gate_id = id_allocator.next_id();
@@ -148,9 +159,14 @@ pub fn trigger(
         },
     );
 
-    // opening gate
+    // opening gate once works complete
 
-    open.insert(gate_id, open::Status::Open);
+    open.insert(gate_id, open::Status::Closed);
+    constructions.enqueue(
+        gate_id,
+        construction::Kind::Gate,
+        utils::construction::work_micros(utils::construction::GATE_PRICE),
+    );
 
     // inserting gate
 
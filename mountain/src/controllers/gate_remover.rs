@@ -7,8 +7,10 @@ use crate::model::ability::ABILITIES;
 use crate::model::entrance::Entrance;
 use crate::model::exit::Exit;
 use crate::model::gate::Gate;
+use crate::model::ledger::Account;
 use crate::model::open;
 use crate::systems::{messenger, piste_computer};
+use crate::utils::construction;
 use crate::Components;
 
 pub fn trigger(
@@ -68,6 +70,7 @@ pub fn remove_gate(
 
     let gate = components.gates.remove(gate_id);
     components.open.remove(gate_id);
+    components.constructions.remove(gate_id);
     if let Some(Entrance {
         destination_piste_id,
         ..
@@ -86,6 +89,10 @@ pub fn remove_gate(
         gate.footprint.iter().for_each(|position| {
             components.reservations[position].remove(gate_id);
         });
+        components.services.economy.record(
+            Account::Construction,
+            construction::refund(construction::GATE_PRICE),
+        );
     }
 
     remove_drawing(graphics, components, gate_id);
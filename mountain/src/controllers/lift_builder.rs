@@ -1,28 +1,55 @@
 use std::collections::{HashMap, HashSet};
+use std::iter::once;
 
 use commons::geometry::{xy, XY, XYZ};
 use commons::grid::Grid;
+use commons::map::ContainsKeyValue;
+use engine::graphics::DrawMode;
 
 use crate::controllers::Result::{self, Action, NoAction};
 use crate::model::carousel::{Car, Carousel};
-use crate::model::direction::Direction;
+use crate::model::construction;
 use crate::model::entrance::Entrance;
 use crate::model::exit::Exit;
+use crate::model::frame::Frame;
+use crate::model::ledger::Account;
 use crate::model::lift::{self, Lift, Segment};
 use crate::model::lift_building::{LiftBuilding, LiftBuildingClass, LiftBuildings};
+use crate::model::lift_queue::{LiftQueue, Maze};
+use crate::model::lift_type::LiftType;
 use crate::model::open;
+use crate::model::reliability::{self, Reliability};
 use crate::model::reservation::Reservation;
 use crate::model::skiing::State;
 use crate::network::velocity_encoding::{encode_velocity, VELOCITY_LEVELS};
-use crate::services::id_allocator;
+use crate::services::{economy, id_allocator};
 use crate::systems::{lift_building_artist, messenger, piste_computer};
 use crate::utils;
+use crate::utils::wire_validation::violations;
 
-pub const LIFT_VELOCITY: f32 = 2.0;
-pub const CAR_INTERVAL_METERS: f32 = 10.0;
+pub const DEFAULT_MAZE: Maze = Maze {
+    lanes: 2,
+    lane_length: 8,
+};
+pub const MEAN_MICROS_BETWEEN_BREAKDOWNS: u128 = 6 * 60 * 60 * 1_000_000;
+pub const REPAIR_MICROS: u128 = 20 * 60 * 1_000_000;
+pub const MAINTENANCE_INTERVAL_MICROS: u128 = 24 * 60 * 60 * 1_000_000;
+pub const MAINTENANCE_MICROS: u128 = 60 * 60 * 1_000_000;
+
+pub const SELECTION_RADIUS_METERS: f32 = 3.0;
 
 pub struct Controller {
     lift_building_id: Option<usize>,
+    lift_type: LiftType,
+    editing: Option<Editing>,
+}
+
+struct Editing {
+    lift_id: usize,
+    original: Vec<LiftBuilding>,
+    original_cable_tension: f32,
+    selected: Option<usize>,
+    dragging: bool,
 }
 
 pub struct TriggerParameters<'a> {
@@ -35,12 +62,18 @@ pub struct TriggerParameters<'a> {
     pub id_allocator: &'a mut id_allocator::Service,
     pub carousels: &'a mut HashMap<usize, Carousel>,
     pub cars: &'a mut HashMap<usize, Car>,
+    pub frames: &'a mut HashMap<usize, Option<Frame>>,
+    pub lift_queues: &'a mut HashMap<usize, LiftQueue>,
+    pub reliabilities: &'a mut HashMap<usize, Reliability>,
     pub exits: &'a mut HashMap<usize, Exit>,
     pub entrances: &'a mut HashMap<usize, Entrance>,
     pub reservations: &'a mut Grid<HashMap<usize, Reservation>>,
     pub parents: &'a mut HashMap<usize, usize>,
     pub children: &'a mut HashMap<usize, Vec<usize>>,
+    pub drawings: &'a mut HashMap<usize, usize>,
     pub piste_computer: &'a mut piste_computer::System,
+    pub constructions: &'a mut construction::Queue,
+    pub economy: &'a mut economy::Service,
     pub messenger: &'a mut messenger::System,
     pub lift_building_artist: &'a mut lift_building_artist::System,
     pub graphics: &'a mut dyn engine::graphics::Graphics,
@@ -58,6 +91,8 @@ impl Controller {
     pub fn new() -> Controller {
         Controller {
             lift_building_id: None,
+            lift_type: LiftType::Chairlift,
+            editing: None,
         }
     }
 
@@ -65,12 +100,115 @@ impl Controller {
         &self.lift_building_id
     }
 
+    pub fn lift_type(&self) -> &LiftType {
+        &self.lift_type
+    }
+
+    pub fn set_lift_type(&mut self, lift_type: LiftType) {
+        self.lift_type = lift_type;
+    }
+
+    pub fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+
+    /// Buildings of the lift being edited as they were before editing started
+    pub fn original_buildings(&self) -> Option<&[LiftBuilding]> {
+        self.editing
+            .as_ref()
+            .map(|editing| editing.original.as_slice())
+    }
+
+    /// Building affected by the lift editor - the selected building when editing, otherwise the last one
+    pub fn current_building_index(&self, lift_buildings: &LiftBuildings) -> Option<usize> {
+        match &self.editing {
+            Some(editing) => editing.selected,
+            None => lift_buildings.buildings.len().checked_sub(1),
+        }
+    }
+
+    pub fn insert_pylon(&mut self, lift_buildings: &mut LiftBuildings) {
+        let Some(editing) = self.editing.as_mut() else {
+            return;
+        };
+        let Some(selected) = editing.selected else {
+            return;
+        };
+        let buildings = &mut lift_buildings.buildings;
+        if buildings.len() < 2 {
+            return;
+        }
+
+        // new pylon goes halfway to the next building, or the previous one for the last building
+        let index = (selected + 1).min(buildings.len() - 1);
+        let from = buildings[index - 1].position;
+        let to = buildings[index].position;
+        buildings.insert(
+            index,
+            LiftBuilding {
+                class: LiftBuildingClass::Pylon,
+                position: xy((from.x + to.x) / 2, (from.y + to.y) / 2),
+                yaw: 0.0,
+                manual_yaw: false,
+            },
+        );
+        lift_buildings.align_yaws();
+
+        editing.selected = Some(index);
+        editing.dragging = false;
+    }
+
+    pub fn remove_pylon(&mut self, lift_buildings: &mut LiftBuildings) {
+        let Some(editing) = self.editing.as_mut() else {
+            return;
+        };
+        let Some(selected) = editing.selected else {
+            return;
+        };
+        if lift_buildings
+            .buildings
+            .get(selected)
+            .map(|building| building.class)
+            != Some(LiftBuildingClass::Pylon)
+        {
+            return;
+        }
+
+        lift_buildings.buildings.remove(selected);
+        lift_buildings.align_yaws();
+
+        editing.selected = None;
+        editing.dragging = false;
+    }
+
+    pub fn cancel_editing(&mut self, lift_buildings: &mut HashMap<usize, LiftBuildings>) {
+        let Some(editing) = self.editing.take() else {
+            return;
+        };
+        if let Some(lift_building_id) = self.lift_building_id.take() {
+            if let Some(lift_buildings) = lift_buildings.get_mut(&lift_building_id) {
+                lift_buildings.buildings = editing.original;
+                lift_buildings.cable_tension = editing.original_cable_tension;
+            }
+        }
+    }
+
     pub fn trigger(&mut self, parameters: TriggerParameters) -> Result {
+        if self.editing.is_some() {
+            return self.edit(parameters);
+        }
+
+        let lift_type = self.lift_type;
         let lift_building_id = self.lift_building_id.get_or_insert_with(|| {
             let id = parameters.id_allocator.next_id();
-            parameters
-                .lift_buildings
-                .insert(id, LiftBuildings { buildings: vec![] });
+            parameters.lift_buildings.insert(
+                id,
+                LiftBuildings {
+                    lift_type,
+                    buildings: vec![],
+                    cable_tension: lift_type.cable_tension(),
+                },
+            );
             id
         });
         let Some(lift_buildings) = parameters.lift_buildings.get_mut(lift_building_id) else {
@@ -98,12 +236,88 @@ impl Controller {
             class: next_class(last_lift_building.map(|building| building.class)),
             position,
             yaw: 0.0,
+            manual_yaw: false,
         });
+        lift_buildings.align_yaws();
         parameters.lift_building_artist.redraw(*lift_building_id);
 
         Action
     }
 
+    /// Starts editing a closed lift, returning whether editing started
+    pub fn start_editing(
+        &mut self,
+        lift_id: &usize,
+        lifts: &HashMap<usize, Lift>,
+        open: &HashMap<usize, open::Status>,
+        lift_buildings: &HashMap<usize, LiftBuildings>,
+        messenger: &mut messenger::System,
+    ) -> bool {
+        if self.lift_building_id.is_some() {
+            messenger.send("Finish building the current lift before editing another");
+            return false;
+        }
+        if !open.contains_key_value(lift_id, open::Status::Closed) {
+            messenger.send(format!(
+                "Lift {} must be closed before it can be edited",
+                lift_id
+            ));
+            return false;
+        }
+        let Some(lift) = lifts.get(lift_id) else {
+            return false;
+        };
+        let Some(buildings) = lift_buildings.get(&lift.buildings_id) else {
+            return false;
+        };
+
+        self.lift_building_id = Some(lift.buildings_id);
+        self.editing = Some(Editing {
+            lift_id: *lift_id,
+            original: buildings.buildings.clone(),
+            original_cable_tension: buildings.cable_tension,
+            selected: None,
+            dragging: false,
+        });
+
+        true
+    }
+
+    fn edit(&mut self, parameters: TriggerParameters) -> Result {
+        let Some(editing) = self.editing.as_mut() else {
+            return NoAction;
+        };
+
+        // drop building being dragged
+
+        if editing.dragging {
+            editing.dragging = false;
+            return Action;
+        }
+
+        // or pick up another building
+
+        let Some(position) =
+            get_position(parameters.mouse_xy, parameters.terrain, parameters.graphics)
+        else {
+            return NoAction;
+        };
+        let index = self
+            .lift_building_id
+            .and_then(|id| parameters.lift_buildings.get(&id))
+            .and_then(|lift_buildings| building_at(&position, lift_buildings));
+        if let Some(index) = index {
+            editing.selected = Some(index);
+            editing.dragging = true;
+            return Action;
+        }
+
+        // or commit changes
+
+        self.create_lift(parameters);
+        Action
+    }
+
     fn create_lift(
         &mut self,
         TriggerParameters {
@@ -115,13 +329,21 @@ impl Controller {
             id_allocator,
             carousels,
             cars,
+            frames,
+            lift_queues,
+            reliabilities,
             exits,
             entrances,
             reservations,
             parents,
             children,
+            drawings,
             piste_computer,
+            constructions,
+            economy,
             messenger,
+            lift_building_artist,
+            graphics,
             ..
         }: TriggerParameters<'_>,
     ) {
@@ -155,10 +377,172 @@ impl Controller {
             return;
         };
 
-        let lift_id = id_allocator.next_id();
-        let carousel_id = id_allocator.next_id();
-        let pick_up_id = id_allocator.next_id();
-        let drop_off_id = id_allocator.next_id();
+        let mid_stations = lift_buildings.get_mid_stations(terrain);
+        let mut mid_station_piste_ids = Vec::with_capacity(mid_stations.len());
+        for mid_station in mid_stations.iter() {
+            let Some(drop_off_piste_id) = piste_map[mid_station.drop_off.position] else {
+                messenger.send("Mid station needs piste at drop off position!");
+                return;
+            };
+            let Some(pick_up_piste_id) = piste_map[mid_station.pick_up.position] else {
+                messenger.send("Mid station needs piste at pick up position!");
+                return;
+            };
+            mid_station_piste_ids.push((drop_off_piste_id, pick_up_piste_id));
+        }
+
+        let lift_type = lift_buildings.lift_type;
+        let run = xy(
+            drop_off.position.x as f32 - pick_up.position.x as f32,
+            drop_off.position.y as f32 - pick_up.position.y as f32,
+        )
+        .magnitude();
+        let rise = (terrain[drop_off.position] - terrain[pick_up.position]).abs();
+        let length = (run * run + rise * rise).sqrt();
+        if length > lift_type.max_length_meters() {
+            messenger.send(format!(
+                "Lift is too long: maximum length is {}m!",
+                lift_type.max_length_meters()
+            ));
+            return;
+        }
+        if rise > run * lift_type.max_slope() {
+            messenger.send(format!(
+                "Lift is too steep: maximum slope is {}%!",
+                (lift_type.max_slope() * 100.0).round()
+            ));
+            return;
+        }
+
+        let cables = lift_buildings.cables(terrain);
+        if let Some(violation) = violations(&lift_type, &cables, terrain)
+            .into_iter()
+            .flatten()
+            .next()
+        {
+            messenger.send(violation.message(&lift_type));
+            return;
+        }
+
+        // replacing the lift being edited, reusing its ids so targets stay valid
+
+        if let Some(editing) = &self.editing {
+            if !open.contains_key_value(editing.lift_id, open::Status::Closed) {
+                messenger.send(format!(
+                    "Lift {} must be closed before it can be edited",
+                    editing.lift_id
+                ));
+                return;
+            }
+        }
+
+        // fitting a queue behind each pick up, keeping the mazes of the lift being edited
+
+        let old_mazes = self
+            .editing
+            .as_ref()
+            .and_then(|editing| lifts.get(&editing.lift_id))
+            .map(|lift| {
+                lift.pick_ups()
+                    .map(|pick_up| lift_queues.get(&pick_up.id).map(|queue| queue.maze))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        let pick_ups = once((&pick_up, origin_piste_id)).chain(
+            mid_stations
+                .iter()
+                .zip(mid_station_piste_ids.iter())
+                .map(|(mid_station, &(_, piste_id))| (&mid_station.pick_up, piste_id)),
+        );
+        let mut mazes = Vec::with_capacity(mid_stations.len() + 1);
+        for (index, (transfer, piste_id)) in pick_ups.enumerate() {
+            let state = State {
+                position: transfer.position,
+                velocity: 0,
+                travel_direction: transfer.travel_direction,
+            };
+            let preferred = old_mazes
+                .get(index)
+                .copied()
+                .flatten()
+                .unwrap_or(DEFAULT_MAZE);
+            let Some(maze) =
+                utils::lift_queue::fit_maze(&preferred, &state, &piste_id, terrain, piste_map)
+            else {
+                messenger.send("Lift needs room for a queue behind each pick up!");
+                return;
+            };
+            mazes.push(maze);
+        }
+
+        // paying for the lift, or the difference when editing
+
+        let price = utils::construction::lift_price(lift_buildings, terrain);
+        let original_price = self
+            .editing
+            .as_ref()
+            .map(|editing| {
+                let original = LiftBuildings {
+                    lift_type,
+                    buildings: editing.original.clone(),
+                    cable_tension: lift_buildings.cable_tension,
+                };
+                utils::construction::lift_price(&original, terrain)
+            })
+            .unwrap_or_default();
+        let difference = price - original_price;
+        if difference > 0 {
+            if !utils::construction::try_to_pay(economy, messenger, "lift", difference) {
+                return;
+            }
+        } else {
+            economy.record(
+                Account::Construction,
+                utils::construction::refund(-difference),
+            );
+        }
+
+        let existing = self
+            .editing
+            .take()
+            .and_then(|editing| Some((editing.lift_id, lifts.remove(&editing.lift_id)?)));
+        let mut old_queues = HashMap::new();
+        let mut old_piste_ids = vec![];
+        if let Some((lift_id, lift)) = &existing {
+            for portal in lift.portals() {
+                parents.remove(&portal.id);
+                reservations[portal.state.position].remove(&portal.id);
+                if let Some(queue) = lift_queues.remove(&portal.id) {
+                    utils::lift_queue::release_maze(portal, &queue.maze, reservations);
+                    old_queues.insert(portal.id, queue);
+                }
+                if let Some(exit) = exits.remove(&portal.id) {
+                    old_piste_ids.push(exit.origin_piste_id);
+                }
+                if let Some(entrance) = entrances.remove(&portal.id) {
+                    old_piste_ids.push(entrance.destination_piste_id);
+                }
+            }
+            children.remove(lift_id);
+            if let Some(drawing_id) = drawings.remove(lift_id) {
+                let _ = graphics.draw_triangles(&drawing_id, DrawMode::Invisible, &[]);
+            }
+        }
+        let old_lift = existing.as_ref().map(|(_, lift)| lift);
+
+        let lift_id = existing
+            .as_ref()
+            .map(|(lift_id, _)| *lift_id)
+            .unwrap_or_else(|| id_allocator.next_id());
+        let carousel_id = old_lift
+            .map(|lift| lift.carousel_id)
+            .unwrap_or_else(|| id_allocator.next_id());
+        let pick_up_id = old_lift
+            .map(|lift| lift.pick_up.id)
+            .unwrap_or_else(|| id_allocator.next_id());
+        let drop_off_id = old_lift
+            .map(|lift| lift.drop_off.id)
+            .unwrap_or_else(|| id_allocator.next_id());
 
         children.entry(lift_id).or_default().append(&mut vec![
             carousel_id,
@@ -169,21 +553,52 @@ impl Controller {
         parents.entry(pick_up_id).insert_entry(lift_id);
         parents.entry(drop_off_id).insert_entry(lift_id);
 
-        let segments = lift_buildings
-            .wire_path(terrain)
-            .drain(..)
+        let mid_station_ids = (0..mid_stations.len())
+            .map(|index| {
+                old_lift
+                    .and_then(|lift| lift.mid_stations.get(index))
+                    .map(|station| (station.drop_off.id, station.pick_up.id))
+                    .unwrap_or_else(|| (id_allocator.next_id(), id_allocator.next_id()))
+            })
+            .collect::<Vec<_>>();
+
+        // removing mid stations that no longer exist
+
+        if let Some(lift) = old_lift {
+            for station in lift.mid_stations.iter().skip(mid_stations.len()) {
+                open.remove(&station.drop_off.id);
+                open.remove(&station.pick_up.id);
+            }
+        }
+        for &(mid_drop_off_id, mid_pick_up_id) in mid_station_ids.iter() {
+            children
+                .entry(lift_id)
+                .or_default()
+                .append(&mut vec![mid_drop_off_id, mid_pick_up_id]);
+            parents.entry(mid_drop_off_id).insert_entry(lift_id);
+            parents.entry(mid_pick_up_id).insert_entry(lift_id);
+        }
+
+        let segments = cables
+            .into_iter()
+            .flatten()
             .map(|segment| Segment::new(segment[0], segment[1]))
             .collect();
-        let travel_direction = get_direction(&pick_up.position, &drop_off.position);
+        let velocity = carousels
+            .get(&carousel_id)
+            .map(|carousel| carousel.velocity)
+            .unwrap_or_else(|| lift_type.velocity());
+        let drop_off_velocity = encode_velocity(&velocity).unwrap_or(VELOCITY_LEVELS - 1);
 
         let lift = Lift {
+            lift_type,
             segments,
             pick_up: lift::Portal {
                 id: pick_up_id,
                 segment: pick_up.global_segment,
                 state: State {
                     position: pick_up.position,
-                    travel_direction,
+                    travel_direction: pick_up.travel_direction,
                     velocity: 0,
                 },
             },
@@ -192,24 +607,64 @@ impl Controller {
                 segment: drop_off.global_segment,
                 state: State {
                     position: drop_off.position,
-                    travel_direction,
-                    velocity: encode_velocity(&LIFT_VELOCITY).unwrap_or(VELOCITY_LEVELS - 1),
+                    travel_direction: drop_off.travel_direction,
+                    velocity: drop_off_velocity,
                 },
             },
+            mid_stations: mid_stations
+                .iter()
+                .zip(mid_station_ids.iter())
+                .map(
+                    |(mid_station, &(mid_drop_off_id, mid_pick_up_id))| lift::MidStation {
+                        drop_off: lift::Portal {
+                            id: mid_drop_off_id,
+                            segment: mid_station.drop_off.global_segment,
+                            state: State {
+                                position: mid_station.drop_off.position,
+                                travel_direction: mid_station.drop_off.travel_direction,
+                                velocity: drop_off_velocity,
+                            },
+                        },
+                        pick_up: lift::Portal {
+                            id: mid_pick_up_id,
+                            segment: mid_station.pick_up.global_segment,
+                            state: State {
+                                position: mid_station.pick_up.position,
+                                travel_direction: mid_station.pick_up.travel_direction,
+                                velocity: 0,
+                            },
+                        },
+                    },
+                )
+                .collect(),
             carousel_id,
             buildings_id: editing,
         };
 
-        // opening lift
-
-        open.insert(lift_id, open::Status::Open);
-        open.insert(lift.pick_up.id, open::Status::Open);
-        open.insert(lift.drop_off.id, open::Status::Open);
+        // new lifts stay closed until their works complete, edited lifts keep their status
+        let status = open.get(&lift_id).copied().unwrap_or(open::Status::Closed);
+        open.insert(lift_id, status);
+        for portal in lift.portals() {
+            open.insert(portal.id, status);
+        }
 
         // setup carousel
 
+        if let Some(carousel) = carousels.get_mut(&carousel_id) {
+            for car_id in carousel.car_ids.drain(..) {
+                cars.remove(&car_id);
+                frames.remove(&car_id);
+                parents.remove(&car_id);
+            }
+        }
+        children.remove(&carousel_id);
+
+        let car_interval_meters = carousels
+            .get(&carousel_id)
+            .map(|carousel| carousel.car_interval_meters)
+            .unwrap_or_else(|| lift_type.car_interval_meters());
         let new_cars =
-            utils::carousel::create_cars(carousel_id, &lift.segments, &CAR_INTERVAL_METERS);
+            utils::carousel::create_cars(carousel_id, &lift.segments, &car_interval_meters);
 
         let car_ids = (0..new_cars.len())
             .map(|_| id_allocator.next_id())
@@ -225,46 +680,100 @@ impl Controller {
             parents.insert(*id, carousel_id);
         });
 
-        carousels.insert(
-            carousel_id,
-            Carousel {
+        carousels
+            .entry(carousel_id)
+            .or_insert_with(|| Carousel {
                 lift_id,
-                velocity: LIFT_VELOCITY,
-                car_ids,
-            },
-        );
+                velocity: lift_type.velocity(),
+                car_interval_meters: lift_type.car_interval_meters(),
+                car_class: lift_type.default_car_class(),
+                car_ids: vec![],
+            })
+            .car_ids = car_ids;
 
-        // setup exit
+        // setup queues
 
-        exits.insert(
-            lift.pick_up.id,
-            Exit {
-                origin_piste_id,
-                stationary_states: HashSet::from([lift.pick_up.state.stationary()]),
-            },
-        );
+        for (pick_up, maze) in lift.pick_ups().zip(mazes) {
+            let mut queue = old_queues
+                .remove(&pick_up.id)
+                .unwrap_or_else(|| LiftQueue::new(maze));
+            queue.maze = maze;
+            lift_queues.insert(pick_up.id, queue);
+        }
 
-        // setup entrance
+        // setup reliability
 
-        entrances.insert(
-            lift.drop_off.id,
-            Entrance {
-                destination_piste_id,
-                stationary_states: HashSet::from([lift.drop_off.state.stationary()]),
-            },
-        );
+        reliabilities.entry(lift_id).or_insert_with(|| Reliability {
+            mean_micros_between_breakdowns: MEAN_MICROS_BETWEEN_BREAKDOWNS,
+            repair_micros: REPAIR_MICROS,
+            maintenance_interval_micros: MAINTENANCE_INTERVAL_MICROS,
+            maintenance_micros: MAINTENANCE_MICROS,
+            last_maintenance_micros: None,
+            status: reliability::Status::Running,
+        });
 
-        // reserve pick up position
+        // setup exits
 
-        reservations[lift.pick_up.state.position].insert(lift.pick_up.id, Reservation::Structure);
+        let origin_piste_ids = once(origin_piste_id)
+            .chain(mid_station_piste_ids.iter().map(|&(_, piste_id)| piste_id))
+            .collect::<Vec<_>>();
+        for (pick_up, piste_id) in lift.pick_ups().zip(origin_piste_ids.iter()) {
+            exits.insert(
+                pick_up.id,
+                Exit {
+                    origin_piste_id: *piste_id,
+                    stationary_states: HashSet::from([pick_up.state.stationary()]),
+                },
+            );
+        }
+
+        // setup entrances
+
+        let destination_piste_ids = mid_station_piste_ids
+            .iter()
+            .map(|&(piste_id, _)| piste_id)
+            .chain(once(destination_piste_id))
+            .collect::<Vec<_>>();
+        for (drop_off, piste_id) in lift.drop_offs().zip(destination_piste_ids.iter()) {
+            entrances.insert(
+                drop_off.id,
+                Entrance {
+                    destination_piste_id: *piste_id,
+                    stationary_states: HashSet::from([drop_off.state.stationary()]),
+                },
+            );
+        }
+
+        // reserve pick up positions and queues
+
+        for pick_up in lift.pick_ups() {
+            reservations[pick_up.state.position].insert(pick_up.id, Reservation::Structure);
+            if let Some(queue) = lift_queues.get(&pick_up.id) {
+                utils::lift_queue::reserve_maze(pick_up, &queue.maze, reservations);
+            }
+        }
 
         // register lift
 
         lifts.insert(lift_id, lift);
 
         // recomputing pistes
-        piste_computer.compute(origin_piste_id);
-        piste_computer.compute(destination_piste_id);
+        for piste_id in origin_piste_ids
+            .iter()
+            .chain(destination_piste_ids.iter())
+            .chain(old_piste_ids.iter())
+        {
+            piste_computer.compute(*piste_id);
+        }
+
+        // queueing works, the lift stays closed until they complete
+
+        constructions.enqueue(
+            lift_id,
+            construction::Kind::Lift,
+            utils::construction::work_micros(difference.abs()),
+        );
+        lift_building_artist.redraw(editing);
 
         // clear editing
 
@@ -288,35 +797,48 @@ impl Controller {
         let Some(lift_buildings) = lift_buildings.get_mut(&lift_building_id) else {
             return;
         };
-        let lift_buildings = &mut lift_buildings.buildings;
-
-        if lift_buildings.is_empty() {
+        let index = match &self.editing {
+            Some(Editing {
+                selected: Some(selected),
+                dragging: true,
+                ..
+            }) => Some(*selected),
+            Some(_) => None,
+            None => lift_buildings.buildings.len().checked_sub(1),
+        };
+        let Some(building) = index.and_then(|index| lift_buildings.buildings.get_mut(index)) else {
             return;
-        }
+        };
 
         let Some(position) = get_position(mouse_xy, terrain, graphics) else {
             return;
         };
 
-        let lift_building_count = lift_buildings.len();
-        if lift_building_count >= 2 {
-            let penultimate_building = &mut lift_buildings[lift_building_count - 2];
-            let yaw = (xy(position.x as f32, position.y as f32)
-                - xy(
-                    penultimate_building.position.x as f32,
-                    penultimate_building.position.y as f32,
-                ))
-            .angle();
-            penultimate_building.yaw = yaw;
-            lift_buildings.last_mut().unwrap().yaw = yaw;
-        }
-
-        lift_buildings.last_mut().unwrap().position = position;
+        building.position = position;
+        lift_buildings.align_yaws();
 
         lift_building_artist.redraw(lift_building_id);
     }
 }
 
+fn building_at(position: &XY<u32>, lift_buildings: &LiftBuildings) -> Option<usize> {
+    lift_buildings
+        .buildings
+        .iter()
+        .enumerate()
+        .map(|(index, building)| {
+            let distance = xy(
+                building.position.x as f32 - position.x as f32,
+                building.position.y as f32 - position.y as f32,
+            )
+            .magnitude();
+            (index, distance)
+        })
+        .filter(|(_, distance)| *distance <= SELECTION_RADIUS_METERS)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(index, _)| index)
+}
+
 fn get_position(
     mouse_xy: &Option<XY<u32>>,
     terrain: &Grid<f32>,
@@ -343,7 +865,481 @@ fn next_class(class: Option<LiftBuildingClass>) -> LiftBuildingClass {
     }
 }
 
-fn get_direction(from: &XY<u32>, to: &XY<u32>) -> Direction {
-    let vector = xy(to.x as f32 - from.x as f32, to.y as f32 - from.y as f32);
-    Direction::snap_to_direction(vector.angle())
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use commons::color::{Rgb, Rgba};
+    use commons::geometry::{xyz, Rectangle};
+    use commons::origin_grid::OriginGrid;
+    use engine::egui;
+    use engine::graphics::elements::{Billboard, OverlayTriangles, Triangle};
+    use engine::graphics::errors::{
+        DrawError, IndexError, InitializationError, RenderError, ScreenshotError,
+    };
+    use engine::graphics::projections::isometric;
+    use engine::graphics::{Graphics, Projection};
+    use nalgebra::Matrix4;
+    use tokio::sync::broadcast;
+
+    use super::*;
+
+    /// Draws nothing, so lifts can be built and edited without a window
+    struct NoGraphics {
+        projection: Box<dyn Projection>,
+    }
+
+    impl NoGraphics {
+        fn new() -> NoGraphics {
+            NoGraphics {
+                projection: Box::new(isometric::Projection::new(isometric::Parameters {
+                    projection: isometric::ProjectionParameters {
+                        pitch: PI / 4.0,
+                        yaw: PI * (5.0 / 8.0),
+                    },
+                    scale: isometric::ScaleParameters {
+                        zoom: 1.0,
+                        z_max: 1.0,
+                        viewport: Rectangle {
+                            width: 512,
+                            height: 512,
+                        },
+                    },
+                })),
+            }
+        }
+    }
+
+    impl Graphics for NoGraphics {
+        fn load_texture(
+            &mut self,
+            _: &Grid<Rgba<u8>>,
+        ) -> std::result::Result<usize, InitializationError> {
+            Ok(0)
+        }
+
+        fn load_texture_from_file(
+            &mut self,
+            _: &str,
+        ) -> std::result::Result<usize, InitializationError> {
+            Ok(0)
+        }
+
+        fn modify_texture(
+            &mut self,
+            _: &usize,
+            _: &OriginGrid<Rgba<u8>>,
+        ) -> std::result::Result<(), DrawError> {
+            Ok(())
+        }
+
+        fn create_triangles(&mut self) -> std::result::Result<usize, IndexError> {
+            Ok(0)
+        }
+
+        fn create_dynamic_triangles(
+            &mut self,
+            _: &usize,
+        ) -> std::result::Result<usize, IndexError> {
+            Ok(0)
+        }
+
+        fn create_overlay_triangles(&mut self) -> std::result::Result<usize, IndexError> {
+            Ok(0)
+        }
+
+        fn create_instanced_triangles(
+            &mut self,
+            _: DrawMode,
+            _: &[Triangle<Rgb<f32>>],
+        ) -> std::result::Result<usize, IndexError> {
+            Ok(0)
+        }
+
+        fn create_billboards(&mut self) -> std::result::Result<usize, IndexError> {
+            Ok(0)
+        }
+
+        fn draw_triangles(
+            &mut self,
+            _: &usize,
+            _: DrawMode,
+            _: &[Triangle<Rgb<f32>>],
+        ) -> std::result::Result<(), DrawError> {
+            Ok(())
+        }
+
+        fn update_dynamic_triangles(
+            &mut self,
+            _: &usize,
+            _: DrawMode,
+            _: &[Triangle<Rgb<f32>>],
+        ) -> std::result::Result<(), DrawError> {
+            Ok(())
+        }
+
+        fn update_instanced_triangles(
+            &mut self,
+            _: &usize,
+            _: &[Option<Matrix4<f32>>],
+        ) -> std::result::Result<(), DrawError> {
+            Ok(())
+        }
+
+        fn draw_overlay_triangles(
+            &mut self,
+            _: &usize,
+            _: DrawMode,
+            _: &OverlayTriangles,
+        ) -> std::result::Result<(), DrawError> {
+            Ok(())
+        }
+
+        fn draw_billboard(
+            &mut self,
+            _: &usize,
+            _: DrawMode,
+            _: &Billboard,
+        ) -> std::result::Result<(), DrawError> {
+            Ok(())
+        }
+
+        fn draw_gui(&mut self, _: &mut dyn FnMut(&egui::Context)) {}
+
+        fn render(&mut self) -> std::result::Result<(), RenderError> {
+            Ok(())
+        }
+
+        fn screenshot(&self, _: &str) -> std::result::Result<(), ScreenshotError> {
+            Ok(())
+        }
+
+        fn look_at(&mut self, _: &XYZ<f32>, _: &XY<u32>) {}
+
+        fn world_xyz_at(&mut self, _: &XY<u32>) -> std::result::Result<XYZ<f32>, IndexError> {
+            Ok(xyz(0.0, 0.0, 0.0))
+        }
+
+        fn projection(&mut self) -> &mut Box<dyn Projection> {
+            &mut self.projection
+        }
+
+        fn clear(&mut self) {}
+    }
+
+    const LIFT_BUILDINGS_ID: usize = 1;
+
+    struct World {
+        terrain: Grid<f32>,
+        piste_map: Grid<Option<usize>>,
+        lift_buildings: HashMap<usize, LiftBuildings>,
+        lifts: HashMap<usize, Lift>,
+        open: HashMap<usize, open::Status>,
+        id_allocator: id_allocator::Service,
+        carousels: HashMap<usize, Carousel>,
+        cars: HashMap<usize, Car>,
+        frames: HashMap<usize, Option<Frame>>,
+        lift_queues: HashMap<usize, LiftQueue>,
+        reliabilities: HashMap<usize, Reliability>,
+        exits: HashMap<usize, Exit>,
+        entrances: HashMap<usize, Entrance>,
+        reservations: Grid<HashMap<usize, Reservation>>,
+        parents: HashMap<usize, usize>,
+        children: HashMap<usize, Vec<usize>>,
+        drawings: HashMap<usize, usize>,
+        piste_computer: piste_computer::System,
+        constructions: construction::Queue,
+        economy: economy::Service,
+        messenger: messenger::System,
+        lift_building_artist: lift_building_artist::System,
+        graphics: NoGraphics,
+    }
+
+    impl World {
+        fn new() -> World {
+            let (tx, _) = broadcast::channel(16);
+            World {
+                terrain: Grid::default(64, 64),
+                piste_map: Grid::from_element(64, 64, Some(0)),
+                lift_buildings: HashMap::from([(
+                    LIFT_BUILDINGS_ID,
+                    LiftBuildings {
+                        lift_type: LiftType::Chairlift,
+                        buildings: vec![
+                            building(LiftBuildingClass::PickUpStation, xy(32, 8)),
+                            building(LiftBuildingClass::Pylon, xy(32, 24)),
+                            building(LiftBuildingClass::DropOffStation, xy(32, 40)),
+                        ],
+                        cable_tension: LiftType::Chairlift.cable_tension(),
+                    },
+                )]),
+                lifts: HashMap::default(),
+                open: HashMap::default(),
+                id_allocator: id_allocator::Service::new(),
+                carousels: HashMap::default(),
+                cars: HashMap::default(),
+                frames: HashMap::default(),
+                lift_queues: HashMap::default(),
+                reliabilities: HashMap::default(),
+                exits: HashMap::default(),
+                entrances: HashMap::default(),
+                reservations: Grid::default(64, 64),
+                parents: HashMap::default(),
+                children: HashMap::default(),
+                drawings: HashMap::default(),
+                piste_computer: piste_computer::System::new(),
+                constructions: construction::Queue::default(),
+                economy: economy::Service::new(),
+                messenger: messenger::System::new(tx),
+                lift_building_artist: lift_building_artist::System::default(),
+                graphics: NoGraphics::new(),
+            }
+        }
+
+        fn parameters(&mut self) -> TriggerParameters<'_> {
+            TriggerParameters {
+                mouse_xy: &None,
+                terrain: &self.terrain,
+                piste_map: &self.piste_map,
+                lift_buildings: &mut self.lift_buildings,
+                lifts: &mut self.lifts,
+                open: &mut self.open,
+                id_allocator: &mut self.id_allocator,
+                carousels: &mut self.carousels,
+                cars: &mut self.cars,
+                frames: &mut self.frames,
+                lift_queues: &mut self.lift_queues,
+                reliabilities: &mut self.reliabilities,
+                exits: &mut self.exits,
+                entrances: &mut self.entrances,
+                reservations: &mut self.reservations,
+                parents: &mut self.parents,
+                children: &mut self.children,
+                drawings: &mut self.drawings,
+                piste_computer: &mut self.piste_computer,
+                constructions: &mut self.constructions,
+                economy: &mut self.economy,
+                messenger: &mut self.messenger,
+                lift_building_artist: &mut self.lift_building_artist,
+                graphics: &mut self.graphics,
+            }
+        }
+
+        fn build_lift(&mut self, controller: &mut Controller) -> usize {
+            controller.lift_building_id = Some(LIFT_BUILDINGS_ID);
+            controller.create_lift(self.parameters());
+            *self.lifts.keys().next().unwrap()
+        }
+
+        fn start_editing(&mut self, controller: &mut Controller, lift_id: &usize) -> bool {
+            controller.start_editing(
+                lift_id,
+                &self.lifts,
+                &self.open,
+                &self.lift_buildings,
+                &mut self.messenger,
+            )
+        }
+
+        fn positions(&self) -> Vec<XY<u32>> {
+            self.lift_buildings[&LIFT_BUILDINGS_ID]
+                .buildings
+                .iter()
+                .map(|building| building.position)
+                .collect()
+        }
+    }
+
+    fn building(class: LiftBuildingClass, position: XY<u32>) -> LiftBuilding {
+        LiftBuilding {
+            class,
+            position,
+            yaw: 0.0,
+            manual_yaw: false,
+        }
+    }
+
+    #[test]
+    fn test_only_closed_lifts_can_be_edited() {
+        // given
+        let mut world = World::new();
+        let mut controller = Controller::new();
+        let lift_id = world.build_lift(&mut controller);
+        world.open.insert(lift_id, open::Status::Open);
+
+        // then
+        assert!(!world.start_editing(&mut controller, &lift_id));
+        assert!(!controller.is_editing());
+
+        // when
+        world.open.insert(lift_id, open::Status::Closed);
+
+        // then
+        assert!(world.start_editing(&mut controller, &lift_id));
+        assert!(controller.is_editing());
+        assert_eq!(controller.lift_building_id(), &Some(LIFT_BUILDINGS_ID));
+    }
+
+    #[test]
+    fn test_insert_and_remove_pylon() {
+        // given
+        let mut world = World::new();
+        let mut controller = Controller::new();
+        let lift_id = world.build_lift(&mut controller);
+        world.start_editing(&mut controller, &lift_id);
+        controller.editing.as_mut().unwrap().selected = Some(0);
+        let lift_buildings = world.lift_buildings.get_mut(&LIFT_BUILDINGS_ID).unwrap();
+
+        // when
+        controller.insert_pylon(lift_buildings);
+
+        // then
+        assert_eq!(
+            world.positions(),
+            vec![xy(32, 8), xy(32, 16), xy(32, 24), xy(32, 40)]
+        );
+        assert!(
+            world.lift_buildings[&LIFT_BUILDINGS_ID].buildings[1].class == LiftBuildingClass::Pylon
+        );
+        assert_eq!(
+            controller.current_building_index(&world.lift_buildings[&LIFT_BUILDINGS_ID]),
+            Some(1)
+        );
+
+        // when
+        let lift_buildings = world.lift_buildings.get_mut(&LIFT_BUILDINGS_ID).unwrap();
+        controller.remove_pylon(lift_buildings);
+
+        // then
+        assert_eq!(world.positions(), vec![xy(32, 8), xy(32, 24), xy(32, 40)]);
+        assert_eq!(
+            controller.current_building_index(&world.lift_buildings[&LIFT_BUILDINGS_ID]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_stations_cannot_be_removed() {
+        // given
+        let mut world = World::new();
+        let mut controller = Controller::new();
+        let lift_id = world.build_lift(&mut controller);
+        world.start_editing(&mut controller, &lift_id);
+        controller.editing.as_mut().unwrap().selected = Some(0);
+        let lift_buildings = world.lift_buildings.get_mut(&LIFT_BUILDINGS_ID).unwrap();
+
+        // when
+        controller.remove_pylon(lift_buildings);
+
+        // then
+        assert_eq!(world.positions(), vec![xy(32, 8), xy(32, 24), xy(32, 40)]);
+    }
+
+    #[test]
+    fn test_cancel_editing_restores_original_buildings() {
+        // given
+        let mut world = World::new();
+        let mut controller = Controller::new();
+        let lift_id = world.build_lift(&mut controller);
+        world.start_editing(&mut controller, &lift_id);
+        controller.editing.as_mut().unwrap().selected = Some(1);
+        let lift_buildings = world.lift_buildings.get_mut(&LIFT_BUILDINGS_ID).unwrap();
+        controller.insert_pylon(lift_buildings);
+        lift_buildings.buildings[0].position = xy(30, 8);
+        lift_buildings.cable_tension *= 2.0;
+
+        // when
+        controller.cancel_editing(&mut world.lift_buildings);
+
+        // then
+        assert!(!controller.is_editing());
+        assert_eq!(controller.lift_building_id(), &None);
+        assert_eq!(world.positions(), vec![xy(32, 8), xy(32, 24), xy(32, 40)]);
+        assert_eq!(
+            world.lift_buildings[&LIFT_BUILDINGS_ID].cable_tension,
+            LiftType::Chairlift.cable_tension()
+        );
+    }
+
+    #[test]
+    fn test_commit_keeps_ids_and_open_status() {
+        // given
+        let mut world = World::new();
+        let mut controller = Controller::new();
+        let lift_id = world.build_lift(&mut controller);
+        let lift = &world.lifts[&lift_id];
+        let carousel_id = lift.carousel_id;
+        let portal_ids = lift.portals().map(|portal| portal.id).collect::<Vec<_>>();
+        world.start_editing(&mut controller, &lift_id);
+        controller.editing.as_mut().unwrap().selected = Some(1);
+        let lift_buildings = world.lift_buildings.get_mut(&LIFT_BUILDINGS_ID).unwrap();
+        controller.insert_pylon(lift_buildings);
+
+        // when
+        controller.create_lift(world.parameters());
+
+        // then
+        assert!(!controller.is_editing());
+        assert_eq!(world.lifts.len(), 1);
+        let lift = &world.lifts[&lift_id];
+        assert_eq!(lift.carousel_id, carousel_id);
+        assert_eq!(
+            lift.portals().map(|portal| portal.id).collect::<Vec<_>>(),
+            portal_ids
+        );
+        assert_eq!(world.carousels.len(), 1);
+        assert_eq!(world.carousels[&carousel_id].lift_id, lift_id);
+        assert_eq!(world.open[&lift_id], open::Status::Closed);
+        for portal_id in portal_ids {
+            assert_eq!(world.open[&portal_id], open::Status::Closed);
+            assert!(world.parents.contains_key_value(portal_id, lift_id));
+        }
+        assert_eq!(world.positions().len(), 4);
+    }
+
+    #[test]
+    fn test_queue_maze_is_reserved() {
+        // given
+        let mut world = World::new();
+        let mut controller = Controller::new();
+
+        // when
+        let lift_id = world.build_lift(&mut controller);
+
+        // then
+        let pick_up = &world.lifts[&lift_id].pick_up;
+        let maze = world.lift_queues[&pick_up.id].maze;
+        assert_eq!(maze, DEFAULT_MAZE);
+        let footprint = utils::lift_queue::footprint(&maze, &pick_up.state, &world.reservations);
+        assert_eq!(footprint.len(), maze.capacity());
+        for cell in footprint {
+            assert!(world.reservations[cell].get(&pick_up.id) == Some(&Reservation::Structure));
+        }
+    }
+
+    #[test]
+    fn test_queue_maze_fits_between_other_pistes() {
+        // given
+        let mut world = World::new();
+        world.piste_map = Grid::from_fn(64, 64, |xy| {
+            if xy.x.abs_diff(32) <= 1 && xy.y.abs_diff(8) <= 3 {
+                Some(0)
+            } else {
+                Some(1)
+            }
+        });
+        world.piste_map[xy(32, 40)] = Some(2);
+        let mut controller = Controller::new();
+
+        // when
+        let lift_id = world.build_lift(&mut controller);
+
+        // then
+        let pick_up = &world.lifts[&lift_id].pick_up;
+        let maze = world.lift_queues[&pick_up.id].maze;
+        assert!(maze.capacity() < DEFAULT_MAZE.capacity());
+        for cell in utils::lift_queue::footprint(&maze, &pick_up.state, &world.piste_map) {
+            assert_eq!(world.piste_map[cell], Some(0));
+        }
+    }
 }
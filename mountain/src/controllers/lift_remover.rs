@@ -5,8 +5,10 @@ use engine::graphics::{DrawMode, Graphics};
 use crate::controllers::Result::{self, Action, NoAction};
 use crate::model::entrance::Entrance;
 use crate::model::exit::Exit;
+use crate::model::ledger::Account;
 use crate::model::open;
 use crate::systems::{messenger, piste_computer};
+use crate::utils::{construction, lift_queue};
 use crate::Components;
 
 pub fn trigger(
@@ -28,7 +30,8 @@ pub fn trigger(
         .lifts
         .iter()
         .filter(|(_, lift)| {
-            lift.pick_up.state.position == position || lift.drop_off.state.position == position
+            lift.portals()
+                .any(|portal| portal.state.position == position)
         })
         .map(|(lift_id, _)| *lift_id)
         .collect::<Vec<_>>();
@@ -67,26 +70,35 @@ pub fn remove_lift(
     // Remove
 
     let lift = components.lifts.remove(lift_id);
+    components.reliabilities.remove(lift_id);
     components.open.remove(lift_id);
+    components.constructions.remove(lift_id);
 
     if let Some(lift) = lift {
-        if let Some(Exit {
-            origin_piste_id, ..
-        }) = components.exits.remove(&lift.pick_up.id)
-        {
-            piste_computer.compute(origin_piste_id);
+        for pick_up in lift.pick_ups() {
+            if let Some(queue) = components.lift_queues.remove(&pick_up.id) {
+                lift_queue::release_maze(pick_up, &queue.maze, &mut components.reservations);
+            }
+            if let Some(Exit {
+                origin_piste_id, ..
+            }) = components.exits.remove(&pick_up.id)
+            {
+                piste_computer.compute(origin_piste_id);
+            }
+            components.open.remove(&pick_up.id);
+            components.reservations[pick_up.state.position].remove(&pick_up.id);
         }
-        components.open.remove(&lift.pick_up.id);
-        components.reservations[lift.pick_up.state.position].remove(&lift.pick_up.id);
-
-        if let Some(Entrance {
-            destination_piste_id,
-            ..
-        }) = components.entrances.remove(&lift.drop_off.id)
-        {
-            piste_computer.compute(destination_piste_id);
+
+        for drop_off in lift.drop_offs() {
+            if let Some(Entrance {
+                destination_piste_id,
+                ..
+            }) = components.entrances.remove(&drop_off.id)
+            {
+                piste_computer.compute(destination_piste_id);
+            }
+            components.open.remove(&drop_off.id);
         }
-        components.open.remove(&lift.drop_off.id);
 
         remove_carousel(graphics, components, &lift.carousel_id);
 
@@ -117,7 +129,13 @@ fn remove_car(graphics: &mut dyn Graphics, components: &mut Components, car_id:
 }
 
 fn remove_lift_buildings(graphics: &mut dyn Graphics, components: &mut Components, id: &usize) {
-    components.lift_buildings.remove(id);
+    if let Some(lift_buildings) = components.lift_buildings.remove(id) {
+        let price = construction::lift_price(&lift_buildings, &components.terrain);
+        components
+            .services
+            .economy
+            .record(Account::Construction, construction::refund(price));
+    }
     remove_drawing(graphics, components, id);
 }
 
@@ -9,6 +9,8 @@ pub mod lift_remover;
 pub mod piste_builder;
 pub mod piste_eraser;
 pub mod save;
+pub mod snowmaking_builder;
+pub mod snowmaking_remover;
 
 #[derive(Debug, PartialEq)]
 pub enum Result {
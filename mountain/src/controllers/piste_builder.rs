@@ -7,11 +7,14 @@ use commons::origin_grid::OriginGrid;
 
 use crate::controllers::Result::{self, Action, NoAction};
 
+use crate::model::construction;
 use crate::model::open;
 use crate::model::piste::{self, Piste};
 use crate::model::selection::Selection;
-use crate::services::id_allocator;
+use crate::model::tree::Tree;
+use crate::services::{economy, id_allocator};
 use crate::systems::{messenger, terrain_artist, tree_artist};
+use crate::utils;
 
 pub struct Controller {
     pub class: piste::Class,
@@ -19,6 +22,8 @@ pub struct Controller {
 }
 
 pub struct Parameters<'a> {
+    pub terrain: &'a Grid<f32>,
+    pub trees: &'a Grid<Option<Tree>>,
     pub pistes: &'a mut HashMap<usize, Piste>,
     pub piste_map: &'a mut Grid<Option<usize>>,
     pub open: &'a mut HashMap<usize, open::Status>,
@@ -26,6 +31,8 @@ pub struct Parameters<'a> {
     pub terrain_artist: &'a mut terrain_artist::System,
     pub tree_artist: &'a mut tree_artist::System,
     pub id_allocator: &'a mut id_allocator::Service,
+    pub constructions: &'a mut construction::Queue,
+    pub economy: &'a mut economy::Service,
     pub messenger: &'a mut messenger::System,
 }
 
@@ -41,6 +48,8 @@ impl Controller {
     pub fn trigger(
         &mut self,
         Parameters {
+            terrain,
+            trees,
             pistes,
             piste_map,
             open,
@@ -48,6 +57,8 @@ impl Controller {
             terrain_artist,
             tree_artist,
             id_allocator,
+            constructions,
+            economy,
             messenger,
         }: Parameters<'_>,
     ) -> Result {
@@ -73,14 +84,34 @@ impl Controller {
             }
         }
 
+        // paying for new cells
+
+        let new_cells = grid
+            .iter()
+            .filter(|cell| grid[cell] && piste_map[cell].is_none())
+            .collect::<Vec<_>>();
+        let price = utils::construction::piste_price(&self.class, terrain, trees, new_cells.iter());
+        if !utils::construction::try_to_pay(economy, messenger, describe_class(&self.class), price)
+        {
+            return NoAction;
+        }
+
         let piste_id = piste_id.unwrap_or_else(|| id_allocator.next_id());
 
+        // queueing works, the piste is closed for editing and cannot reopen until they complete
+
+        if !new_cells.is_empty() {
+            let kind = match self.class {
+                piste::Class::Piste => construction::Kind::Piste,
+                piste::Class::Path => construction::Kind::Path,
+            };
+            constructions.enqueue(piste_id, kind, utils::construction::work_micros(price));
+        }
+
         // updating piste map
 
-        for cell in grid.iter().filter(|cell| grid[cell]) {
-            if piste_map[cell].is_none() {
-                piste_map[cell] = Some(piste_id)
-            }
+        for cell in new_cells {
+            piste_map[cell] = Some(piste_id)
         }
 
         // updating piste
@@ -123,3 +154,10 @@ impl Controller {
         Action
     }
 }
+
+fn describe_class(class: &piste::Class) -> &'static str {
+    match class {
+        piste::Class::Piste => "piste",
+        piste::Class::Path => "path",
+    }
+}
@@ -8,17 +8,23 @@ use crate::controllers::Result::{self, Action, NoAction};
 
 use crate::model::door::Door;
 use crate::model::gate::Gate;
+use crate::model::ledger::Account;
 use crate::model::lift::Lift;
 use crate::model::open;
 use crate::model::piste::Piste;
 use crate::model::selection::Selection;
+use crate::model::tree::Tree;
+use crate::services::economy;
 use crate::systems::{messenger, terrain_artist, tree_artist};
+use crate::utils::construction;
 
 pub struct Controller {
     enabled: bool,
 }
 
 pub struct Parameters<'a> {
+    pub terrain: &'a Grid<f32>,
+    pub trees: &'a Grid<Option<Tree>>,
     pub open: &'a HashMap<usize, open::Status>,
     pub lifts: &'a HashMap<usize, Lift>,
     pub gates: &'a HashMap<usize, Gate>,
@@ -28,6 +34,7 @@ pub struct Parameters<'a> {
     pub selection: &'a mut Selection,
     pub terrain_artist: &'a mut terrain_artist::System,
     pub tree_artist: &'a mut tree_artist::System,
+    pub economy: &'a mut economy::Service,
     pub messenger: &'a mut messenger::System,
 }
 
@@ -47,6 +54,8 @@ impl Controller {
     pub fn trigger(
         &self,
         Parameters {
+            terrain,
+            trees,
             open,
             lifts,
             gates,
@@ -56,6 +65,7 @@ impl Controller {
             selection,
             terrain_artist,
             tree_artist,
+            economy,
             messenger,
         }: Parameters<'_>,
     ) -> Result {
@@ -106,9 +116,10 @@ impl Controller {
         };
 
         for (lift_id, lift) in lifts.iter() {
-            let drop_off = lift.drop_off.state.position;
-            let pick_up = lift.pick_up.state.position;
-            if is_in_selection_on_piste(drop_off) || is_in_selection_on_piste(pick_up) {
+            if lift
+                .portals()
+                .any(|portal| is_in_selection_on_piste(portal.state.position))
+            {
                 messenger.send(format!(
                     "Cannot erase piste: selection contains Lift {}",
                     lift_id
@@ -143,12 +154,19 @@ impl Controller {
 
         // updating piste map
 
-        for cell in grid.iter().filter(|cell| grid[cell]) {
-            if piste_map[cell] == Some(piste_id) {
-                piste_map[cell] = None
-            }
+        let erased_cells = grid
+            .iter()
+            .filter(|cell| grid[cell] && piste_map[cell] == Some(piste_id))
+            .collect::<Vec<_>>();
+        for cell in erased_cells.iter() {
+            piste_map[cell] = None
         }
 
+        // refunding erased cells
+
+        let price = construction::piste_price(&piste.class, terrain, trees, erased_cells.iter());
+        economy.record(Account::Construction, construction::refund(price));
+
         // updating piste
 
         piste.grid = piste.grid.paste(&point_grid);
@@ -0,0 +1,126 @@
+use std::collections::HashMap;
+
+use commons::geometry::{xy, XY, XYZ};
+use commons::grid::Grid;
+
+use crate::controllers::Result::{self, Action, NoAction};
+use crate::model::snowmaking::{self, Cannon, Status, WaterSource};
+use crate::services::id_allocator;
+use crate::systems::messenger;
+
+const STREAM_SEARCH_RADIUS: u32 = 3;
+
+pub struct Parameters<'a> {
+    pub mouse_xy: &'a Option<XY<u32>>,
+    pub rain: &'a Grid<usize>,
+    pub piste_map: &'a Grid<Option<usize>>,
+    pub id_allocator: &'a mut id_allocator::Service,
+    pub water_sources: &'a mut HashMap<usize, WaterSource>,
+    pub cannons: &'a mut HashMap<usize, Cannon>,
+    pub messenger: &'a mut messenger::System,
+    pub graphics: &'a mut dyn engine::graphics::Graphics,
+}
+
+pub fn trigger(
+    Parameters {
+        mouse_xy,
+        rain,
+        piste_map,
+        id_allocator,
+        water_sources,
+        cannons,
+        messenger,
+        graphics,
+    }: Parameters<'_>,
+) -> Result {
+    let Some(mouse_xy) = mouse_xy else {
+        return NoAction;
+    };
+    let Ok(XYZ { x, y, .. }) = graphics.world_xyz_at(mouse_xy) else {
+        return NoAction;
+    };
+    let position = xy(x.round() as u32, y.round() as u32);
+    if !rain.in_bounds(position) {
+        return NoAction;
+    }
+
+    // clicking on a stream taps it for water
+
+    if let Some(stream) = stream_near(rain, &position) {
+        if water_sources
+            .values()
+            .any(|source| distance(&source.position, &stream) <= STREAM_SEARCH_RADIUS as f32)
+        {
+            messenger.send("There is already a water source here");
+            return NoAction;
+        }
+        water_sources.insert(
+            id_allocator.next_id(),
+            WaterSource::new(stream, rain[stream]),
+        );
+        return Action;
+    }
+
+    // anywhere else on a piste is a cannon piped to the nearest part of the network
+
+    if piste_map[position].is_none() {
+        messenger.send("Snow cannons must be placed on a piste or water sources on a stream");
+        return NoAction;
+    }
+
+    let nearest = water_sources
+        .iter()
+        .map(|(source_id, source)| (*source_id, source.position))
+        .chain(
+            cannons
+                .values()
+                .map(|cannon| (cannon.source_id, cannon.position)),
+        )
+        .map(|(source_id, node)| (source_id, node, distance(&position, &node)))
+        .min_by(|(_, _, a), (_, _, b)| a.total_cmp(b));
+    let Some((source_id, pipe_from, pipe_meters)) = nearest else {
+        messenger.send("Snow cannons need a water source: place one on a stream first");
+        return NoAction;
+    };
+    if pipe_meters > snowmaking::MAX_PIPE_METERS {
+        messenger.send(format!(
+            "Snow cannon must be within {}m of a water source or another snow cannon",
+            snowmaking::MAX_PIPE_METERS
+        ));
+        return NoAction;
+    }
+    if pipe_meters == 0.0 {
+        return NoAction;
+    }
+
+    cannons.insert(
+        id_allocator.next_id(),
+        Cannon {
+            position,
+            source_id,
+            pipe_from,
+            status: Status::Idle,
+        },
+    );
+
+    Action
+}
+
+fn stream_near(rain: &Grid<usize>, position: &XY<u32>) -> Option<XY<u32>> {
+    let from = xy(
+        position.x.saturating_sub(STREAM_SEARCH_RADIUS),
+        position.y.saturating_sub(STREAM_SEARCH_RADIUS),
+    );
+    let to = xy(
+        (position.x + STREAM_SEARCH_RADIUS).min(rain.width() - 1),
+        (position.y + STREAM_SEARCH_RADIUS).min(rain.height() - 1),
+    );
+    (from.y..=to.y)
+        .flat_map(|y| (from.x..=to.x).map(move |x| xy(x, y)))
+        .filter(|cell| rain[cell] >= snowmaking::MIN_SOURCE_RAIN)
+        .max_by_key(|cell| rain[cell])
+}
+
+fn distance(a: &XY<u32>, b: &XY<u32>) -> f32 {
+    xy(a.x as f32 - b.x as f32, a.y as f32 - b.y as f32).magnitude()
+}
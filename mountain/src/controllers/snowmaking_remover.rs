@@ -0,0 +1,74 @@
+use commons::geometry::{xy, XY, XYZ};
+use engine::graphics::{DrawMode, Graphics};
+
+use crate::controllers::Result::{self, Action, NoAction};
+use crate::systems::messenger;
+use crate::Components;
+
+const SELECTION_RADIUS_METERS: f32 = 1.5;
+
+pub fn trigger(
+    mouse_xy: &Option<XY<u32>>,
+    components: &mut Components,
+    messenger: &mut messenger::System,
+    graphics: &mut dyn Graphics,
+) -> Result {
+    let Some(mouse_xy) = mouse_xy else {
+        return NoAction;
+    };
+    let Ok(XYZ { x, y, .. }) = graphics.world_xyz_at(mouse_xy) else {
+        return NoAction;
+    };
+    let position = xy(x, y);
+    let is_selected = |node: &XY<u32>| {
+        xy(node.x as f32 - position.x, node.y as f32 - position.y).magnitude()
+            <= SELECTION_RADIUS_METERS
+    };
+
+    let cannon = components
+        .cannons
+        .iter()
+        .find(|(_, cannon)| is_selected(&cannon.position))
+        .map(|(cannon_id, cannon)| (*cannon_id, cannon.position));
+    if let Some((cannon_id, cannon_position)) = cannon {
+        if components
+            .cannons
+            .values()
+            .any(|cannon| cannon.pipe_from == cannon_position)
+        {
+            messenger.send("Cannot remove a snow cannon that supplies other snow cannons");
+            return NoAction;
+        }
+        components.cannons.remove(&cannon_id);
+        remove_drawing(graphics, components, &cannon_id);
+        return Action;
+    }
+
+    let source_id = components
+        .water_sources
+        .iter()
+        .find(|(_, source)| is_selected(&source.position))
+        .map(|(source_id, _)| *source_id);
+    if let Some(source_id) = source_id {
+        if components
+            .cannons
+            .values()
+            .any(|cannon| cannon.source_id == source_id)
+        {
+            messenger.send("Cannot remove a water source that supplies snow cannons");
+            return NoAction;
+        }
+        components.water_sources.remove(&source_id);
+        remove_drawing(graphics, components, &source_id);
+        return Action;
+    }
+
+    NoAction
+}
+
+fn remove_drawing(graphics: &mut dyn Graphics, components: &mut Components, id: &usize) {
+    if let Some(drawing_id) = components.drawings.get(id) {
+        let _ = graphics.draw_triangles(drawing_id, DrawMode::Invisible, &[]);
+    }
+    components.drawings.remove(id);
+}
@@ -88,8 +88,15 @@ pub fn draw(graphics: &mut dyn Graphics, index: &usize, building: &Building, ter
         .into_iter()
         .collect::<Vec<_>>();
 
+    // works in progress are drawn as holograms
+    let draw_mode = if *under_construction {
+        DrawMode::Hologram
+    } else {
+        DrawMode::Solid
+    };
+
     graphics
-        .draw_triangles(index, DrawMode::Solid, &triangles)
+        .draw_triangles(index, draw_mode, &triangles)
         .unwrap();
 }
 
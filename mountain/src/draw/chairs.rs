@@ -4,39 +4,54 @@ use engine::graphics::utils::{transformation_matrix, triangles_from_quads, Trans
 use engine::graphics::{DrawMode, Graphics};
 
 use crate::draw::model::{self};
+use crate::model::carousel::{CarClass, CAR_CLASSES};
 use crate::model::frame::{Frame, Model};
 
 pub struct Drawing {
-    pub index: usize,
+    pub indices: HashMap<CarClass, usize>,
 }
 
 impl Drawing {
     pub fn init(graphics: &mut dyn Graphics) -> Drawing {
-        let quads = model::chair::model().quads;
-        let triangles = triangles_from_quads(&quads);
-        let index = graphics
-            .create_instanced_triangles(DrawMode::Hologram, &triangles)
-            .unwrap();
-        Drawing { index }
+        let indices = CAR_CLASSES
+            .into_iter()
+            .map(|class| {
+                let quads = match class {
+                    CarClass::TBar => model::t_bar::model(class.capacity()).quads,
+                    CarClass::Belt => model::belt::model().quads,
+                    CarClass::Gondola => model::gondola::model(class.capacity() / 2).quads,
+                    _ => model::chair::model(class.capacity()).quads,
+                };
+                let triangles = triangles_from_quads(&quads);
+                let index = graphics
+                    .create_instanced_triangles(DrawMode::Hologram, &triangles)
+                    .unwrap();
+                (class, index)
+            })
+            .collect();
+        Drawing { indices }
     }
 
     pub fn draw(&self, graphics: &mut dyn Graphics, frames: &HashMap<usize, Option<Frame>>) {
-        let world_matrices = frames
-            .values()
-            .flatten()
-            .filter(|frame| matches!(frame.model, Model::Chair))
-            .map(|frame| Transformation {
+        let mut world_matrices: HashMap<CarClass, Vec<_>> = HashMap::new();
+        for frame in frames.values().flatten() {
+            let Model::Car(class) = frame.model else {
+                continue;
+            };
+            let matrix = transformation_matrix(Transformation {
                 translation: Some(frame.position),
                 yaw: Some(frame.yaw),
                 pitch: Some(frame.pitch),
                 ..Transformation::default()
-            })
-            .map(transformation_matrix)
-            .map(Some)
-            .collect::<Vec<_>>();
+            });
+            world_matrices.entry(class).or_default().push(Some(matrix));
+        }
 
-        graphics
-            .update_instanced_triangles(&self.index, &world_matrices)
-            .unwrap();
+        for (class, index) in self.indices.iter() {
+            let matrices = world_matrices.remove(class).unwrap_or_default();
+            graphics
+                .update_instanced_triangles(index, &matrices)
+                .unwrap();
+        }
     }
 }
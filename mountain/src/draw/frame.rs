@@ -5,7 +5,7 @@ use engine::graphics::transform::{Recolor, Transform};
 use engine::graphics::utils::{transformation_matrix, triangles_from_quads, Transformation};
 
 use crate::draw;
-use crate::draw::model::{self, chair, skier};
+use crate::draw::model::{self, skier};
 use crate::model::frame::{Frame, Model};
 use crate::model::skier::Clothes;
 
@@ -13,13 +13,20 @@ lazy_static! {
     static ref SKIER_STANDING_MODEL: draw::model::Model<skier::Color, skier::AttachmentPoints> =
         model::skier_standing::model();
 }
+lazy_static! {
+    static ref SNOWBOARDER_MODEL: draw::model::Model<skier::Color, ()> =
+        model::snowboarder::model();
+}
 lazy_static! {
     static ref SKIER_SITTING_MODEL: draw::model::Model<skier::Color, ()> =
         model::skier_sitting::model();
 }
 lazy_static! {
-    static ref CHAIR_MODEL: draw::model::Model<Rgb<f32>, chair::AttachmentPoints> =
-        model::chair::model();
+    static ref SKIER_LYING_MODEL: draw::model::Model<skier::Color, ()> =
+        model::skier_lying::model();
+}
+lazy_static! {
+    static ref GROOMER_MODEL: draw::model::Model<Rgb<f32>, ()> = model::groomer::model();
 }
 
 pub fn draw(frame: &Frame) -> Vec<Triangle<Rgb<f32>>> {
@@ -43,9 +50,16 @@ pub fn draw(frame: &Frame) -> Vec<Triangle<Rgb<f32>>> {
         } => SKIER_STANDING_MODEL
             .quads
             .recolor(&|color| get_rgb(&clothes, color)),
+        Model::Snowboarding { clothes } => SNOWBOARDER_MODEL
+            .quads
+            .recolor(&|color| get_rgb(&clothes, color)),
         Model::Sitting { clothes } => SKIER_SITTING_MODEL
             .quads
             .recolor(&|color| get_rgb(&clothes, color)),
+        Model::Lying { clothes } => SKIER_LYING_MODEL
+            .quads
+            .recolor(&|color| get_rgb(&clothes, color)),
+        Model::Groomer => GROOMER_MODEL.quads.clone(),
         unsupported => {
             panic!("Cannot draw model {:?}", unsupported);
         }
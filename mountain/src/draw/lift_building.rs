@@ -1,20 +1,53 @@
 use commons::color::Rgb;
 use commons::grid::Grid;
 use engine::graphics::elements::Triangle;
-use engine::graphics::transform::Transform;
+use engine::graphics::transform::{Recolor, Transform};
 use engine::graphics::{DrawMode, Graphics};
 
 use crate::draw::model::{lift_building, line};
 use crate::model::lift_building::{LiftBuilding, LiftBuildingClass, LiftBuildings};
+use crate::model::lift_type::LiftType;
+use crate::utils::wire_validation::violations;
 
-pub fn draw(graphics: &mut dyn Graphics, index: &usize, lift: &LiftBuildings, terrain: &Grid<f32>) {
-    let wire = lift.wire_path(terrain);
+const INVALID_COLOR: Rgb<f32> = Rgb::new(1.0, 0.0, 0.0);
+const UNDER_CONSTRUCTION_COLOR: Rgb<f32> = Rgb::new(0.969, 0.577, 0.111);
 
-    let mut wire_triangles = line::model(&wire, 0.5);
+pub fn draw(
+    graphics: &mut dyn Graphics,
+    index: &usize,
+    lift: &LiftBuildings,
+    terrain: &Grid<f32>,
+    under_construction: bool,
+) {
+    let cables = lift.cables(terrain);
+    let violations = violations(&lift.lift_type, &cables, terrain);
+    let wire = cables.into_iter().flatten().collect::<Vec<_>>();
+    let (invalid, valid): (Vec<_>, Vec<_>) = wire
+        .iter()
+        .zip(violations.iter())
+        .partition(|(_, violation)| violation.is_some());
+    let valid = valid
+        .into_iter()
+        .map(|(segment, _)| *segment)
+        .collect::<Vec<_>>();
+    let invalid = invalid
+        .into_iter()
+        .map(|(segment, _)| *segment)
+        .collect::<Vec<_>>();
+
+    let mut wire_triangles = line::model(&valid, 0.5);
+    wire_triangles.append(&mut line::colored_model(&invalid, 0.5, INVALID_COLOR));
     let building_triangles = lift
         .buildings
         .iter()
-        .flat_map(|building| building_triangles(building, terrain));
+        .flat_map(|building| building_triangles(building, &lift.lift_type, terrain))
+        .map(|triangle| {
+            if under_construction {
+                triangle.recolor(&|_| UNDER_CONSTRUCTION_COLOR)
+            } else {
+                triangle
+            }
+        });
     let triangles = wire_triangles
         .drain(..)
         .chain(building_triangles)
@@ -25,11 +58,17 @@ pub fn draw(graphics: &mut dyn Graphics, index: &usize, lift: &LiftBuildings, te
         .unwrap();
 }
 
-fn building_triangles(building: &LiftBuilding, terrain: &Grid<f32>) -> Vec<Triangle<Rgb<f32>>> {
-    let triangles = match &building.class {
-        LiftBuildingClass::PickUpStation => lift_building::chairlift_station(),
-        LiftBuildingClass::Pylon => lift_building::chairlift_pylon(),
-        LiftBuildingClass::DropOffStation => lift_building::chairlift_station(),
+fn building_triangles(
+    building: &LiftBuilding,
+    lift_type: &LiftType,
+    terrain: &Grid<f32>,
+) -> Vec<Triangle<Rgb<f32>>> {
+    let triangles = match (lift_type, &building.class) {
+        (LiftType::MagicCarpet, LiftBuildingClass::Pylon) => lift_building::conveyor_support(),
+        (_, LiftBuildingClass::PickUpStation) => lift_building::chairlift_station(),
+        (_, LiftBuildingClass::Pylon) => lift_building::chairlift_pylon(),
+        (_, LiftBuildingClass::MidStation) => lift_building::chairlift_station(),
+        (_, LiftBuildingClass::DropOffStation) => lift_building::chairlift_station(),
     };
-    triangles.transform(&building.transformation_matrix(terrain))
+    triangles.transform(&building.transformation_matrix(lift_type, terrain))
 }
@@ -5,6 +5,8 @@ pub mod frame;
 pub mod gate;
 pub mod lift_building;
 pub mod model;
+pub mod snowfall;
+pub mod snowmaking;
 pub mod terrain;
 pub mod trees;
 pub mod windows;
@@ -0,0 +1,26 @@
+use std::collections::HashMap;
+
+use commons::color::Rgb;
+use commons::geometry::xyz;
+use engine::graphics::elements::Quad;
+
+use crate::draw::model::Model;
+
+const COLOR: Rgb<f32> = Rgb::new(0.2, 0.2, 0.2);
+
+const SLAT: Quad<Rgb<f32>> = Quad {
+    color: COLOR,
+    corners: [
+        xyz(0.6, -0.5, 0.0),
+        xyz(0.6, 0.5, 0.0),
+        xyz(-0.6, 0.5, 0.0),
+        xyz(-0.6, -0.5, 0.0),
+    ],
+};
+
+pub fn model() -> Model<Rgb<f32>, ()> {
+    Model {
+        quads: vec![SLAT],
+        attachment_points: HashMap::new(),
+    }
+}
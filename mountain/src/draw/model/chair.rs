@@ -5,6 +5,7 @@ use commons::geometry::xyz;
 use engine::graphics::elements::Quad;
 
 use crate::draw::model::Model;
+use crate::model::carousel::SEAT_WIDTH_METERS;
 
 const COLOR: Rgb<f32> = Rgb::new(0.389, 0.389, 0.389);
 
@@ -28,53 +29,43 @@ const POLE_BACK: Quad<Rgb<f32>> = Quad {
     ],
 };
 
-const CHAIR_REST_FRONT: Quad<Rgb<f32>> = Quad {
-    color: COLOR,
-    corners: [
-        xyz(0.0, -0.75, -1.5),
-        xyz(0.0, 0.75, -1.5),
-        xyz(0.0, 0.75, -1.0),
-        xyz(0.0, -0.75, -1.0),
-    ],
-};
-
-const CHAIR_REST_BACK: Quad<Rgb<f32>> = Quad {
-    color: COLOR,
-    corners: [
-        xyz(0.0, -0.75, -1.0),
-        xyz(0.0, 0.75, -1.0),
-        xyz(0.0, 0.75, -1.5),
-        xyz(0.0, -0.75, -1.5),
-    ],
-};
-
-const CHAIR_SEAT: Quad<Rgb<f32>> = Quad {
-    color: COLOR,
-    corners: [
-        xyz(0.5, -0.75, -1.5),
-        xyz(0.5, 0.75, -1.5),
-        xyz(0.0, 0.75, -1.5),
-        xyz(0.0, -0.75, -1.5),
-    ],
-};
-
 #[derive(Eq, Hash, PartialEq)]
 pub enum AttachmentPoints {
     FrontOfChair,
 }
 
-pub fn model() -> Model<Rgb<f32>, AttachmentPoints> {
-    Model {
-        quads: vec![
-            POLE_FRONT,
-            POLE_BACK,
-            CHAIR_REST_FRONT,
-            CHAIR_REST_BACK,
-            CHAIR_SEAT,
+pub fn model(seats: usize) -> Model<Rgb<f32>, AttachmentPoints> {
+    let half_width = seats as f32 * SEAT_WIDTH_METERS / 2.0;
+    let rest_front = Quad {
+        color: COLOR,
+        corners: [
+            xyz(0.0, -half_width, -1.5),
+            xyz(0.0, half_width, -1.5),
+            xyz(0.0, half_width, -1.0),
+            xyz(0.0, -half_width, -1.0),
+        ],
+    };
+    let rest_back = Quad {
+        color: COLOR,
+        corners: [
+            xyz(0.0, -half_width, -1.0),
+            xyz(0.0, half_width, -1.0),
+            xyz(0.0, half_width, -1.5),
+            xyz(0.0, -half_width, -1.5),
         ],
-        attachment_points: HashMap::from_iter([(
-            AttachmentPoints::FrontOfChair,
-            (CHAIR_SEAT.corners[0] + CHAIR_SEAT.corners[1]) / 2.0,
-        )]),
+    };
+    let seat = Quad {
+        color: COLOR,
+        corners: [
+            xyz(0.5, -half_width, -1.5),
+            xyz(0.5, half_width, -1.5),
+            xyz(0.0, half_width, -1.5),
+            xyz(0.0, -half_width, -1.5),
+        ],
+    };
+    let front_of_chair = (seat.corners[0] + seat.corners[1]) / 2.0;
+    Model {
+        quads: vec![POLE_FRONT, POLE_BACK, rest_front, rest_back, seat],
+        attachment_points: HashMap::from_iter([(AttachmentPoints::FrontOfChair, front_of_chair)]),
     }
 }
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use commons::color::Rgb;
+use commons::geometry::{xyz, XYZ};
+use engine::graphics::elements::Quad;
+
+use crate::draw::model::Model;
+use crate::model::carousel::{GONDOLA_ROW_OFFSET_METERS, SEAT_WIDTH_METERS};
+
+const COLOR: Rgb<f32> = Rgb::new(0.389, 0.389, 0.389);
+const ROOF_Z: f32 = -0.5;
+const FLOOR_Z: f32 = -2.1;
+const END_WALL_OFFSET_METERS: f32 = 0.5;
+
+pub fn model(seats_per_row: usize) -> Model<Rgb<f32>, ()> {
+    let half_length = GONDOLA_ROW_OFFSET_METERS + END_WALL_OFFSET_METERS;
+    let half_width = seats_per_row as f32 * SEAT_WIDTH_METERS / 2.0;
+
+    let pole = [
+        xyz(0.0, -0.05, ROOF_Z),
+        xyz(0.0, 0.05, ROOF_Z),
+        xyz(0.0, 0.05, 0.0),
+        xyz(0.0, -0.05, 0.0),
+    ];
+    let roof = [
+        xyz(half_length, -half_width, ROOF_Z),
+        xyz(half_length, half_width, ROOF_Z),
+        xyz(-half_length, half_width, ROOF_Z),
+        xyz(-half_length, -half_width, ROOF_Z),
+    ];
+    let floor = roof.map(|corner| xyz(corner.x, corner.y, FLOOR_Z));
+    let front = [
+        xyz(half_length, -half_width, FLOOR_Z),
+        xyz(half_length, half_width, FLOOR_Z),
+        xyz(half_length, half_width, ROOF_Z),
+        xyz(half_length, -half_width, ROOF_Z),
+    ];
+    let back = front.map(|corner| xyz(-corner.x, corner.y, corner.z));
+
+    Model {
+        quads: [pole, roof, floor, front, back]
+            .into_iter()
+            .flat_map(double_sided)
+            .collect(),
+        attachment_points: HashMap::new(),
+    }
+}
+
+fn double_sided(corners: [XYZ<f32>; 4]) -> [Quad<Rgb<f32>>; 2] {
+    let [a, b, c, d] = corners;
+    [
+        Quad {
+            color: COLOR,
+            corners,
+        },
+        Quad {
+            color: COLOR,
+            corners: [d, c, b, a],
+        },
+    ]
+}
@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+use commons::color::Rgb;
+use commons::geometry::{xyz, XYZ};
+use engine::graphics::elements::Quad;
+use engine::graphics::models::cube;
+use engine::graphics::transform::{Recolor, Transform};
+use engine::graphics::utils::{transformation_matrix, Transformation};
+
+use crate::draw::model::Model;
+
+const TRACK_COLOR: Rgb<f32> = Rgb::new(0.15, 0.15, 0.15);
+const BODY_COLOR: Rgb<f32> = Rgb::new(0.8, 0.1, 0.1);
+const CAB_COLOR: Rgb<f32> = Rgb::new(0.3, 0.4, 0.5);
+const BLADE_COLOR: Rgb<f32> = Rgb::new(0.6, 0.6, 0.6);
+
+pub fn model() -> Model<Rgb<f32>, ()> {
+    let quads = [
+        part(xyz(4.5, 0.8, 0.8), xyz(0.0, -0.9, 0.4), TRACK_COLOR),
+        part(xyz(4.5, 0.8, 0.8), xyz(0.0, 0.9, 0.4), TRACK_COLOR),
+        part(xyz(3.0, 2.2, 1.0), xyz(-0.3, 0.0, 1.3), BODY_COLOR),
+        part(xyz(1.6, 2.0, 1.0), xyz(0.6, 0.0, 2.3), CAB_COLOR),
+        part(xyz(0.2, 3.2, 1.0), xyz(2.6, 0.0, 0.5), BLADE_COLOR),
+        part(xyz(0.8, 3.0, 0.4), xyz(-2.7, 0.0, 0.3), TRACK_COLOR),
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+
+    Model {
+        quads,
+        attachment_points: HashMap::new(),
+    }
+}
+
+fn part(scale: XYZ<f32>, translation: XYZ<f32>, color: Rgb<f32>) -> Vec<Quad<Rgb<f32>>> {
+    cube::model()
+        .recolor(&|_| color)
+        .transform(&transformation_matrix(Transformation {
+            scale: Some(scale),
+            translation: Some(translation),
+            ..Transformation::default()
+        }))
+}
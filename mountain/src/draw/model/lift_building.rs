@@ -37,3 +37,14 @@ pub fn chairlift_station() -> Vec<Triangle<Rgb<f32>>> {
             .recolor(&|_| COLOR),
     )
 }
+
+pub fn conveyor_support() -> Vec<Triangle<Rgb<f32>>> {
+    triangles_from_quads(
+        &cube::model()
+            .transform(&transformation_matrix(Transformation {
+                translation: Some(xyz(0.0, 0.0, 0.5)),
+                ..Transformation::default()
+            }))
+            .recolor(&|_| COLOR),
+    )
+}
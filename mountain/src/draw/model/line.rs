@@ -7,6 +7,14 @@ use engine::graphics::utils::triangles_from_quads;
 const GREY: Rgb<f32> = Rgb::new(0.389, 0.389, 0.389);
 
 pub fn model(segments: &[[XYZ<f32>; 2]], height: f32) -> Vec<Triangle<Rgb<f32>>> {
+    colored_model(segments, height, GREY)
+}
+
+pub fn colored_model(
+    segments: &[[XYZ<f32>; 2]],
+    height: f32,
+    color: Rgb<f32>,
+) -> Vec<Triangle<Rgb<f32>>> {
     let quads = segments
         .iter()
         .flat_map(|segment| {
@@ -14,7 +22,7 @@ pub fn model(segments: &[[XYZ<f32>; 2]], height: f32) -> Vec<Triangle<Rgb<f32>>>
             let to = segment[1];
             [
                 Quad {
-                    color: GREY,
+                    color,
                     corners: [
                         xyz(from.x, from.y, from.z),
                         xyz(from.x, from.y, from.z + height),
@@ -23,7 +31,7 @@ pub fn model(segments: &[[XYZ<f32>; 2]], height: f32) -> Vec<Triangle<Rgb<f32>>>
                     ],
                 },
                 Quad {
-                    color: GREY,
+                    color,
                     corners: [
                         xyz(from.x, from.y, from.z),
                         xyz(to.x, to.y, to.z),
@@ -3,15 +3,21 @@ use std::collections::HashMap;
 use commons::geometry::XYZ;
 use engine::graphics::elements::Quad;
 
+pub mod belt;
 pub mod building;
 pub mod chair;
+pub mod gondola;
+pub mod groomer;
 pub mod lift_building;
 pub mod line;
 pub mod prism;
 pub mod pyramid;
 pub mod skier;
+pub mod skier_lying;
 pub mod skier_sitting;
 pub mod skier_standing;
+pub mod snowboarder;
+pub mod t_bar;
 pub mod tree;
 pub mod window;
 
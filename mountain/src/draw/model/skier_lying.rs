@@ -0,0 +1,25 @@
+use std::collections::HashMap;
+use std::f32::consts::PI;
+
+use commons::geometry::xyz;
+use engine::graphics::transform::Transform;
+use engine::graphics::utils::{transformation_matrix, Transformation};
+
+use crate::draw::model::{skier, skier_standing, Model};
+
+pub fn model() -> Model<skier::Color, ()> {
+    // an injured skier has lost their skis and lies on their back
+    let quads = skier_standing::model()
+        .quads
+        .into_iter()
+        .filter(|quad| !matches!(quad.color, skier::Color::Skis))
+        .collect::<Vec<_>>();
+    Model {
+        quads: quads.transform(&transformation_matrix(Transformation {
+            translation: Some(xyz(0.0, 0.0, 0.2)),
+            pitch: Some(-PI / 2.0),
+            ..Transformation::default()
+        })),
+        attachment_points: HashMap::new(),
+    }
+}
@@ -24,7 +24,7 @@ pub fn model() -> Model<skier::Color, ()> {
         skier_parameters.lower_leg_scale.z - skier_parameters.upper_leg_scale.x,
     );
     let skier = skier::model(skier_parameters);
-    let chair = chair::model();
+    let chair = chair::model(1);
     let offset = chair.attachment_points[&chair::AttachmentPoints::FrontOfChair]
         - skier.attachment_points[&skier::AttachmentPoints::BackOfHeels]
         - heels_to_knee;
@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+use std::f32::consts::PI;
+use std::iter::once;
+
+use commons::geometry::xyz;
+use engine::graphics::elements::Quad;
+use engine::graphics::transform::Transform;
+use engine::graphics::utils::{transformation_matrix, Transformation};
+
+use crate::draw::model::{skier, skier_standing, Model};
+
+const BOARD: Quad<skier::Color> = Quad {
+    color: skier::Color::Skis,
+    corners: [
+        xyz(-0.75, -0.15, 0.0),
+        xyz(0.75, -0.15, 0.0),
+        xyz(0.75, 0.15, 0.0),
+        xyz(-0.75, 0.15, 0.0),
+    ],
+};
+
+pub fn model() -> Model<skier::Color, ()> {
+    // snowboarders ride sideways, facing across the board
+    let rider = skier_standing::model()
+        .quads
+        .into_iter()
+        .filter(|quad| !matches!(quad.color, skier::Color::Skis))
+        .collect::<Vec<_>>()
+        .transform(&transformation_matrix(Transformation {
+            yaw: Some(PI / 2.0),
+            ..Transformation::default()
+        }));
+    Model {
+        quads: once(BOARD).chain(rider).collect(),
+        attachment_points: HashMap::new(),
+    }
+}
@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+
+use commons::color::Rgb;
+use commons::geometry::xyz;
+use engine::graphics::elements::Quad;
+
+use crate::draw::model::Model;
+use crate::model::carousel::SEAT_WIDTH_METERS;
+
+const COLOR: Rgb<f32> = Rgb::new(0.389, 0.389, 0.389);
+const BAR_Z: f32 = -2.0;
+
+const POLE_FRONT: Quad<Rgb<f32>> = Quad {
+    color: COLOR,
+    corners: [
+        xyz(0.0, -0.05, BAR_Z),
+        xyz(0.0, 0.05, BAR_Z),
+        xyz(0.0, 0.05, 0.0),
+        xyz(0.0, -0.05, 0.0),
+    ],
+};
+
+const POLE_BACK: Quad<Rgb<f32>> = Quad {
+    color: COLOR,
+    corners: [
+        xyz(0.0, -0.05, 0.0),
+        xyz(0.0, 0.05, 0.0),
+        xyz(0.0, 0.05, BAR_Z),
+        xyz(0.0, -0.05, BAR_Z),
+    ],
+};
+
+pub fn model(riders: usize) -> Model<Rgb<f32>, ()> {
+    let half_width = riders as f32 * SEAT_WIDTH_METERS / 2.0;
+    let bar_front = Quad {
+        color: COLOR,
+        corners: [
+            xyz(0.0, -half_width, BAR_Z - 0.1),
+            xyz(0.0, half_width, BAR_Z - 0.1),
+            xyz(0.0, half_width, BAR_Z),
+            xyz(0.0, -half_width, BAR_Z),
+        ],
+    };
+    let bar_back = Quad {
+        color: COLOR,
+        corners: [
+            xyz(0.0, -half_width, BAR_Z),
+            xyz(0.0, half_width, BAR_Z),
+            xyz(0.0, half_width, BAR_Z - 0.1),
+            xyz(0.0, -half_width, BAR_Z - 0.1),
+        ],
+    };
+    Model {
+        quads: vec![POLE_FRONT, POLE_BACK, bar_front, bar_back],
+        attachment_points: HashMap::new(),
+    }
+}
@@ -0,0 +1,79 @@
+use commons::color::Rgba;
+use commons::geometry::{xyz, Rectangle, XYZ};
+use commons::grid::Grid;
+use engine::graphics::elements::Billboard;
+use engine::graphics::{DrawMode, Graphics};
+
+pub const MAX_FLAKES: usize = 400;
+const FLAKE_METERS: f32 = 0.75;
+const TEXTURE_SIZE: u32 = 4;
+/// Flakes are drawn just behind the near plane so they fall in front of everything
+const GL_DEPTH: f32 = -0.95;
+/// Screen heights fallen per second
+const FALL_PER_SECOND: f32 = 0.15;
+/// Screen widths drifted per second for each meter per second of wind
+const DRIFT_PER_WIND_METER: f32 = 0.004;
+
+pub struct Drawing {
+    texture: usize,
+    flakes: Vec<usize>,
+    visible: usize,
+}
+
+impl Drawing {
+    pub fn init(graphics: &mut dyn Graphics) -> Drawing {
+        let image = Grid::from_element(TEXTURE_SIZE, TEXTURE_SIZE, Rgba::new(255, 255, 255, 255));
+        Drawing {
+            texture: graphics.load_texture(&image).unwrap(),
+            flakes: (0..MAX_FLAKES)
+                .map(|_| graphics.create_billboards().unwrap())
+                .collect(),
+            visible: 0,
+        }
+    }
+
+    pub fn draw(
+        &mut self,
+        graphics: &mut dyn Graphics,
+        flakes: usize,
+        seconds: f32,
+        wind_meters_per_second: f32,
+    ) {
+        let drift = wind_meters_per_second * DRIFT_PER_WIND_METER;
+        for index in &self.flakes[flakes.min(self.visible)..self.visible] {
+            graphics
+                .draw_billboard(
+                    index,
+                    DrawMode::Invisible,
+                    &self.billboard(xyz(0.0, 0.0, 0.0)),
+                )
+                .unwrap();
+        }
+        self.visible = flakes;
+
+        for (i, index) in self.flakes.iter().take(flakes).enumerate() {
+            // scatter the flakes with the golden ratio so they never line up
+            let start_x = (i as f32 * 0.618_034).fract();
+            let start_y = (i as f32 * 0.754_877).fract();
+            let speed = 1.0 + (i as f32 * 0.569_840).fract();
+            let gl_x = (start_x + drift * speed * seconds).rem_euclid(1.0) * 2.0 - 1.0;
+            let gl_y = 1.0 - (start_y + FALL_PER_SECOND * speed * seconds).rem_euclid(1.0) * 2.0;
+
+            let position = graphics.projection().unproject(&xyz(gl_x, gl_y, GL_DEPTH));
+            graphics
+                .draw_billboard(index, DrawMode::Solid, &self.billboard(position))
+                .unwrap();
+        }
+    }
+
+    fn billboard(&self, position: XYZ<f32>) -> Billboard {
+        Billboard {
+            position,
+            dimensions: Rectangle {
+                width: FLAKE_METERS,
+                height: FLAKE_METERS,
+            },
+            texture: self.texture,
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use commons::color::Rgb;
+use commons::geometry::{xy, xyz, XY, XYZ};
+use commons::grid::Grid;
+use engine::graphics::elements::Quad;
+use engine::graphics::models::cube;
+use engine::graphics::transform::{Recolor, Transform};
+use engine::graphics::utils::{transformation_matrix, triangles_from_quads, Transformation};
+use engine::graphics::{DrawMode, Graphics};
+
+use crate::draw::model::line;
+use crate::model::snowmaking::{Cannon, WaterSource};
+
+const POLE_HEIGHT_METERS: f32 = 3.0;
+const BARREL_PITCH: f32 = -0.5;
+const PIPE_HEIGHT_METERS: f32 = 0.2;
+const STRUCTURE_COLOR: Rgb<f32> = Rgb::new(0.2, 0.2, 0.2);
+const BARREL_COLOR: Rgb<f32> = Rgb::new(1.0, 0.8, 0.0);
+const PIPE_COLOR: Rgb<f32> = Rgb::new(0.1, 0.2, 0.6);
+const PUMP_HOUSE_COLOR: Rgb<f32> = Rgb::new(0.3, 0.5, 0.7);
+
+pub fn draw_cannon(
+    graphics: &mut dyn Graphics,
+    index: &usize,
+    cannon: &Cannon,
+    terrain: &Grid<f32>,
+) {
+    let base = ground(terrain, &cannon.position);
+    let yaw = (cannon.position.y as f32 - cannon.pipe_from.y as f32)
+        .atan2(cannon.position.x as f32 - cannon.pipe_from.x as f32);
+
+    let pole = part(
+        xyz(0.2, 0.2, POLE_HEIGHT_METERS),
+        base + xyz(0.0, 0.0, POLE_HEIGHT_METERS / 2.0),
+        0.0,
+        0.0,
+        STRUCTURE_COLOR,
+    );
+    let barrel = part(
+        xyz(1.2, 0.6, 0.6),
+        base + xyz(0.0, 0.0, POLE_HEIGHT_METERS),
+        yaw,
+        BARREL_PITCH,
+        BARREL_COLOR,
+    );
+    let quads = pole.into_iter().chain(barrel).collect::<Vec<_>>();
+
+    let mut triangles = triangles_from_quads(&quads);
+    triangles.append(&mut line::colored_model(
+        &pipe(terrain, &cannon.pipe_from, &cannon.position),
+        PIPE_HEIGHT_METERS,
+        PIPE_COLOR,
+    ));
+
+    graphics
+        .draw_triangles(index, DrawMode::Solid, &triangles)
+        .unwrap();
+}
+
+pub fn draw_water_source(
+    graphics: &mut dyn Graphics,
+    index: &usize,
+    source: &WaterSource,
+    terrain: &Grid<f32>,
+) {
+    let quads = part(
+        xyz(2.0, 2.0, 1.5),
+        ground(terrain, &source.position) + xyz(0.0, 0.0, 0.75),
+        0.0,
+        0.0,
+        PUMP_HOUSE_COLOR,
+    );
+
+    graphics
+        .draw_triangles(index, DrawMode::Solid, &triangles_from_quads(&quads))
+        .unwrap();
+}
+
+fn ground(terrain: &Grid<f32>, position: &XY<u32>) -> XYZ<f32> {
+    xyz(position.x as f32, position.y as f32, terrain[position])
+}
+
+/// Pipe laid along the ground, roughly one segment per cell
+fn pipe(terrain: &Grid<f32>, from: &XY<u32>, to: &XY<u32>) -> Vec<[XYZ<f32>; 2]> {
+    let run = xy(to.x as f32 - from.x as f32, to.y as f32 - from.y as f32);
+    let steps = run.magnitude().ceil().max(1.0) as usize;
+    let points = (0..=steps)
+        .map(|step| {
+            let p = step as f32 / steps as f32;
+            let position = xy(
+                (from.x as f32 + run.x * p).round() as u32,
+                (from.y as f32 + run.y * p).round() as u32,
+            );
+            xyz(
+                from.x as f32 + run.x * p,
+                from.y as f32 + run.y * p,
+                terrain[position],
+            )
+        })
+        .collect::<Vec<_>>();
+    points.windows(2).map(|pair| [pair[0], pair[1]]).collect()
+}
+
+fn part(
+    scale: XYZ<f32>,
+    translation: XYZ<f32>,
+    yaw: f32,
+    pitch: f32,
+    color: Rgb<f32>,
+) -> Vec<Quad<Rgb<f32>>> {
+    cube::model()
+        .recolor(&|_| color)
+        .transform(&transformation_matrix(Transformation {
+            scale: Some(scale),
+            translation: Some(translation),
+            yaw: Some(yaw),
+            pitch: Some(pitch),
+            ..Transformation::default()
+        }))
+}
@@ -1,15 +1,21 @@
 use std::collections::HashMap;
 
+use commons::geometry::{xy, XYRectangle};
 use engine::binding::Binding;
 use engine::egui;
 use engine::engine::Engine;
 use engine::events::{Button, ButtonState, KeyboardKey};
 use engine::graphics::Graphics;
 
-use crate::services::mode;
+use crate::controllers::building_builder::{self, HEIGHT_MIN};
+use crate::model::weather::{Sky, Weather};
+use crate::model::{incident, piste};
+use crate::services::{mode, weather};
+use crate::utils::construction;
 use crate::widgets::entity_window::EntityWindow;
 use crate::widgets::{
-    self, building_editor, lift_editor, menu, piste_build_mode, toaster, ContextWidget, UiWidget,
+    self, building_editor, construction_queue, finance, goals, lift_editor, menu, piste_build_mode,
+    toaster, ContextWidget, UiWidget,
 };
 use crate::{Bindings, Game};
 
@@ -28,7 +34,7 @@ enum Panel {
     Build,
 }
 
-const MODE_BUTTONS: [ModeButton; 9] = [
+const MODE_BUTTONS: [ModeButton; 10] = [
     ModeButton {
         icon: "❓",
         hover_text: "Info",
@@ -77,6 +83,12 @@ const MODE_BUTTONS: [ModeButton; 9] = [
         build_mode: mode::Mode::Door,
         panel: Panel::Build,
     },
+    ModeButton {
+        icon: "💧",
+        hover_text: "Snowmaking",
+        build_mode: mode::Mode::Snowmaking,
+        panel: Panel::Build,
+    },
     ModeButton {
         icon: "💣",
         hover_text: "Demolish",
@@ -87,6 +99,9 @@ const MODE_BUTTONS: [ModeButton; 9] = [
 
 pub struct Widgets {
     pub building_editor: building_editor::Widget,
+    pub construction_queue: construction_queue::Widget,
+    pub finance: finance::Widget,
+    pub goals: goals::Widget,
     pub lift_editor: lift_editor::Widget,
     pub piste_build_mode: piste_build_mode::Widget,
     pub menu: menu::Widget,
@@ -119,7 +134,12 @@ pub fn run(
         }
     };
 
+    let mut finance_clicked = false;
+    let mut goals_clicked = false;
+    let mut construction_queue_clicked = false;
     let mut view_pistes_clicked = false;
+    let mut view_snow_clicked = false;
+    let mut view_grooming_clicked = false;
     let mut view_trees_clicked = false;
     let mut view_skier_abilities_clicked = false;
 
@@ -133,6 +153,8 @@ pub fn run(
         save_file: &game.config.save_file,
         save_directory: &game.config.save_directory,
         save_extension: &game.config.save_extension,
+        scenario_directory: &game.config.scenario_directory,
+        scenario_extension: &game.config.scenario_extension,
     });
     game.widgets.building_editor.init(building_editor::Input {
         mode: build_mode,
@@ -141,6 +163,7 @@ pub fn run(
     });
     game.widgets.lift_editor.init(lift_editor::Input {
         mode: build_mode,
+        terrain: &game.components.terrain,
         lift_builder: &game.controllers.lift_builder,
         lift_buildings: &game.components.lift_buildings,
     });
@@ -149,18 +172,47 @@ pub fn run(
         bindings: &game.bindings.piste_mode,
         piste_eraser: &game.controllers.piste_eraser,
     });
+    game.widgets.finance.init(&game.components);
+    game.widgets.goals.init(&game.components);
+    game.widgets.construction_queue.init(&game.components);
     game.widgets.toaster.init(());
+    let selection_cost = describe_selection_cost(game, build_mode);
     graphics.draw_gui(&mut |ctx| {
         ctx.set_pixels_per_point(PIXELS_PER_POINT);
         game.widgets.menu.draw(ctx);
         game.widgets.toaster.draw(ctx);
+        game.widgets.finance.draw(ctx);
+        game.widgets.goals.draw(ctx);
+        game.widgets.construction_queue.draw(ctx);
         egui::TopBottomPanel::bottom("base_panel").show(ctx, |ui| {
             ui.horizontal(|ui| {
                 ui.vertical(|ui| {
                     ui.label("Run");
                     ui.horizontal(|ui| {
-                        ui.add(egui::Slider::new(&mut speed, 0.0..=50.0).step_by(1.0));
+                        ui.add_enabled(
+                            !game.components.services.economy.is_bankrupt(),
+                            egui::Slider::new(&mut speed, 0.0..=50.0).step_by(1.0),
+                        );
                         draw_mode_buttons(ui, Panel::Run);
+                        let finance = ui
+                            .button(format!(
+                                "💰 {}",
+                                format_money(game.components.services.economy.ledger().balance())
+                            ))
+                            .on_hover_text("Finance");
+                        finance_clicked = finance.clicked();
+                        if game.widgets.finance.is_open() {
+                            finance.highlight();
+                        }
+                        ui.label(format!("⭐ {:.1}", game.components.rating.stars))
+                            .on_hover_text(rating_text(&game.components.incident_statistics));
+                        if game.components.scenario.is_some() {
+                            let goals = ui.button("🏁").on_hover_text("Goals");
+                            goals_clicked = goals.clicked();
+                            if game.widgets.goals.is_open() {
+                                goals.highlight();
+                            }
+                        }
                     });
                 });
                 ui.separator();
@@ -168,6 +220,13 @@ pub fn run(
                     ui.label("Build");
                     ui.horizontal(|ui| {
                         draw_mode_buttons(ui, Panel::Build);
+                        let construction_queue = ui
+                            .button(format!("🏗 {}", game.components.constructions.len()))
+                            .on_hover_text("Construction");
+                        construction_queue_clicked = construction_queue.clicked();
+                        if game.widgets.construction_queue.is_open() {
+                            construction_queue.highlight();
+                        }
                     });
                 });
                 ui.separator();
@@ -183,6 +242,24 @@ pub fn run(
                             pistes.highlight();
                         }
 
+                        let snow = ui.button("❄").on_hover_text(format!(
+                            "Snow ({})",
+                            describe_binding(&game.bindings.view.toggle_snow)
+                        ));
+                        view_snow_clicked = snow.clicked();
+                        if game.systems.terrain_artist.is_showing_snow() {
+                            snow.highlight();
+                        }
+
+                        let grooming = ui.button("🚜").on_hover_text(format!(
+                            "Last groomed ({})",
+                            describe_binding(&game.bindings.view.toggle_grooming)
+                        ));
+                        view_grooming_clicked = grooming.clicked();
+                        if game.systems.terrain_artist.is_showing_grooming() {
+                            grooming.highlight();
+                        }
+
                         let trees = ui.button("🌲").on_hover_text(format!(
                             "Trees ({})",
                             describe_binding(&game.bindings.view.toggle_trees)
@@ -203,9 +280,35 @@ pub fn run(
                     });
                 });
                 ui.separator();
+                ui.vertical(|ui| {
+                    ui.label("Weather");
+                    ui.horizontal(|ui| {
+                        let current = game.components.services.weather.current();
+                        ui.label(format!(
+                            "{} {:.0} m/s",
+                            weather_icon(current),
+                            current.wind_meters_per_second
+                        ))
+                        .on_hover_text(describe_weather(current));
+                        ui.separator();
+                        let hours_per_period = weather::PERIOD_MICROS / 3_600_000_000;
+                        for (i, forecast) in game.components.services.weather.forecast().enumerate()
+                        {
+                            ui.label(weather_icon(forecast)).on_hover_text(format!(
+                                "In {} hours: {}",
+                                (i as u128 + 1) * hours_per_period,
+                                describe_weather(forecast)
+                            ));
+                        }
+                    });
+                });
+                ui.separator();
                 game.widgets.building_editor.draw(ui);
                 game.widgets.lift_editor.draw(ui);
                 game.widgets.piste_build_mode.draw(ui);
+                if let Some(selection_cost) = &selection_cost {
+                    ui.label(selection_cost);
+                }
             });
         });
         for window in game.widgets.windows.values_mut() {
@@ -229,7 +332,13 @@ pub fn run(
             buildings: &mut game.components.buildings,
             artist: &mut game.systems.building_artist,
         });
+    game.widgets.finance.update(finance::Output {
+        components: &mut game.components,
+    });
+    game.widgets.construction_queue.update(());
+    game.widgets.goals.update(());
     game.widgets.lift_editor.update(lift_editor::Output {
+        lift_builder: &mut game.controllers.lift_builder,
         lift_buildings: &mut game.components.lift_buildings,
         lift_building_artist: &mut game.systems.lift_building_artist,
     });
@@ -245,12 +354,29 @@ pub fn run(
         window.update(widgets::entity_window::Output {
             components: &mut game.components,
             systems: &mut game.systems,
+            lift_builder: &mut game.controllers.lift_builder,
         });
         window.is_open()
     });
 
+    // a bankrupt resort stays frozen until a new game or save is loaded
+    if game.components.services.economy.is_bankrupt() {
+        speed = 0.0;
+    }
     game.components.services.clock.set_speed(speed);
 
+    if finance_clicked {
+        game.widgets.finance.toggle();
+    }
+
+    if goals_clicked {
+        game.widgets.goals.toggle();
+    }
+
+    if construction_queue_clicked {
+        game.widgets.construction_queue.toggle();
+    }
+
     for (i, &clicked) in mode_button_clicked.iter().enumerate() {
         if clicked {
             let config = &MODE_BUTTONS[i];
@@ -266,6 +392,16 @@ pub fn run(
         game.systems.terrain_artist.update_whole_overlay();
     }
 
+    if view_snow_clicked {
+        game.systems.terrain_artist.toggle_show_snow();
+        game.systems.terrain_artist.update_whole_overlay();
+    }
+
+    if view_grooming_clicked {
+        game.systems.terrain_artist.toggle_show_grooming();
+        game.systems.terrain_artist.update_whole_overlay();
+    }
+
     if view_trees_clicked {
         game.systems.tree_artist.toggle_visible(graphics);
     }
@@ -275,6 +411,109 @@ pub fn run(
     }
 }
 
+fn weather_icon(weather: &Weather) -> &'static str {
+    if weather.is_foggy() {
+        return "🌫";
+    }
+    match weather.sky {
+        Sky::Sunny => "☀",
+        Sky::Overcast => "☁",
+        Sky::Snowing => "🌨",
+        Sky::Storm => "🌪",
+    }
+}
+
+fn describe_weather(weather: &Weather) -> String {
+    let sky = match weather.sky {
+        Sky::Sunny => "Sunny",
+        Sky::Overcast => "Overcast",
+        Sky::Snowing => "Snowing",
+        Sky::Storm => "Storm",
+    };
+    format!(
+        "{}, wind {:.0} m/s, visibility {:.0} m",
+        sky, weather.wind_meters_per_second, weather.visibility_meters
+    )
+}
+
+/// Cost of building on the current selection, or the refund for erasing it
+fn rating_text(incident_statistics: &incident::Statistics) -> String {
+    let mut text = format!(
+        "Resort rating\n{} incidents in the last day",
+        incident_statistics.recent.len()
+    );
+    if let Some(wait_micros) = incident_statistics.average_wait_micros() {
+        text.push_str(&format!(
+            "\nAverage wait for the ski patrol: {} minutes",
+            wait_micros / 60_000_000
+        ));
+    }
+    text
+}
+
+fn describe_selection_cost(game: &Game, build_mode: mode::Mode) -> Option<String> {
+    let components = &game.components;
+    let selection = &components.selection;
+    let (Some(origin), Some(grid)) = (selection.cells.first(), &selection.grid) else {
+        return None;
+    };
+    let piste_map = &components.piste_map;
+
+    match build_mode {
+        mode::Mode::Piste | mode::Mode::Path if *game.controllers.piste_eraser.is_enabled() => {
+            let piste_id = piste_map[origin]?;
+            let piste = components.pistes.get(&piste_id)?;
+            let erased_cells = grid
+                .iter()
+                .filter(|cell| grid[cell] && piste_map[cell] == Some(piste_id))
+                .collect::<Vec<_>>();
+            let price = construction::piste_price(
+                &piste.class,
+                &components.terrain,
+                &components.trees,
+                erased_cells.iter(),
+            );
+            Some(format!(
+                "Refund: {}",
+                format_money(construction::refund(price))
+            ))
+        }
+        mode::Mode::Piste | mode::Mode::Path => {
+            let class = if build_mode == mode::Mode::Piste {
+                piste::Class::Piste
+            } else {
+                piste::Class::Path
+            };
+            let new_cells = grid
+                .iter()
+                .filter(|cell| grid[cell] && piste_map[cell].is_none())
+                .collect::<Vec<_>>();
+            let price = construction::piste_price(
+                &class,
+                &components.terrain,
+                &components.trees,
+                new_cells.iter(),
+            );
+            Some(format!("Cost: {}", format_money(price)))
+        }
+        mode::Mode::Gate => Some(format!("Cost: {}", format_money(construction::GATE_PRICE))),
+        mode::Mode::Door => Some(format!("Cost: {}", format_money(construction::DOOR_PRICE))),
+        mode::Mode::Building
+            if *game.controllers.building_builder.state() == building_builder::State::Selecting =>
+        {
+            let footprint = XYRectangle {
+                from: *grid.origin(),
+                to: *grid.origin() + xy(grid.width(), grid.height()),
+            };
+            Some(format!(
+                "Cost from: {}",
+                format_money(construction::building_price(&footprint, HEIGHT_MIN))
+            ))
+        }
+        _ => None,
+    }
+}
+
 fn mode_button_hover_text(bindings: &Bindings, mode_button: &ModeButton) -> String {
     let Some(binding) = bindings.mode.get(&mode_button.build_mode) else {
         return mode_button.hover_text.to_string();
@@ -282,6 +521,22 @@ fn mode_button_hover_text(bindings: &Bindings, mode_button: &ModeButton) -> Stri
     format!("{} ({})", mode_button.hover_text, describe_binding(binding))
 }
 
+pub fn format_money(amount: i64) -> String {
+    let digits = amount.unsigned_abs().to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+    if amount < 0 {
+        format!("-${}", grouped)
+    } else {
+        format!("${}", grouped)
+    }
+}
+
 pub fn describe_binding(binding: &Binding) -> String {
     match binding {
         Binding::Single { button, state } => match state {
@@ -40,9 +40,9 @@ pub fn handle(
     };
     let position = xy(x.round() as u32, y.round() as u32);
 
-    for lift in lifts.values() {
-        let pick_up_id = &lift.pick_up.id;
-        if lift.pick_up.state.position == position {
+    for pick_up in lifts.values().flat_map(|lift| lift.pick_ups()) {
+        let pick_up_id = &pick_up.id;
+        if pick_up.state.position == position {
             global_targets.clear();
 
             for &skier_id in skiers.keys() {
@@ -26,6 +26,16 @@ pub fn handle(
         terrain_artist.update_whole_overlay();
     }
 
+    if bindings.toggle_snow.binds_event(event) {
+        terrain_artist.toggle_show_snow();
+        terrain_artist.update_whole_overlay();
+    }
+
+    if bindings.toggle_grooming.binds_event(event) {
+        terrain_artist.toggle_show_grooming();
+        terrain_artist.update_whole_overlay();
+    }
+
     if bindings.toggle_trees.binds_event(event) {
         tree_artist.toggle_visible(graphics);
     }
@@ -37,6 +47,8 @@ pub fn handle(
 
 pub struct Bindings {
     pub toggle_pistes: Binding,
+    pub toggle_snow: Binding,
+    pub toggle_grooming: Binding,
     pub toggle_trees: Binding,
     pub toggle_skier_ability: Binding,
 }
@@ -32,6 +32,7 @@ use engine::graphics::projections::isometric;
 use engine::graphics::Graphics;
 use engine::handlers::{drag, yaw, zoom};
 use serde::{Deserialize, Serialize};
+use terrain_gen::Rain;
 use tokio::sync::broadcast::{self};
 
 use crate::controllers::building_builder::FinalizeParameters;
@@ -43,30 +44,42 @@ use crate::init::trees::generate_trees;
 use crate::model::ability::Ability;
 use crate::model::building::Building;
 use crate::model::carousel::{Car, Carousel};
+use crate::model::construction;
 use crate::model::costs::Costs;
 use crate::model::door::Door;
 use crate::model::entrance::Entrance;
 use crate::model::exit::Exit;
 use crate::model::frame::Frame;
 use crate::model::gate::Gate;
+use crate::model::groomer::Groomer;
+use crate::model::group::Group;
 use crate::model::hash_vec::HashVec;
+use crate::model::incident::{self, Incident};
 use crate::model::lift::Lift;
 use crate::model::lift_building::LiftBuildings;
+use crate::model::lift_queue::LiftQueue;
+use crate::model::needs::Needs;
 use crate::model::open;
+use crate::model::patroller::Patroller;
 use crate::model::piste::{self, Piste};
+use crate::model::reliability::Reliability;
 use crate::model::reservation::Reservation;
+use crate::model::satisfaction::{Rating, Satisfaction};
+use crate::model::scenario::{self, Scenario};
 use crate::model::selection::Selection;
 use crate::model::skier::{Clothes, Skier};
 use crate::model::skiing::{self, State};
+use crate::model::snow::Snow;
+use crate::model::snowmaking::{Cannon, WaterSource};
 use crate::model::tree::Tree;
 use crate::services::{id_allocator, mode};
 use crate::systems::door::Parameters;
 use crate::systems::{
     building_artist, carousel, chair_artist, chair_framer, closer, door, door_artist, frame_artist,
-    frame_wiper, gate, gate_artist, global_computer, global_target_setter, lift_artist,
-    lift_building_artist, log, messenger, piste_adopter, piste_computer, planner,
-    selection_rasterizer, skiing_framer, target_checker, target_setter, terrain_artist,
-    tree_artist, window_artist,
+    frame_wiper, gate, gate_artist, global_computer, global_target_setter, groomer_framer,
+    lift_artist, lift_building_artist, lift_queue, log, messenger, patrol_framer, piste_adopter,
+    piste_computer, planner, queue_framer, reliability, selection_rasterizer, skiing_framer,
+    snowmaking_artist, target_checker, target_setter, terrain_artist, tree_artist, window_artist,
 };
 use crate::utils::computer;
 use crate::widgets::{building_editor, lift_editor, menu, toaster};
@@ -141,6 +154,9 @@ fn new_game(components: Components, save_file: Option<String>) -> Game {
         },
         widgets: Widgets {
             building_editor: building_editor::Widget::default(),
+            construction_queue: widgets::construction_queue::Widget::default(),
+            finance: widgets::finance::Widget::default(),
+            goals: widgets::goals::Widget::default(),
             lift_editor: lift_editor::Widget::default(),
             menu: menu::Widget::default(),
             piste_build_mode: widgets::piste_build_mode::Widget::default(),
@@ -157,8 +173,20 @@ fn new_game(components: Components, save_file: Option<String>) -> Game {
             building_artist: building_artist::System::new(),
             carousel: carousel::System::new(),
             chair_artist: chair_artist::System::new(),
+            construction: systems::construction::System::new(),
+            economy: systems::economy::System::new(),
+            scenario: systems::scenario::System::new(),
             global_computer: global_computer::System::new(),
             piste_computer: piste_computer::System::new(),
+            reliability: reliability::System::new(),
+            hotels: systems::hotels::System::new(),
+            incidents: systems::incidents::System::new(),
+            satisfaction: systems::satisfaction::System::new(),
+            services: systems::services::System::new(),
+            visitors: systems::visitors::System::new(),
+            snow: systems::snow::System::new(),
+            grooming: systems::grooming::System::new(),
+            snowmaking: systems::snowmaking::System::new(),
             lift_building_artist: lift_building_artist::System::default(),
             skier_colors: systems::skier_colors::System::new(
                 systems::skier_colors::AbilityColors {
@@ -182,10 +210,21 @@ fn new_game(components: Components, save_file: Option<String>) -> Game {
                     expert: Rgba::new(34, 28, 34, 192),
                     ungraded: Rgba::new(247, 147, 28, 192),
                 },
+                snow: terrain_artist::SnowColors {
+                    powder: Rgba::new(255, 255, 255, 160),
+                    packed: Rgba::new(190, 200, 210, 160),
+                    icy: Rgba::new(120, 200, 240, 160),
+                    slush: Rgba::new(170, 150, 110, 160),
+                    bare: Rgba::new(110, 80, 50, 192),
+                },
+                groomed: Rgba::new(255, 215, 0, 160),
+                construction: Rgba::new(160, 160, 160, 128),
                 cliff: Rgba::new(46, 46, 46, 128),
+                fog: Rgba::new(230, 230, 235, 160),
             }),
             messenger: messenger::System::new(tx),
             tree_artist: tree_artist::System::new(),
+            weather_artist: systems::weather_artist::System::new(),
             window_artist: window_artist::System::new(),
         },
         bindings: Bindings {
@@ -267,6 +306,13 @@ fn new_game(components: Components, save_file: Option<String>) -> Game {
                         state: ButtonState::Pressed,
                     },
                 ),
+                (
+                    mode::Mode::Snowmaking,
+                    Binding::Single {
+                        button: Button::Keyboard(KeyboardKey::from("m")),
+                        state: ButtonState::Pressed,
+                    },
+                ),
                 (
                     mode::Mode::Demolish,
                     Binding::Single {
@@ -312,6 +358,14 @@ fn new_game(components: Components, save_file: Option<String>) -> Game {
                     button: Button::Keyboard(KeyboardKey::from("P")),
                     state: ButtonState::Pressed,
                 },
+                toggle_snow: Binding::Single {
+                    button: Button::Keyboard(KeyboardKey::from("s")),
+                    state: ButtonState::Pressed,
+                },
+                toggle_grooming: Binding::Single {
+                    button: Button::Keyboard(KeyboardKey::from("G")),
+                    state: ButtonState::Pressed,
+                },
                 toggle_trees: Binding::Single {
                     button: Button::Keyboard(KeyboardKey::from("t")),
                     state: ButtonState::Pressed,
@@ -366,6 +420,8 @@ fn new_game(components: Components, save_file: Option<String>) -> Game {
             save_file,
             save_directory: "./saves/".to_string(),
             save_extension: "save".to_string(),
+            scenario_directory: "./scenarios/".to_string(),
+            scenario_extension: "toml".to_string(),
         },
         mouse_xy: None,
         components,
@@ -388,10 +444,32 @@ const DEFAULT_NEW_GAME_PARAMETERS: NewGameParameters = NewGameParameters {
 };
 
 fn new_components(parameters: NewGameParameters) -> Components {
+    let seed = parameters.terrain.seed;
     let terrain = generate_heightmap(parameters.terrain);
     let trees = generate_trees(&terrain, parameters.trees);
+    components_from_terrain(terrain, trees, seed)
+}
+
+fn new_scenario_components(scenario: &Scenario, terrain: Grid<f32>) -> Components {
+    let trees = generate_trees(
+        &terrain,
+        init::trees::Parameters {
+            power: terrain.width().ilog2(),
+            tree_line_elevation: scenario.tree_line_elevation,
+        },
+    );
+    let mut components = components_from_terrain(terrain, trees, scenario.terrain.seed());
+    components.services.economy =
+        services::economy::Service::with_balance(scenario.starting_balance);
+    components.scenario = Some(scenario::Progress::new(scenario));
+    components
+}
+
+fn components_from_terrain(terrain: Grid<f32>, trees: Grid<Option<Tree>>, seed: i32) -> Components {
+    let weather = services::weather::Service::new(seed as u64);
     Components {
         skiers: HashMap::default(),
+        groups: HashMap::default(),
         plans: HashMap::default(),
         locations: HashMap::default(),
         targets: HashMap::default(),
@@ -403,11 +481,20 @@ fn new_components(parameters: NewGameParameters) -> Components {
         global_costs: Costs::new(),
         lifts: HashMap::default(),
         lift_buildings: HashMap::default(),
+        lift_queues: HashMap::default(),
+        reliabilities: HashMap::default(),
+        groomers: HashMap::default(),
+        constructions: construction::Queue::default(),
+        water_sources: HashMap::default(),
+        cannons: HashMap::default(),
         carousels: HashMap::default(),
         cars: HashMap::default(),
         gates: HashMap::default(),
         reservations: Grid::default(terrain.width(), terrain.height()),
         piste_map: Grid::default(terrain.width(), terrain.height()),
+        snow: Grid::default(terrain.width(), terrain.height()),
+        groomed: Grid::default(terrain.width(), terrain.height()),
+        rain: terrain.rain(),
         exits: HashMap::default(),
         entrances: HashMap::default(),
         abilities: HashMap::default(),
@@ -418,13 +505,25 @@ fn new_components(parameters: NewGameParameters) -> Components {
         parents: HashMap::default(),
         children: HashMap::default(),
         highlights: HashSet::default(),
+        wind_holds: HashSet::default(),
+        satisfaction: HashMap::default(),
+        needs: HashMap::default(),
+        visits: HashMap::default(),
+        check_outs: HashMap::default(),
+        incidents: HashMap::default(),
+        incident_statistics: incident::Statistics::default(),
+        patrollers: HashMap::default(),
+        rating: Rating::default(),
         terrain,
         trees,
         planning_queue: HashVec::new(),
         selection: Selection::default(),
+        scenario: None,
         services: Services {
             clock: services::clock::Service::new(),
+            economy: services::economy::Service::new(),
             id_allocator: id_allocator::Service::new(),
+            weather,
             mode: mode::Service::default(),
         },
     }
@@ -446,12 +545,14 @@ struct Game {
 enum Command {
     None,
     NewGame(NewGameParameters),
+    NewScenario(String),
     LoadGame(String),
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct Components {
     skiers: HashMap<usize, Skier>,
+    groups: HashMap<usize, Group>,
     plans: HashMap<usize, skiing::Plan>,
     locations: HashMap<usize, usize>,
     targets: HashMap<usize, usize>,
@@ -467,6 +568,12 @@ pub struct Components {
     global_costs: Costs<usize>,
     lifts: HashMap<usize, Lift>,
     lift_buildings: HashMap<usize, LiftBuildings>,
+    lift_queues: HashMap<usize, LiftQueue>,
+    reliabilities: HashMap<usize, Reliability>,
+    groomers: HashMap<usize, Groomer>,
+    constructions: construction::Queue,
+    water_sources: HashMap<usize, WaterSource>,
+    cannons: HashMap<usize, Cannon>,
     cars: HashMap<usize, Car>,
     carousels: HashMap<usize, Carousel>,
     gates: HashMap<usize, Gate>,
@@ -480,13 +587,29 @@ pub struct Components {
     children: HashMap<usize, Vec<usize>>,
     #[serde(skip)]
     highlights: HashSet<usize>,
+    wind_holds: HashSet<usize>,
+    satisfaction: HashMap<usize, Satisfaction>,
+    needs: HashMap<usize, Needs>,
+    /// When each skier visiting a service building is done there
+    visits: HashMap<usize, u128>,
+    /// Day on which each hotel guest checks out
+    check_outs: HashMap<usize, u128>,
+    /// Injured skiers waiting for or being carried by the ski patrol
+    incidents: HashMap<usize, Incident>,
+    incident_statistics: incident::Statistics,
+    patrollers: HashMap<usize, Patroller>,
+    rating: Rating,
     terrain: Grid<f32>,
     trees: Grid<Option<Tree>>,
     reservations: Grid<HashMap<usize, Reservation>>,
     piste_map: Grid<Option<usize>>,
+    snow: Grid<Snow>,
+    groomed: Grid<Option<u128>>,
+    rain: Grid<usize>,
     planning_queue: HashVec<usize>,
     #[serde(skip)]
     selection: Selection,
+    scenario: Option<scenario::Progress>,
     services: Services,
 }
 
@@ -511,13 +634,26 @@ struct Systems {
     building_artist: building_artist::System,
     carousel: carousel::System,
     chair_artist: chair_artist::System,
+    construction: systems::construction::System,
+    economy: systems::economy::System,
     global_computer: global_computer::System,
     piste_computer: piste_computer::System,
+    reliability: reliability::System,
+    hotels: systems::hotels::System,
+    incidents: systems::incidents::System,
+    satisfaction: systems::satisfaction::System,
+    services: systems::services::System,
+    visitors: systems::visitors::System,
+    scenario: systems::scenario::System,
+    snow: systems::snow::System,
+    grooming: systems::grooming::System,
+    snowmaking: systems::snowmaking::System,
     lift_building_artist: lift_building_artist::System,
     messenger: messenger::System,
     skier_colors: systems::skier_colors::System,
     terrain_artist: terrain_artist::System,
     tree_artist: tree_artist::System,
+    weather_artist: systems::weather_artist::System,
     window_artist: window_artist::System,
 }
 
@@ -539,12 +675,16 @@ pub struct Config {
     save_file: Option<String>,
     save_directory: String,
     save_extension: String,
+    scenario_directory: String,
+    scenario_extension: String,
 }
 
 #[derive(Serialize, Deserialize)]
 pub struct Services {
     clock: services::clock::Service,
+    economy: services::economy::Service,
     id_allocator: id_allocator::Service,
+    weather: services::weather::Service,
     #[serde(skip)]
     mode: services::mode::Service,
 }
@@ -559,6 +699,7 @@ impl Game {
         self.systems.chair_artist.init(graphics);
         self.systems.terrain_artist.init(graphics, terrain);
         self.systems.tree_artist.init(graphics);
+        self.systems.weather_artist.init(graphics);
         self.systems.window_artist.init(graphics);
         graphics.look_at(
             &xyz(
@@ -588,6 +729,37 @@ impl Game {
         self.init(graphics);
     }
 
+    fn new_scenario(&mut self, file: String, graphics: &mut dyn Graphics) {
+        let path = format!(
+            "{}{}.{}",
+            self.config.scenario_directory, file, self.config.scenario_extension
+        );
+        let scenario = utils::scenario::load(&path).and_then(|scenario| {
+            utils::scenario::terrain(&scenario.terrain, &self.config.scenario_directory)
+                .map(|terrain| (scenario, terrain))
+        });
+        match scenario {
+            Ok((scenario, terrain)) => {
+                let components = new_scenario_components(&scenario, terrain);
+                *self = new_game(components, None);
+                graphics.clear();
+                self.init(graphics);
+                utils::scenario::place_structures(
+                    &scenario.structures,
+                    &mut self.components,
+                    &mut self.systems,
+                );
+                self.widgets.goals.toggle();
+            }
+            Err(error) => {
+                self.systems
+                    .messenger
+                    .send(format!("Could not load scenario {}: {}", file, error));
+                self.command = Command::None;
+            }
+        }
+    }
+
     fn load(&mut self, file: String, graphics: &mut dyn Graphics) {
         let components = load_components(&format!(
             "{}{}.{}",
@@ -613,6 +785,7 @@ impl EventHandler for Game {
     fn handle(&mut self, event: &Event, engine: &mut dyn Engine, graphics: &mut dyn Graphics) {
         match self.command.clone() {
             Command::NewGame(parameters) => self.new_game(parameters, graphics),
+            Command::NewScenario(file) => self.new_scenario(file, graphics),
             Command::LoadGame(file) => self.load(file.clone(), graphics),
             Command::None => (),
         }
@@ -688,16 +861,85 @@ impl EventHandler for Game {
         self.controllers
             .building_builder
             .finalize(FinalizeParameters {
+                pistes: &self.components.pistes,
+                piste_map: &self.components.piste_map,
+                buildings: &mut self.components.buildings,
+                constructions: &mut self.components.constructions,
+                building_artist: &mut self.systems.building_artist,
+                economy: &mut self.components.services.economy,
+                messenger: &mut self.systems.messenger,
+            });
+
+        self.systems
+            .construction
+            .run(systems::construction::Parameters {
+                micros: &self.components.services.clock.get_micros(),
                 terrain: &self.components.terrain,
+                pistes: &self.components.pistes,
+                lifts: &self.components.lifts,
+                entrances: &self.components.entrances,
+                exits: &self.components.exits,
+                constructions: &mut self.components.constructions,
+                open: &mut self.components.open,
                 id_allocator: &mut self.components.services.id_allocator,
                 buildings: &mut self.components.buildings,
                 locations: &mut self.components.locations,
                 skiers: &mut self.components.skiers,
+                groups: &mut self.components.groups,
+                rating: &self.components.rating,
+                piste_computer: &mut self.systems.piste_computer,
+                terrain_artist: &mut self.systems.terrain_artist,
                 building_artist: &mut self.systems.building_artist,
+                lift_building_artist: &mut self.systems.lift_building_artist,
                 window_artist: &mut self.systems.window_artist,
                 messenger: &mut self.systems.messenger,
             });
 
+        systems::weather::run(systems::weather::Parameters {
+            micros: &self.components.services.clock.get_micros(),
+            lifts: &self.components.lifts,
+            reliabilities: &self.components.reliabilities,
+            weather: &mut self.components.services.weather,
+            wind_holds: &mut self.components.wind_holds,
+            open: &mut self.components.open,
+            global_computer: &mut self.systems.global_computer,
+            terrain_artist: &mut self.systems.terrain_artist,
+            messenger: &mut self.systems.messenger,
+        });
+        self.systems.snow.run(systems::snow::Parameters {
+            micros: &self.components.services.clock.get_micros(),
+            terrain: &self.components.terrain,
+            plans: &self.components.plans,
+            weather: self.components.services.weather.current(),
+            piste_map: &self.components.piste_map,
+            snow: &mut self.components.snow,
+            piste_computer: &mut self.systems.piste_computer,
+            terrain_artist: &mut self.systems.terrain_artist,
+        });
+        self.systems
+            .snowmaking
+            .run(systems::snowmaking::Parameters {
+                micros: &self.components.services.clock.get_micros(),
+                terrain: &self.components.terrain,
+                piste_map: &self.components.piste_map,
+                water_sources: &self.components.water_sources,
+                cannons: &mut self.components.cannons,
+                snow: &mut self.components.snow,
+                piste_computer: &mut self.systems.piste_computer,
+                terrain_artist: &mut self.systems.terrain_artist,
+            });
+        self.systems.grooming.run(systems::grooming::Parameters {
+            micros: &self.components.services.clock.get_micros(),
+            pistes: &self.components.pistes,
+            piste_map: &self.components.piste_map,
+            open: &self.components.open,
+            groomers: &mut self.components.groomers,
+            groomed: &mut self.components.groomed,
+            snow: &mut self.components.snow,
+            terrain_artist: &mut self.systems.terrain_artist,
+            messenger: &mut self.systems.messenger,
+        });
+
         self.systems
             .piste_computer
             .run(systems::piste_computer::Parameters {
@@ -705,6 +947,7 @@ impl EventHandler for Game {
                 entrances: &self.components.entrances,
                 exits: &self.components.exits,
                 terrain: &self.components.terrain,
+                snow: &self.components.snow,
                 reservations: &self.components.reservations,
                 costs: &mut self.components.costs,
                 abilities: &mut self.components.abilities,
@@ -718,6 +961,7 @@ impl EventHandler for Game {
             .run(computer::global_costs::Parameters {
                 lifts: &self.components.lifts,
                 carousels: &self.components.carousels,
+                lift_queues: &self.components.lift_queues,
                 entrances: &self.components.entrances,
                 exits: &self.components.exits,
                 costs: &self.components.costs,
@@ -726,8 +970,20 @@ impl EventHandler for Game {
                 global_costs: &mut self.components.global_costs,
             });
 
+        self.systems
+            .reliability
+            .run(systems::reliability::Parameters {
+                micros: &self.components.services.clock.get_micros(),
+                lifts: &self.components.lifts,
+                reliabilities: &mut self.components.reliabilities,
+                open: &mut self.components.open,
+                global_computer: &mut self.systems.global_computer,
+                messenger: &mut self.systems.messenger,
+            });
         self.systems.carousel.run(systems::carousel::Parameters {
             micros: &self.components.services.clock.get_micros(),
+            skiers: &self.components.skiers,
+            groups: &self.components.groups,
             lifts: &self.components.lifts,
             open: &self.components.open,
             carousels: &self.components.carousels,
@@ -738,6 +994,64 @@ impl EventHandler for Game {
             targets: &mut self.components.targets,
             global_targets: &mut self.components.global_targets,
             cars: &mut self.components.cars,
+            lift_queues: &mut self.components.lift_queues,
+            global_costs: &self.components.global_costs,
+            reliabilities: &self.components.reliabilities,
+            satisfaction: &mut self.components.satisfaction,
+            economy: &mut self.components.services.economy,
+        });
+        self.systems
+            .satisfaction
+            .run(systems::satisfaction::Parameters {
+                micros: &self.components.services.clock.get_micros(),
+                skiers: &self.components.skiers,
+                plans: &self.components.plans,
+                locations: &self.components.locations,
+                pistes: &self.components.pistes,
+                abilities: &self.components.abilities,
+                reservations: &self.components.reservations,
+                incident_statistics: &self.components.incident_statistics,
+                weather: self.components.services.weather.current(),
+                satisfaction: &mut self.components.satisfaction,
+                rating: &mut self.components.rating,
+            });
+        self.systems.scenario.run(systems::scenario::Parameters {
+            micros: &self.components.services.clock.get_micros(),
+            terrain: &self.components.terrain,
+            pistes: &self.components.pistes,
+            abilities: &self.components.abilities,
+            open: &self.components.open,
+            constructions: &self.components.constructions,
+            economy: &self.components.services.economy,
+            rating: &self.components.rating,
+            scenario: &mut self.components.scenario,
+            messenger: &mut self.systems.messenger,
+        });
+        self.systems.economy.run(systems::economy::Parameters {
+            micros: &self.components.services.clock.get_micros(),
+            skiers: &self.components.skiers,
+            buildings: &self.components.buildings,
+            lifts: &self.components.lifts,
+            pistes: &self.components.pistes,
+            open: &self.components.open,
+            economy: &mut self.components.services.economy,
+            messenger: &mut self.systems.messenger,
+        });
+        lift_queue::run(lift_queue::Parameters {
+            micros: &self.components.services.clock.get_micros(),
+            skiers: &self.components.skiers,
+            groups: &self.components.groups,
+            lifts: &self.components.lifts,
+            carousels: &self.components.carousels,
+            exits: &self.components.exits,
+            open: &self.components.open,
+            reservations: &mut self.components.reservations,
+            plans: &mut self.components.plans,
+            locations: &mut self.components.locations,
+            targets: &mut self.components.targets,
+            global_targets: &mut self.components.global_targets,
+            lift_queues: &mut self.components.lift_queues,
+            global_computer: &mut self.systems.global_computer,
         });
 
         target_checker::run(&self.components.open, &mut self.components.targets);
@@ -745,21 +1059,30 @@ impl EventHandler for Game {
             &self.components.plans,
             &self.components.piste_map,
             &self.components.skiers,
+            &self.components.groups,
             &self.components.abilities,
             &mut self.components.locations,
         );
         global_target_setter::run(global_target_setter::Parameters {
+            micros: &self.components.services.clock.get_micros(),
             skiers: &self.components.skiers,
+            groups: &self.components.groups,
             plans: &self.components.plans,
             locations: &self.components.locations,
             lifts: &self.components.lifts,
+            buildings: &self.components.buildings,
             doors: &self.components.doors,
             costs: &self.components.costs,
             global_costs: &self.components.global_costs,
+            needs: &self.components.needs,
+            check_outs: &self.components.check_outs,
+            weather: self.components.services.weather.current(),
             global_targets: &mut self.components.global_targets,
         });
         target_setter::run(target_setter::Parameters {
+            micros: &self.components.services.clock.get_micros(),
             skiers: &self.components.skiers,
+            groups: &self.components.groups,
             plans: &self.components.plans,
             locations: &self.components.locations,
             doors: &self.components.doors,
@@ -768,27 +1091,105 @@ impl EventHandler for Game {
             open: &self.components.open,
             global_targets: &mut self.components.global_targets,
             targets: &mut self.components.targets,
+            satisfaction: &mut self.components.satisfaction,
+        });
+
+        let checked_out = self.systems.hotels.run(systems::hotels::Parameters {
+            micros: &self.components.services.clock.get_micros(),
+            buildings: &self.components.buildings,
+            doors: &self.components.doors,
+            plans: &self.components.plans,
+            targets: &self.components.targets,
+            demand: self.components.services.weather.outlook(),
+            rating: &self.components.rating,
+            id_allocator: &mut self.components.services.id_allocator,
+            locations: &mut self.components.locations,
+            skiers: &mut self.components.skiers,
+            groups: &mut self.components.groups,
+            check_outs: &mut self.components.check_outs,
+            messenger: &mut self.systems.messenger,
+        });
+        utils::skiers::remove(graphics, &mut self.components, &checked_out);
+
+        let departed = self.systems.visitors.run(systems::visitors::Parameters {
+            micros: &self.components.services.clock.get_micros(),
+            buildings: &self.components.buildings,
+            doors: &self.components.doors,
+            plans: &self.components.plans,
+            targets: &self.components.targets,
+            weather: self.components.services.weather.current(),
+            rating: &self.components.rating,
+            id_allocator: &mut self.components.services.id_allocator,
+            locations: &mut self.components.locations,
+            skiers: &mut self.components.skiers,
+            groups: &mut self.components.groups,
+            messenger: &mut self.systems.messenger,
+        });
+        utils::skiers::remove(graphics, &mut self.components, &departed);
+
+        self.systems.services.run(systems::services::Parameters {
+            micros: &self.components.services.clock.get_micros(),
+            buildings: &self.components.buildings,
+            doors: &self.components.doors,
+            plans: &mut self.components.plans,
+            locations: &mut self.components.locations,
+            targets: &mut self.components.targets,
+            global_targets: &mut self.components.global_targets,
+            reservations: &mut self.components.reservations,
+            needs: &mut self.components.needs,
+            visits: &mut self.components.visits,
+            economy: &mut self.components.services.economy,
         });
 
         door::run(Parameters {
+            micros: &self.components.services.clock.get_micros(),
             doors: &self.components.doors,
+            open: &self.components.open,
+            skiers: &self.components.skiers,
+            weather: self.components.services.weather.current(),
             reservations: &mut self.components.reservations,
             locations: &mut self.components.locations,
             plans: &mut self.components.plans,
+            visits: &mut self.components.visits,
+        });
+        gate::run(gate::Parameters {
+            skiers: &self.components.skiers,
+            groups: &self.components.groups,
+            plans: &self.components.plans,
+            gates: &self.components.gates,
+            entrances: &self.components.entrances,
+            open: &self.components.open,
+            targets: &mut self.components.targets,
+            global_targets: &mut self.components.global_targets,
+            locations: &mut self.components.locations,
+        });
+        self.systems.incidents.run(systems::incidents::Parameters {
+            micros: &self.components.services.clock.get_micros(),
+            terrain: &self.components.terrain,
+            snow: &self.components.snow,
+            skiers: &self.components.skiers,
+            pistes: &self.components.pistes,
+            abilities: &self.components.abilities,
+            buildings: &self.components.buildings,
+            doors: &self.components.doors,
+            open: &self.components.open,
+            id_allocator: &mut self.components.services.id_allocator,
+            plans: &mut self.components.plans,
+            locations: &mut self.components.locations,
+            reservations: &mut self.components.reservations,
+            visits: &mut self.components.visits,
+            incidents: &mut self.components.incidents,
+            incident_statistics: &mut self.components.incident_statistics,
+            patrollers: &mut self.components.patrollers,
+            satisfaction: &mut self.components.satisfaction,
+            messenger: &mut self.systems.messenger,
         });
-        gate::run(
-            &self.components.plans,
-            &self.components.gates,
-            &self.components.entrances,
-            &self.components.open,
-            &mut self.components.targets,
-            &mut self.components.global_targets,
-            &mut self.components.locations,
-        );
         planner::run(systems::planner::Parameters {
             terrain: &self.components.terrain,
+            snow: &self.components.snow,
             micros: &self.components.services.clock.get_micros(),
             skiers: &mut self.components.skiers,
+            groups: &self.components.groups,
             locations: &self.components.locations,
             targets: &self.components.targets,
             pistes: &self.components.pistes,
@@ -796,6 +1197,8 @@ impl EventHandler for Game {
             plans: &mut self.components.plans,
             reservations: &mut self.components.reservations,
             planning_queue: &mut self.components.planning_queue,
+            satisfaction: &mut self.components.satisfaction,
+            incidents: &self.components.incidents,
         });
 
         closer::run(
@@ -814,14 +1217,39 @@ impl EventHandler for Game {
             &self.components.terrain,
             &self.components.services.clock.get_micros(),
             &self.components.plans,
+            &self.components.skiers,
             &self.components.clothes,
             &mut self.components.frames,
         );
-        chair_framer::run(
-            &self.components.carousels,
+        chair_framer::run(chair_framer::Parameters {
+            terrain: &self.components.terrain,
+            carousels: &self.components.carousels,
+            lifts: &self.components.lifts,
+            cars: &self.components.cars,
+            locations: &self.components.locations,
+            skiers: &self.components.skiers,
+            clothes: &self.components.clothes,
+            frames: &mut self.components.frames,
+        });
+        groomer_framer::run(
+            &self.components.terrain,
+            &self.components.groomers,
+            &self.components.open,
+            &mut self.components.frames,
+        );
+        queue_framer::run(
+            &self.components.terrain,
             &self.components.lifts,
-            &self.components.cars,
-            &self.components.locations,
+            &self.components.lift_queues,
+            &self.components.skiers,
+            &self.components.clothes,
+            &mut self.components.frames,
+        );
+        patrol_framer::run(
+            &self.components.terrain,
+            &self.components.services.clock.get_micros(),
+            &self.components.incidents,
+            &self.components.patrollers,
             &self.components.clothes,
             &mut self.components.frames,
         );
@@ -851,6 +1279,15 @@ impl EventHandler for Game {
         self.systems.lift_building_artist.run(
             graphics,
             &self.components.lift_buildings,
+            &self.components.lifts,
+            &self.components.constructions,
+            &self.components.terrain,
+            &mut self.components.drawings,
+        );
+        snowmaking_artist::run(
+            graphics,
+            &self.components.water_sources,
+            &self.components.cannons,
             &self.components.terrain,
             &mut self.components.drawings,
         );
@@ -882,8 +1319,13 @@ impl EventHandler for Game {
             .run(systems::terrain_artist::Parameters {
                 terrain: &self.components.terrain,
                 piste_map: &self.components.piste_map,
+                snow: &self.components.snow,
+                groomed: &self.components.groomed,
+                micros: &self.components.services.clock.get_micros(),
+                weather: self.components.services.weather.current(),
                 highlights: &self.components.highlights,
                 abilities: &self.components.abilities,
+                constructions: &self.components.constructions,
                 selection: &self.components.selection,
                 graphics,
             });
@@ -897,6 +1339,9 @@ impl EventHandler for Game {
         self.systems
             .window_artist
             .run(&self.components.buildings, graphics);
+        self.systems
+            .weather_artist
+            .run(self.components.services.weather.current(), graphics);
 
         gui::run(self, event, engine, graphics);
     }
@@ -5,6 +5,7 @@ use crate::model::direction::Direction;
 
 #[derive(Serialize, Deserialize)]
 pub struct Building {
+    pub kind: Kind,
     pub footprint: XYRectangle<u32>,
     pub height: u32,
     pub roof: Roof,
@@ -12,6 +13,47 @@ pub struct Building {
     pub windows: Vec<Window>,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Kind {
+    Hotel,
+    /// Where day visitors arrive by car
+    CarPark,
+    /// Where day visitors arrive by bus
+    BusStop,
+    Restaurant,
+    Toilets,
+    TicketOffice,
+    RentalShop,
+    /// Where ski patrollers are sent out from to help injured skiers
+    PatrolHut,
+    /// Where injured skiers are brought by the ski patrol to be treated
+    FirstAid,
+}
+
+pub const KINDS: [Kind; 9] = [
+    Kind::Hotel,
+    Kind::CarPark,
+    Kind::BusStop,
+    Kind::Restaurant,
+    Kind::Toilets,
+    Kind::TicketOffice,
+    Kind::RentalShop,
+    Kind::PatrolHut,
+    Kind::FirstAid,
+];
+
+/// What skiers get from visiting a service building
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Service {
+    /// Hunger taken away by a visit
+    pub hunger: f32,
+    /// Tiredness taken away by a visit
+    pub rest: f32,
+    pub dwell_micros: u128,
+    /// Spent by each skier on each visit
+    pub price: i64,
+}
+
 #[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum Roof {
     Peaked,
@@ -21,6 +63,58 @@ pub enum Roof {
 
 pub const ROOFS: [Roof; 3] = [Roof::Peaked, Roof::PeakedRotated, Roof::Flat];
 
+impl Kind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Kind::Hotel => "Hotel",
+            Kind::CarPark => "Car Park",
+            Kind::BusStop => "Bus Stop",
+            Kind::Restaurant => "Restaurant",
+            Kind::Toilets => "Toilets",
+            Kind::TicketOffice => "Ticket Office",
+            Kind::RentalShop => "Rental Shop",
+            Kind::PatrolHut => "Patrol Hut",
+            Kind::FirstAid => "First Aid",
+        }
+    }
+
+    pub fn service(&self) -> Option<Service> {
+        let minutes = |minutes: u128| minutes * 60 * 1_000_000;
+        match self {
+            Kind::Hotel | Kind::CarPark | Kind::BusStop | Kind::PatrolHut | Kind::FirstAid => None,
+            Kind::Restaurant => Some(Service {
+                hunger: 1.0,
+                rest: 0.5,
+                dwell_micros: minutes(45),
+                price: 25,
+            }),
+            Kind::Toilets => Some(Service {
+                hunger: 0.0,
+                rest: 0.2,
+                dwell_micros: minutes(5),
+                price: 1,
+            }),
+            Kind::TicketOffice => Some(Service {
+                hunger: 0.0,
+                rest: 0.0,
+                dwell_micros: minutes(10),
+                price: 10,
+            }),
+            Kind::RentalShop => Some(Service {
+                hunger: 0.0,
+                rest: 0.0,
+                dwell_micros: minutes(20),
+                price: 30,
+            }),
+        }
+    }
+
+    /// Whether day visitors arrive at and leave from buildings of this kind
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Kind::CarPark | Kind::BusStop)
+    }
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Window {
     pub position: XYZ<f32>,
@@ -1,9 +1,17 @@
+use std::f32::consts::PI;
+
+use commons::geometry::{xy, XY};
 use serde::{Deserialize, Serialize};
 
+pub const SEAT_WIDTH_METERS: f32 = 0.75;
+pub const GONDOLA_ROW_OFFSET_METERS: f32 = 0.7;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Carousel {
     pub lift_id: usize,
     pub velocity: f32,
+    pub car_interval_meters: f32,
+    pub car_class: CarClass,
     pub car_ids: Vec<usize>,
 }
 
@@ -13,3 +21,113 @@ pub struct Car {
     pub segment: usize,
     pub distance_from_start_meters: f32,
 }
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum CarClass {
+    TBar,
+    Belt,
+    Single,
+    Double,
+    Quad,
+    SixPack,
+    Gondola,
+}
+
+pub const CAR_CLASSES: [CarClass; 7] = [
+    CarClass::TBar,
+    CarClass::Belt,
+    CarClass::Single,
+    CarClass::Double,
+    CarClass::Quad,
+    CarClass::SixPack,
+    CarClass::Gondola,
+];
+
+pub struct Seat {
+    pub offset: XY<f32>,
+    pub yaw: f32,
+}
+
+impl CarClass {
+    pub fn capacity(&self) -> usize {
+        match self {
+            CarClass::TBar => 2,
+            CarClass::Belt => 1,
+            CarClass::Single => 1,
+            CarClass::Double => 2,
+            CarClass::Quad => 4,
+            CarClass::SixPack => 6,
+            CarClass::Gondola => 8,
+        }
+    }
+
+    /// Seat positions relative to the car, where the car faces along the x-axis
+    pub fn seats(&self) -> Vec<Seat> {
+        match self {
+            CarClass::Gondola => {
+                // two rows facing each other
+                let row = self.capacity() / 2;
+                let forward = bench(row).map(|y| Seat {
+                    offset: xy(-GONDOLA_ROW_OFFSET_METERS, y),
+                    yaw: 0.0,
+                });
+                let backward = bench(row).map(|y| Seat {
+                    offset: xy(GONDOLA_ROW_OFFSET_METERS, y),
+                    yaw: PI,
+                });
+                forward.chain(backward).collect()
+            }
+            _ => bench(self.capacity())
+                .map(|y| Seat {
+                    offset: xy(0.0, y),
+                    yaw: 0.0,
+                })
+                .collect(),
+        }
+    }
+}
+
+fn bench(seats: usize) -> impl Iterator<Item = f32> {
+    let first = -((seats - 1) as f32) / 2.0;
+    (0..seats).map(move |seat| (first + seat as f32) * SEAT_WIDTH_METERS)
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::almost_eq::assert_almost_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_seat_count_matches_capacity() {
+        for class in CAR_CLASSES {
+            assert_eq!(class.seats().len(), class.capacity());
+        }
+    }
+
+    #[test]
+    fn test_chair_seats_are_centered() {
+        // when
+        let seats = CarClass::Double.seats();
+
+        // then
+        assert_almost_eq(seats[0].offset.x, 0.0);
+        assert_almost_eq(seats[0].offset.y, -SEAT_WIDTH_METERS / 2.0);
+        assert_almost_eq(seats[1].offset.x, 0.0);
+        assert_almost_eq(seats[1].offset.y, SEAT_WIDTH_METERS / 2.0);
+    }
+
+    #[test]
+    fn test_gondola_rows_face_each_other() {
+        // when
+        let seats = CarClass::Gondola.seats();
+
+        // then
+        assert!(seats[..4]
+            .iter()
+            .all(|seat| seat.offset.x < 0.0 && seat.yaw == 0.0));
+        assert!(seats[4..]
+            .iter()
+            .all(|seat| seat.offset.x > 0.0 && seat.yaw == PI));
+    }
+}
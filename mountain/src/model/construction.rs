@@ -0,0 +1,185 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+/// Works that progress at the same time, later works wait for a crew to finish
+pub const CREWS: usize = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Kind {
+    Piste,
+    Path,
+    Lift,
+    Gate,
+    Building,
+    Door,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Construction {
+    pub id: usize,
+    pub kind: Kind,
+    pub work_micros: u128,
+    pub done_micros: u128,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct Queue {
+    constructions: VecDeque<Construction>,
+}
+
+impl Kind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Kind::Piste => "Piste",
+            Kind::Path => "Path",
+            Kind::Lift => "Lift",
+            Kind::Gate => "Gate",
+            Kind::Building => "Building",
+            Kind::Door => "Door",
+        }
+    }
+}
+
+impl Construction {
+    pub fn progress(&self) -> f32 {
+        if self.work_micros == 0 {
+            return 1.0;
+        }
+        self.done_micros as f32 / self.work_micros as f32
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.done_micros >= self.work_micros
+    }
+}
+
+impl Queue {
+    /// Adds works for `id` to the back of the queue, or more work if `id` is already queued
+    pub fn enqueue(&mut self, id: usize, kind: Kind, work_micros: u128) {
+        if let Some(construction) = self.constructions.iter_mut().find(|c| c.id == id) {
+            construction.work_micros += work_micros;
+            return;
+        }
+        self.constructions.push_back(Construction {
+            id,
+            kind,
+            work_micros,
+            done_micros: 0,
+        });
+    }
+
+    pub fn contains(&self, id: &usize) -> bool {
+        self.constructions.iter().any(|c| c.id == *id)
+    }
+
+    pub fn get(&self, id: &usize) -> Option<&Construction> {
+        self.constructions.iter().find(|c| c.id == *id)
+    }
+
+    pub fn remove(&mut self, id: &usize) {
+        self.constructions.retain(|c| c.id != *id);
+    }
+
+    pub fn len(&self) -> usize {
+        self.constructions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.constructions.is_empty()
+    }
+
+    /// Works in order, the first `CREWS` are in progress
+    pub fn iter(&self) -> impl Iterator<Item = &Construction> {
+        self.constructions.iter()
+    }
+
+    /// Progresses the works at the front of the queue by `micros`, returning the completed works
+    pub fn work(&mut self, micros: u128) -> Vec<Construction> {
+        for construction in self.constructions.iter_mut().take(CREWS) {
+            construction.done_micros =
+                (construction.done_micros + micros).min(construction.work_micros);
+        }
+
+        let mut completed = vec![];
+        self.constructions.retain(|construction| {
+            if construction.is_complete() {
+                completed.push(construction.clone());
+                false
+            } else {
+                true
+            }
+        });
+        completed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_front_of_queue_progresses() {
+        // given
+        let mut queue = Queue::default();
+        queue.enqueue(1, Kind::Lift, 100);
+        queue.enqueue(2, Kind::Piste, 100);
+        queue.enqueue(3, Kind::Gate, 100);
+
+        // when
+        let completed = queue.work(40);
+
+        // then
+        assert!(completed.is_empty());
+        assert_eq!(queue.get(&1).unwrap().progress(), 0.4);
+        assert_eq!(queue.get(&2).unwrap().progress(), 0.4);
+        assert_eq!(queue.get(&3).unwrap().progress(), 0.0);
+    }
+
+    #[test]
+    fn test_completed_works_leave_queue() {
+        // given
+        let mut queue = Queue::default();
+        queue.enqueue(1, Kind::Lift, 100);
+        queue.enqueue(2, Kind::Piste, 50);
+        queue.enqueue(3, Kind::Gate, 100);
+
+        // when
+        let completed = queue.work(60);
+
+        // then
+        assert_eq!(
+            completed,
+            vec![Construction {
+                id: 2,
+                kind: Kind::Piste,
+                work_micros: 50,
+                done_micros: 50
+            }]
+        );
+        assert!(!queue.contains(&2));
+        assert_eq!(queue.get(&3).unwrap().done_micros, 0);
+
+        // when
+        queue.work(60);
+
+        // then
+        assert!(!queue.contains(&1));
+        assert_eq!(queue.get(&3).unwrap().done_micros, 60);
+    }
+
+    #[test]
+    fn test_enqueue_adds_work_to_queued_id() {
+        // given
+        let mut queue = Queue::default();
+        queue.enqueue(1, Kind::Piste, 100);
+        queue.work(50);
+
+        // when
+        queue.enqueue(1, Kind::Piste, 100);
+
+        // then
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.get(&1).unwrap().progress(), 0.25);
+    }
+}
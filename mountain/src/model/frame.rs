@@ -1,7 +1,8 @@
 use commons::color::Rgb;
 use commons::geometry::XYZ;
 
-use crate::model::skier::Clothes;
+use crate::model::carousel::CarClass;
+use crate::model::skier::{Clothes, Equipment};
 
 #[derive(Clone, Copy)]
 pub struct Frame {
@@ -17,8 +18,28 @@ pub enum Model {
         skis: bool,
         clothes: Clothes<Rgb<f32>>,
     },
+    Snowboarding {
+        clothes: Clothes<Rgb<f32>>,
+    },
     Sitting {
         clothes: Clothes<Rgb<f32>>,
     },
-    Chair,
+    Lying {
+        clothes: Clothes<Rgb<f32>>,
+    },
+    Car(CarClass),
+    Groomer,
+}
+
+impl Model {
+    /// A skier or snowboarder on their feet
+    pub fn on_snow(equipment: &Equipment, clothes: Clothes<Rgb<f32>>) -> Model {
+        match equipment {
+            Equipment::Skis => Model::Standing {
+                skis: true,
+                clothes,
+            },
+            Equipment::Snowboard => Model::Snowboarding { clothes },
+        }
+    }
 }
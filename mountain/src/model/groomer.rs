@@ -0,0 +1,287 @@
+use std::collections::{HashMap, VecDeque};
+
+use commons::geometry::{xy, XY};
+use commons::grid::OFFSETS_8;
+use commons::origin_grid::OriginGrid;
+use serde::{Deserialize, Serialize};
+
+pub const VELOCITY: f32 = 3.0;
+/// Cells either side of a stop that the groomer's tiller covers
+pub const SWATH_RADIUS: u32 = 2;
+
+#[derive(Serialize, Deserialize)]
+pub struct Groomer {
+    pub piste_id: usize,
+    pub route: Vec<XY<u32>>,
+    pub next_stop: usize,
+    pub position: XY<f32>,
+    pub yaw: f32,
+}
+
+impl Groomer {
+    pub fn new(piste_id: usize, route: Vec<XY<u32>>) -> Groomer {
+        let position = route
+            .first()
+            .map(|stop| xy(stop.x as f32, stop.y as f32))
+            .unwrap_or(xy(0.0, 0.0));
+        Groomer {
+            piste_id,
+            route,
+            next_stop: 0,
+            position,
+            yaw: 0.0,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.next_stop >= self.route.len()
+    }
+
+    pub fn progress(&self) -> f32 {
+        if self.route.is_empty() {
+            return 1.0;
+        }
+        self.next_stop as f32 / self.route.len() as f32
+    }
+
+    /// Drives up to `meters` along the route, waiting short of any stop it may not enter,
+    /// and returns the stops reached
+    pub fn drive(&mut self, mut meters: f32, can_enter: impl Fn(&XY<u32>) -> bool) -> Vec<XY<u32>> {
+        let mut reached = vec![];
+
+        while let Some(stop) = self.route.get(self.next_stop) {
+            if !can_enter(stop) {
+                break;
+            }
+
+            let offset = xy(
+                stop.x as f32 - self.position.x,
+                stop.y as f32 - self.position.y,
+            );
+            let distance = offset.magnitude();
+            if distance > 0.0 {
+                self.yaw = offset.y.atan2(offset.x);
+            }
+
+            if distance > meters {
+                let p = meters / distance;
+                self.position = xy(
+                    self.position.x + offset.x * p,
+                    self.position.y + offset.y * p,
+                );
+                break;
+            }
+
+            meters -= distance;
+            self.position = xy(stop.x as f32, stop.y as f32);
+            reached.push(*stop);
+            self.next_stop += 1;
+        }
+
+        reached
+    }
+}
+
+/// Back and forth passes across the piste, one per swath of rows, stopping only on piste cells
+/// and each stop next to the last so the groomer never leaves the piste
+pub fn coverage_route(grid: &OriginGrid<bool>) -> Vec<XY<u32>> {
+    let mut route: Vec<XY<u32>> = vec![];
+    for stop in coverage_stops(grid) {
+        let Some(last) = route.last() else {
+            route.push(stop);
+            continue;
+        };
+        // parts of the piste that cannot be reached across it are left ungroomed
+        if let Some(path) = path_across(grid, last, &stop) {
+            route.extend(path);
+        }
+    }
+    route
+}
+
+/// Piste cells to stop on so every cell of the piste is covered by a swath, in pass order
+fn coverage_stops(grid: &OriginGrid<bool>) -> Vec<XY<u32>> {
+    let swath_width = SWATH_RADIUS * 2 + 1;
+    let origin = grid.origin();
+    let is_piste = |x: u32, y: u32| grid[xy(origin.x + x, origin.y + y)];
+
+    (0..grid.height())
+        .step_by(swath_width as usize)
+        .enumerate()
+        .flat_map(|(pass, from_y)| {
+            let to_y = (from_y + swath_width).min(grid.height());
+            let mut stops = vec![];
+            for x in 0..grid.width() {
+                // stopping as far along the column as possible while covering the first cell left
+                let mut y = from_y;
+                while y < to_y {
+                    if !is_piste(x, y) {
+                        y += 1;
+                        continue;
+                    }
+                    let stop_y = (y..=(y + SWATH_RADIUS).min(to_y - 1))
+                        .rev()
+                        .find(|&stop_y| is_piste(x, stop_y))
+                        .unwrap_or(y);
+                    stops.push(xy(origin.x + x, origin.y + stop_y));
+                    y = stop_y + SWATH_RADIUS + 1;
+                }
+            }
+            if pass % 2 == 1 {
+                stops.reverse();
+            }
+            stops
+        })
+        .collect()
+}
+
+/// Shortest path over piste cells from one cell to another, excluding the first cell
+fn path_across(grid: &OriginGrid<bool>, from: &XY<u32>, to: &XY<u32>) -> Option<Vec<XY<u32>>> {
+    let mut previous = HashMap::from([(*from, *from)]);
+    let mut queue = VecDeque::from([*from]);
+    while let Some(cell) = queue.pop_front() {
+        if cell == *to {
+            let mut path = vec![cell];
+            let mut cell = cell;
+            while let Some(&before) = previous.get(&cell).filter(|before| *before != from) {
+                path.push(before);
+                cell = before;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for neighbour in grid.offsets(cell, &OFFSETS_8) {
+            if grid[neighbour] && !previous.contains_key(&neighbour) {
+                previous.insert(neighbour, cell);
+                queue.push_back(neighbour);
+            }
+        }
+    }
+    None
+}
+
+/// Piste cells groomed when the groomer passes over the stop
+pub fn swath<'a>(grid: &'a OriginGrid<bool>, stop: &XY<u32>) -> impl Iterator<Item = XY<u32>> + 'a {
+    let x = stop.x;
+    (stop.y.saturating_sub(SWATH_RADIUS)..=stop.y + SWATH_RADIUS)
+        .map(move |y| xy(x, y))
+        .filter(|cell| grid.in_bounds(cell) && grid[cell])
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::almost_eq::assert_almost_eq;
+    use commons::grid::Grid;
+
+    use super::*;
+
+    fn is_adjacent(a: &XY<u32>, b: &XY<u32>) -> bool {
+        a.x.abs_diff(b.x) <= 1 && a.y.abs_diff(b.y) <= 1
+    }
+
+    #[test]
+    fn test_coverage_route() {
+        // given
+        let mut grid = OriginGrid::new(xy(10, 20), Grid::from_element(3, 7, false));
+        for cell in [
+            xy(10, 20),
+            xy(10, 21),
+            xy(11, 22),
+            xy(12, 23),
+            xy(12, 24),
+            xy(11, 25),
+            xy(11, 26),
+        ] {
+            grid[cell] = true;
+        }
+
+        // when
+        let route = coverage_route(&grid);
+
+        // then
+        assert_eq!(
+            route,
+            vec![
+                xy(10, 21),
+                xy(11, 22),
+                xy(12, 23),
+                xy(12, 24),
+                xy(11, 25),
+                xy(11, 26)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coverage_route_stays_on_piste() {
+        // given
+        let mut grid = OriginGrid::new(xy(0, 0), Grid::from_element(9, 12, true));
+        // a hole the route must go around, and a part of the piste that cannot be reached
+        for x in 0..7 {
+            grid[xy(x, 6)] = false;
+        }
+        grid[xy(7, 11)] = false;
+        grid[xy(8, 10)] = false;
+        grid[xy(7, 10)] = false;
+        grid[xy(8, 11)] = true;
+
+        // when
+        let route = coverage_route(&grid);
+
+        // then
+        assert!(route.iter().all(|stop| grid[stop]));
+        assert!(route.windows(2).all(|leg| is_adjacent(&leg[0], &leg[1])));
+        assert!(!route.contains(&xy(8, 11)));
+        assert!(grid
+            .iter()
+            .filter(|cell| grid[cell] && *cell != xy(8, 11))
+            .all(|cell| route
+                .iter()
+                .any(|stop| swath(&grid, stop).any(|c| c == cell))));
+    }
+
+    #[test]
+    fn test_coverage_route_covers_piste() {
+        // given
+        let grid = OriginGrid::new(xy(5, 5), Grid::from_element(4, 12, true));
+
+        // when
+        let route = coverage_route(&grid);
+
+        // then
+        assert!(grid.iter().all(|cell| route
+            .iter()
+            .any(|stop| swath(&grid, stop).any(|c| c == cell))));
+        assert!(route.windows(2).all(|leg| is_adjacent(&leg[0], &leg[1])));
+    }
+
+    #[test]
+    fn test_drive() {
+        // given
+        let mut groomer = Groomer::new(0, vec![xy(0, 0), xy(2, 0), xy(2, 4)]);
+
+        // when
+        let reached = groomer.drive(4.0, |_| true);
+
+        // then
+        assert_eq!(reached, vec![xy(0, 0), xy(2, 0)]);
+        assert_almost_eq(groomer.position.x, 2.0);
+        assert_almost_eq(groomer.position.y, 2.0);
+        assert_almost_eq(groomer.yaw, std::f32::consts::FRAC_PI_2);
+        assert!(!groomer.is_finished());
+    }
+
+    #[test]
+    fn test_drive_waits_for_stop_it_cannot_enter() {
+        // given
+        let mut groomer = Groomer::new(0, vec![xy(0, 0), xy(1, 0), xy(2, 0)]);
+
+        // when
+        let reached = groomer.drive(10.0, |stop| stop.x < 2);
+
+        // then
+        assert_eq!(reached, vec![xy(0, 0), xy(1, 0)]);
+        assert_almost_eq(groomer.position.x, 1.0);
+        assert_eq!(groomer.next_stop, 2);
+    }
+}
@@ -0,0 +1,63 @@
+use std::ops::RangeInclusive;
+
+use crate::model::satisfaction::Rating;
+use crate::model::time_of_day::MICROS_PER_HOUR;
+use crate::services::economy::MICROS_PER_DAY;
+
+/// Nights guests book, chosen when they check in
+pub const STAY_DAYS: RangeInclusive<u128> = 2..=7;
+/// Time of day when guests leaving that day check out
+pub const CHECK_OUT_MICROS: u128 = 10 * MICROS_PER_HOUR;
+/// Time of day when new guests check in to empty rooms
+pub const CHECK_IN_MICROS: u128 = 15 * MICROS_PER_HOUR;
+
+/// Whether a guest due to leave on `check_out_day` should be leaving at `micros`
+pub fn is_checking_out(check_out_day: &u128, micros: &u128) -> bool {
+    *micros >= check_out_day * MICROS_PER_DAY + CHECK_OUT_MICROS
+}
+
+/// Guests checking in to a hotel, filling its rooms up to what the rating and demand attract
+///
+/// `demand` runs from 0 to 1
+pub fn check_ins(rooms: usize, guests: usize, rating: &Rating, demand: f32) -> usize {
+    let booked = (rooms as f32 * rating.occupancy() * demand.clamp(0.0, 1.0)).round() as usize;
+    booked.saturating_sub(guests)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::satisfaction::MAX_STARS;
+
+    use super::*;
+
+    #[test]
+    fn test_is_checking_out() {
+        assert!(!is_checking_out(&2, &(2 * MICROS_PER_DAY)));
+        assert!(is_checking_out(
+            &2,
+            &(2 * MICROS_PER_DAY + CHECK_OUT_MICROS)
+        ));
+        assert!(is_checking_out(&2, &(3 * MICROS_PER_DAY)));
+    }
+
+    #[test]
+    fn test_check_ins_fill_empty_rooms() {
+        // given
+        let rating = Rating { stars: MAX_STARS };
+
+        // then
+        assert_eq!(check_ins(40, 30, &rating, 1.0), 10);
+        assert_eq!(check_ins(40, 30, &rating, 0.5), 0);
+        assert_eq!(check_ins(40, 50, &rating, 1.0), 0);
+    }
+
+    #[test]
+    fn test_poor_rating_fills_fewer_rooms() {
+        // given
+        let poor = Rating { stars: 0.0 };
+        let good = Rating { stars: MAX_STARS };
+
+        // then
+        assert!(check_ins(40, 0, &poor, 1.0) < check_ins(40, 0, &good, 1.0));
+    }
+}
@@ -0,0 +1,153 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::ability::Ability;
+use crate::model::skiing::State;
+use crate::services::economy::MICROS_PER_DAY;
+
+/// Chance of falling in each minute of skiing a run within the skier's ability at an easy pace
+const BASE_FALL_CHANCE_PER_MINUTE: f32 = 0.0001;
+/// Falls are this many times more likely for each ability level the run is above the skier
+const TOO_HARD_FACTOR: f32 = 20.0;
+/// Skiers going at least this fast, in meters per second, are more likely to fall
+pub const FAST_VELOCITY: f32 = 8.0;
+/// Falls are this many times more likely for skiers going fast
+const FAST_FACTOR: f32 = 5.0;
+/// Injured skiers left waiting longer than this for the ski patrol are unhappy about it
+pub const SLOW_RESCUE_MICROS: u128 = 15 * 60 * 1_000_000;
+/// How long injured skiers spend being treated at a first-aid building
+pub const TREATMENT_MICROS: u128 = 60 * 60 * 1_000_000;
+
+/// A skier lying injured on the mountain, waiting for the ski patrol
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Incident {
+    /// When the skier fell
+    pub micros: u128,
+    /// Where the skier is lying
+    pub state: State,
+    /// The patroller sent to help, once one has been sent
+    pub patroller_id: Option<usize>,
+}
+
+/// Incidents on the mountain, which put guests off the resort
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Statistics {
+    /// When each incident in the last day happened, oldest first
+    pub recent: VecDeque<u128>,
+    pub total: usize,
+    pub rescued: usize,
+    /// Time injured skiers spent waiting for a patroller, across all rescues
+    pub total_wait_micros: u128,
+}
+
+/// Chance of a skier falling over `minutes` of skiing a run of the given ability at `velocity`
+pub fn fall_chance(skier: &Ability, run: &Ability, velocity: f32, minutes: f32) -> f32 {
+    let levels_too_hard = (*run as i32 - *skier as i32).max(0);
+    let mut chance = BASE_FALL_CHANCE_PER_MINUTE * TOO_HARD_FACTOR.powi(levels_too_hard);
+    if velocity >= FAST_VELOCITY {
+        chance *= FAST_FACTOR;
+    }
+    (chance * minutes).min(1.0)
+}
+
+impl Statistics {
+    pub fn record_incident(&mut self, micros: &u128) {
+        self.recent.push_back(*micros);
+        self.total += 1;
+    }
+
+    pub fn record_rescue(&mut self, wait_micros: &u128) {
+        self.rescued += 1;
+        self.total_wait_micros += wait_micros;
+    }
+
+    /// Forgets incidents more than a day before `micros`
+    pub fn forget(&mut self, micros: &u128) {
+        while self
+            .recent
+            .front()
+            .is_some_and(|incident_micros| micros.saturating_sub(*incident_micros) > MICROS_PER_DAY)
+        {
+            self.recent.pop_front();
+        }
+    }
+
+    /// Incidents in the last day for each guest
+    pub fn daily_rate(&self, guests: usize) -> f32 {
+        if guests == 0 {
+            return 0.0;
+        }
+        self.recent.len() as f32 / guests as f32
+    }
+
+    pub fn average_wait_micros(&self) -> Option<u128> {
+        (self.rescued > 0).then(|| self.total_wait_micros / self.rescued as u128)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::almost_eq::assert_almost_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_runs_above_ability_and_speed_make_falls_more_likely() {
+        // given
+        let easy = fall_chance(&Ability::Advanced, &Ability::Intermediate, 2.0, 1.0);
+        let too_hard = fall_chance(&Ability::Intermediate, &Ability::Advanced, 2.0, 1.0);
+        let far_too_hard = fall_chance(&Ability::Beginner, &Ability::Advanced, 2.0, 1.0);
+        let fast = fall_chance(
+            &Ability::Advanced,
+            &Ability::Intermediate,
+            FAST_VELOCITY,
+            1.0,
+        );
+
+        // then
+        assert_almost_eq(easy, BASE_FALL_CHANCE_PER_MINUTE);
+        assert_almost_eq(too_hard, BASE_FALL_CHANCE_PER_MINUTE * TOO_HARD_FACTOR);
+        assert!(far_too_hard > too_hard);
+        assert_almost_eq(fast, BASE_FALL_CHANCE_PER_MINUTE * FAST_FACTOR);
+    }
+
+    #[test]
+    fn test_fall_chance_is_at_most_one() {
+        assert_eq!(
+            fall_chance(&Ability::Beginner, &Ability::Expert, FAST_VELOCITY, 1000.0),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_statistics_forget_incidents_after_a_day() {
+        // given
+        let mut statistics = Statistics::default();
+        statistics.record_incident(&0);
+        statistics.record_incident(&MICROS_PER_DAY);
+
+        // when
+        statistics.forget(&(MICROS_PER_DAY + 1));
+
+        // then
+        assert_eq!(statistics.recent, VecDeque::from([MICROS_PER_DAY]));
+        assert_eq!(statistics.total, 2);
+        assert_almost_eq(statistics.daily_rate(4), 0.25);
+        assert_eq!(statistics.daily_rate(0), 0.0);
+    }
+
+    #[test]
+    fn test_average_wait() {
+        // given
+        let mut statistics = Statistics::default();
+        assert_eq!(statistics.average_wait_micros(), None);
+
+        // when
+        statistics.record_rescue(&10);
+        statistics.record_rescue(&20);
+
+        // then
+        assert_eq!(statistics.average_wait_micros(), Some(15));
+    }
+}
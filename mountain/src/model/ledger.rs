@@ -0,0 +1,170 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+/// Days of history kept after they close
+pub const MAX_HISTORY_DAYS: usize = 60;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum Account {
+    Tickets,
+    Rooms,
+    Services,
+    Lifts,
+    Pistes,
+    Construction,
+}
+
+pub const ACCOUNTS: [Account; 6] = [
+    Account::Tickets,
+    Account::Rooms,
+    Account::Services,
+    Account::Lifts,
+    Account::Pistes,
+    Account::Construction,
+];
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Day {
+    pub day: u128,
+    pub totals: HashMap<Account, i64>,
+    pub closing_balance: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Ledger {
+    balance: i64,
+    today: Day,
+    history: VecDeque<Day>,
+}
+
+impl Account {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Account::Tickets => "Lift tickets",
+            Account::Rooms => "Hotel rooms",
+            Account::Services => "Services",
+            Account::Lifts => "Lift operation",
+            Account::Pistes => "Piste upkeep",
+            Account::Construction => "Construction",
+        }
+    }
+}
+
+impl Day {
+    fn new(day: u128, balance: i64) -> Day {
+        Day {
+            day,
+            totals: HashMap::default(),
+            closing_balance: balance,
+        }
+    }
+
+    pub fn total(&self, account: &Account) -> i64 {
+        self.totals.get(account).copied().unwrap_or_default()
+    }
+
+    pub fn net(&self) -> i64 {
+        self.totals.values().sum()
+    }
+}
+
+impl Ledger {
+    pub fn new(balance: i64) -> Ledger {
+        Ledger {
+            balance,
+            today: Day::new(0, balance),
+            history: VecDeque::with_capacity(MAX_HISTORY_DAYS),
+        }
+    }
+
+    pub fn balance(&self) -> i64 {
+        self.balance
+    }
+
+    /// Totals so far for the day that has not closed yet
+    pub fn today(&self) -> &Day {
+        &self.today
+    }
+
+    /// Closed days, oldest first
+    pub fn history(&self) -> impl DoubleEndedIterator<Item = &Day> {
+        self.history.iter()
+    }
+
+    pub fn record(&mut self, account: Account, amount: i64) {
+        if amount == 0 {
+            return;
+        }
+        self.balance += amount;
+        self.today.closing_balance = self.balance;
+        *self.today.totals.entry(account).or_default() += amount;
+    }
+
+    pub fn close_day(&mut self, next_day: u128) {
+        let closed = std::mem::replace(&mut self.today, Day::new(next_day, self.balance));
+        if self.history.len() == MAX_HISTORY_DAYS {
+            self.history.pop_front();
+        }
+        self.history.push_back(closed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        // given
+        let mut ledger = Ledger::new(1000);
+
+        // when
+        ledger.record(Account::Tickets, 50);
+        ledger.record(Account::Tickets, 25);
+        ledger.record(Account::Lifts, -100);
+
+        // then
+        assert_eq!(ledger.balance(), 975);
+        assert_eq!(ledger.today().total(&Account::Tickets), 75);
+        assert_eq!(ledger.today().total(&Account::Lifts), -100);
+        assert_eq!(ledger.today().total(&Account::Rooms), 0);
+        assert_eq!(ledger.today().net(), -25);
+        assert_eq!(ledger.today().closing_balance, 975);
+    }
+
+    #[test]
+    fn test_close_day() {
+        // given
+        let mut ledger = Ledger::new(1000);
+        ledger.record(Account::Rooms, 200);
+
+        // when
+        ledger.close_day(1);
+
+        // then
+        let closed = ledger.history().collect::<Vec<_>>();
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].day, 0);
+        assert_eq!(closed[0].total(&Account::Rooms), 200);
+        assert_eq!(closed[0].closing_balance, 1200);
+        assert_eq!(ledger.today().day, 1);
+        assert_eq!(ledger.today().net(), 0);
+        assert_eq!(ledger.balance(), 1200);
+    }
+
+    #[test]
+    fn test_history_is_capped() {
+        // given
+        let mut ledger = Ledger::new(0);
+
+        // when
+        for day in 1..=(MAX_HISTORY_DAYS as u128 + 5) {
+            ledger.close_day(day);
+        }
+
+        // then
+        assert_eq!(ledger.history().count(), MAX_HISTORY_DAYS);
+        assert_eq!(ledger.history().next().unwrap().day, 5);
+    }
+}
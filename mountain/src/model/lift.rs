@@ -1,18 +1,29 @@
+use std::iter::once;
+
 use commons::geometry::XYZ;
 use nalgebra::Point3;
 use serde::{Deserialize, Serialize};
 
+use crate::model::lift_type::LiftType;
 use crate::model::skiing::State;
 
 #[derive(Serialize, Deserialize)]
 pub struct Lift {
+    pub lift_type: LiftType,
     pub segments: Vec<Segment>,
     pub pick_up: Portal,
     pub drop_off: Portal,
+    pub mid_stations: Vec<MidStation>,
     pub carousel_id: usize,
     pub buildings_id: usize,
 }
 
+#[derive(Serialize, Deserialize)]
+pub struct MidStation {
+    pub drop_off: Portal,
+    pub pick_up: Portal,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct Segment {
     pub from: XYZ<f32>,
@@ -28,15 +39,42 @@ pub struct Portal {
 }
 
 impl Lift {
-    pub fn ride_length_meters(&self) -> f32 {
-        let mut segment = self.pick_up.segment;
+    pub fn pick_ups(&self) -> impl Iterator<Item = &Portal> {
+        once(&self.pick_up).chain(self.mid_stations.iter().map(|station| &station.pick_up))
+    }
+
+    pub fn drop_offs(&self) -> impl Iterator<Item = &Portal> {
+        self.mid_stations
+            .iter()
+            .map(|station| &station.drop_off)
+            .chain(once(&self.drop_off))
+    }
+
+    pub fn portals(&self) -> impl Iterator<Item = &Portal> {
+        self.pick_ups().chain(self.drop_offs())
+    }
+
+    /// Drop offs reachable from the pick up - every skier gets off at the lift's final drop off
+    pub fn drop_offs_after<'a>(&'a self, pick_up: &'a Portal) -> impl Iterator<Item = &'a Portal> {
+        let last = self.segments_between(pick_up.segment, self.drop_off.segment);
+        self.drop_offs().filter(move |drop_off| {
+            self.segments_between(pick_up.segment, drop_off.segment) <= last
+        })
+    }
+
+    pub fn ride_length_meters_between(&self, pick_up: &Portal, drop_off: &Portal) -> f32 {
+        let mut segment = pick_up.segment;
         let mut out = 0.0;
-        while segment != self.drop_off.segment {
+        while segment != drop_off.segment {
             out += self.segments[segment].length_meters;
             segment = (segment + 1) % self.segments.len();
         }
         out
     }
+
+    fn segments_between(&self, from: usize, to: usize) -> usize {
+        (to + self.segments.len() - from) % self.segments.len()
+    }
 }
 
 impl Segment {
@@ -76,6 +114,7 @@ mod tests {
     fn test_ride_length_meters_pick_up_before_drop_off() {
         // given
         let lift = Lift {
+            lift_type: LiftType::Chairlift,
             segments: segments(&[
                 xyz(0.0, 0.0, 0.0),
                 xyz(2.0, 0.0, 0.0),
@@ -101,12 +140,13 @@ mod tests {
                     velocity: 0,
                 },
             },
+            mid_stations: vec![],
             carousel_id: 0,
             buildings_id: 0,
         };
 
         // when
-        let result = lift.ride_length_meters();
+        let result = lift.ride_length_meters_between(&lift.pick_up, &lift.drop_off);
 
         // then
         assert_eq!(result, 3.0);
@@ -116,6 +156,7 @@ mod tests {
     fn test_ride_length_meters_pick_up_after_drop_off() {
         // given
         let lift = Lift {
+            lift_type: LiftType::Chairlift,
             segments: segments(&[
                 xyz(2.0, 1.0, 0.0),
                 xyz(0.0, 1.0, 0.0),
@@ -141,14 +182,60 @@ mod tests {
                     velocity: 0,
                 },
             },
+            mid_stations: vec![],
             carousel_id: 0,
             buildings_id: 0,
         };
 
         // when
-        let result = lift.ride_length_meters();
+        let result = lift.ride_length_meters_between(&lift.pick_up, &lift.drop_off);
 
         // then
         assert_eq!(result, 3.0);
     }
+
+    #[test]
+    fn test_drop_offs_after() {
+        // given
+        let state = State {
+            position: xy(0, 0),
+            travel_direction: Direction::North,
+            velocity: 0,
+        };
+        let portal = |id, segment| Portal { id, segment, state };
+        let lift = Lift {
+            lift_type: LiftType::Chairlift,
+            segments: segments(&[
+                xyz(0.0, 0.0, 0.0),
+                xyz(1.0, 0.0, 0.0),
+                xyz(2.0, 0.0, 0.0),
+                xyz(3.0, 0.0, 0.0),
+                xyz(2.0, 0.0, 0.0),
+                xyz(1.0, 0.0, 0.0),
+                xyz(0.0, 0.0, 0.0),
+            ]),
+            pick_up: portal(0, 0),
+            drop_off: portal(1, 3),
+            mid_stations: vec![MidStation {
+                drop_off: portal(2, 1),
+                pick_up: portal(3, 2),
+            }],
+            carousel_id: 0,
+            buildings_id: 0,
+        };
+
+        // when
+        let from_bottom = lift
+            .drop_offs_after(&lift.pick_up)
+            .map(|drop_off| drop_off.id)
+            .collect::<Vec<_>>();
+        let from_mid_station = lift
+            .drop_offs_after(&lift.mid_stations[0].pick_up)
+            .map(|drop_off| drop_off.id)
+            .collect::<Vec<_>>();
+
+        // then
+        assert_eq!(from_bottom, vec![2, 1]);
+        assert_eq!(from_mid_station, vec![1]);
+    }
 }
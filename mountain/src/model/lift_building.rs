@@ -1,3 +1,5 @@
+use std::f32::consts::PI;
+
 use commons::geometry::{xy, xyz, XY, XYZ};
 use commons::grid::Grid;
 use engine::graphics::transform::Transform;
@@ -5,35 +7,78 @@ use engine::graphics::utils::{transformation_matrix, Transformation};
 use nalgebra::Matrix4;
 use serde::{Deserialize, Serialize};
 
+use crate::model::direction::Direction;
+use crate::model::lift_type::LiftType;
+
+/// Number of segments the cable hanging between two wire path segments is split into
+pub const CABLE_PIECES: usize = 8;
+
 #[derive(Serialize, Deserialize)]
 pub struct LiftBuildings {
+    pub lift_type: LiftType,
     pub buildings: Vec<LiftBuilding>,
+    pub cable_tension: f32,
 }
 
 pub struct GlobalTransfer {
     pub position: XY<u32>,
     pub global_segment: usize,
+    pub travel_direction: Direction,
+}
+
+pub struct GlobalMidStation {
+    pub drop_off: GlobalTransfer,
+    pub pick_up: GlobalTransfer,
 }
 
 impl LiftBuildings {
-    pub fn wire_path(&self, terrain: &Grid<f32>) -> Vec<[XYZ<f32>; 2]> {
+    /// Points each building without a manual yaw along the line between its neighbours
+    pub fn align_yaws(&mut self) {
+        let positions = self
+            .buildings
+            .iter()
+            .map(|building| building.position)
+            .collect::<Vec<_>>();
+        let last = positions.len().saturating_sub(1);
+
+        for (index, building) in self.buildings.iter_mut().enumerate() {
+            if building.manual_yaw {
+                continue;
+            }
+            let from = positions[index.saturating_sub(1)];
+            let to = positions[(index + 1).min(last)];
+            let vector = xy(to.x as f32 - from.x as f32, to.y as f32 - from.y as f32);
+            if vector.magnitude() == 0.0 {
+                continue;
+            }
+            building.yaw = vector.angle();
+        }
+    }
+
+    /// Wire path grouped into the segments through each building and the cables hanging between them
+    pub fn cables(&self, terrain: &Grid<f32>) -> Vec<Vec<[XYZ<f32>; 2]>> {
         let unlinked = self
             .buildings
             .iter()
-            .flat_map(|building| building.wire_path_out(terrain))
+            .flat_map(|building| building.wire_path_out(&self.lift_type, terrain))
             .chain(
                 self.buildings
                     .iter()
                     .rev()
-                    .flat_map(|building| building.wire_path_back(terrain)),
+                    .flat_map(|building| building.wire_path_back(&self.lift_type, terrain)),
             )
             .collect::<Vec<_>>();
 
         let mut linked = Vec::with_capacity(unlinked.len() * 2);
         for i in 0..unlinked.len() {
             let j = (i + 1) % unlinked.len();
-            linked.push(unlinked[i]);
-            linked.push([unlinked[i][1], unlinked[j][0]]);
+            linked.push(vec![unlinked[i]]);
+            linked.push(catenary(
+                &unlinked[i][1],
+                &unlinked[j][0],
+                self.cable_tension,
+                CABLE_PIECES,
+            ));
         }
         linked
     }
@@ -44,44 +89,104 @@ impl LiftBuildings {
     ) -> (Option<GlobalTransfer>, Option<GlobalTransfer>) {
         let mut pick_up = None;
         let mut drop_off = None;
-        let mut index = 0;
-        let mut global_segment = 0;
-        while pick_up.is_none() || drop_off.is_none() {
-            if index >= self.buildings.len() {
+        for (building, global_segment) in self.buildings_with_global_segments() {
+            if pick_up.is_some() && drop_off.is_some() {
                 break;
             }
-            let building = &self.buildings[index];
 
             if let Some(LocalTransfer { segment, class }) = building.class.transfer() {
-                let position = building.wire_path_out(terrain)[segment][0];
-                let position = xy(position.x.round() as u32, position.y.round() as u32);
-                let transfer = GlobalTransfer {
-                    position,
-                    global_segment: global_segment + segment,
-                };
+                let transfer = self.global_transfer(building, global_segment, segment, terrain);
 
                 match class {
                     LocalTransferClass::PickUp => pick_up = Some(transfer),
                     LocalTransferClass::DropOff => drop_off = Some(transfer),
                 }
             }
-
-            index += 1;
-            global_segment += building.class.wire_path_out().len() + 1;
         }
         (pick_up, drop_off)
     }
+
+    pub fn get_mid_stations(&self, terrain: &Grid<f32>) -> Vec<GlobalMidStation> {
+        self.buildings_with_global_segments()
+            .filter(|(building, _)| building.class == LiftBuildingClass::MidStation)
+            .map(|(building, global_segment)| GlobalMidStation {
+                drop_off: self.global_transfer(building, global_segment, 0, terrain),
+                pick_up: self.global_transfer(building, global_segment, 1, terrain),
+            })
+            .collect()
+    }
+
+    fn buildings_with_global_segments(&self) -> impl Iterator<Item = (&LiftBuilding, usize)> {
+        // each segment out of a building is followed by a cable linking it to the next
+        self.buildings.iter().scan(0, |global_segment, building| {
+            let out = (building, *global_segment);
+            *global_segment += building.class.wire_path_out().len() * (1 + CABLE_PIECES);
+            Some(out)
+        })
+    }
+
+    fn global_transfer(
+        &self,
+        building: &LiftBuilding,
+        global_segment: usize,
+        segment: usize,
+        terrain: &Grid<f32>,
+    ) -> GlobalTransfer {
+        let position = building.wire_path_out(&self.lift_type, terrain)[segment][0];
+        GlobalTransfer {
+            position: xy(position.x.round() as u32, position.y.round() as u32),
+            global_segment: global_segment + segment * (1 + CABLE_PIECES),
+            // the out path runs along the building's x axis
+            travel_direction: Direction::snap_to_direction(building.yaw.rem_euclid(2.0 * PI)),
+        }
+    }
 }
 
-#[derive(Serialize, Deserialize)]
+/// Splits the cable hanging between two points into pieces along a catenary.
+/// Tension is the catenary parameter in meters - the higher the tension, the less the cable sags.
+fn catenary(from: &XYZ<f32>, to: &XYZ<f32>, tension: f32, pieces: usize) -> Vec<[XYZ<f32>; 2]> {
+    let run = xy(to.x - from.x, to.y - from.y).magnitude();
+    let sags = run > 0.0 && tension > 0.0;
+
+    // z = a * cosh((x - x0) / a) + c, passing through both ends
+    let a = tension;
+    let rise = to.z - from.z;
+    let x0 = run / 2.0 - a * (rise / (2.0 * a * (run / (2.0 * a)).sinh())).asinh();
+    let c = from.z - a * (-x0 / a).cosh();
+
+    let points = (0..=pieces)
+        .map(|piece| {
+            let p = piece as f32 / pieces as f32;
+            let z = if piece == 0 {
+                from.z
+            } else if piece == pieces {
+                to.z
+            } else if sags {
+                a * ((run * p - x0) / a).cosh() + c
+            } else {
+                from.z + rise * p
+            };
+            xyz(
+                from.x + (to.x - from.x) * p,
+                from.y + (to.y - from.y) * p,
+                z,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    points.windows(2).map(|pair| [pair[0], pair[1]]).collect()
+}
+
+#[derive(Clone, Serialize, Deserialize)]
 pub struct LiftBuilding {
     pub class: LiftBuildingClass,
     pub position: XY<u32>,
     pub yaw: f32,
+    pub manual_yaw: bool,
 }
 
 impl LiftBuilding {
-    pub fn transformation_matrix(&self, terrain: &Grid<f32>) -> Matrix4<f32> {
+    pub fn transformation_matrix(&self, lift_type: &LiftType, terrain: &Grid<f32>) -> Matrix4<f32> {
         transformation_matrix(Transformation {
             translation: Some(xyz(
                 self.position.x as f32,
@@ -91,26 +196,27 @@ impl LiftBuilding {
             yaw: Some(self.yaw),
             ..Transformation::default()
         }) * transformation_matrix(Transformation {
-            translation: Some(self.class.offset()),
-            scale: Some(self.class.scale()),
+            translation: Some(self.class.offset(lift_type)),
+            scale: Some(self.class.scale(lift_type)),
             ..Transformation::default()
         })
     }
 
-    pub fn wire_path_out(&self, terrain: &Grid<f32>) -> Vec<[XYZ<f32>; 2]> {
-        self.wire_path_over_terrain(&self.class.wire_path_out(), terrain)
+    pub fn wire_path_out(&self, lift_type: &LiftType, terrain: &Grid<f32>) -> Vec<[XYZ<f32>; 2]> {
+        self.wire_path_over_terrain(&self.class.wire_path_out(), lift_type, terrain)
     }
 
-    pub fn wire_path_back(&self, terrain: &Grid<f32>) -> Vec<[XYZ<f32>; 2]> {
-        self.wire_path_over_terrain(&self.class.wire_path_back(), terrain)
+    pub fn wire_path_back(&self, lift_type: &LiftType, terrain: &Grid<f32>) -> Vec<[XYZ<f32>; 2]> {
+        self.wire_path_over_terrain(&self.class.wire_path_back(), lift_type, terrain)
     }
 
     fn wire_path_over_terrain(
         &self,
         wire_path: &[[XYZ<f32>; 2]],
+        lift_type: &LiftType,
         terrain: &Grid<f32>,
     ) -> Vec<[XYZ<f32>; 2]> {
-        let matrix = self.transformation_matrix(terrain);
+        let matrix = self.transformation_matrix(lift_type, terrain);
 
         wire_path
             .iter()
@@ -123,6 +229,7 @@ impl LiftBuilding {
 pub enum LiftBuildingClass {
     PickUpStation,
     Pylon,
+    MidStation,
     DropOffStation,
 }
 
@@ -145,6 +252,13 @@ impl LiftBuildingClass {
             LiftBuildingClass::Pylon => {
                 vec![[xyz(-0.125, -0.5, 1.0), xyz(0.125, -0.5, 1.0)]]
             }
+            LiftBuildingClass::MidStation => {
+                // skiers get off at the start of the first segment and on at the start of the second
+                vec![
+                    [xyz(-0.5, -0.5, 0.0), xyz(-0.1, -0.5, 0.0)],
+                    [xyz(0.1, -0.5, 0.0), xyz(0.5, -0.5, 0.0)],
+                ]
+            }
             LiftBuildingClass::DropOffStation => {
                 vec![[xyz(-0.5, -0.5, 0.0), xyz(0.5, -0.5, 0.0)]]
             }
@@ -157,23 +271,31 @@ impl LiftBuildingClass {
             LiftBuildingClass::Pylon => {
                 vec![[xyz(0.125, 0.5, 1.0), xyz(-0.125, 0.5, 1.0)]]
             }
+            LiftBuildingClass::MidStation => vec![[xyz(0.5, 0.5, 0.0), xyz(-0.5, 0.5, 0.0)]],
             LiftBuildingClass::DropOffStation => vec![[xyz(0.5, 0.5, 0.0), xyz(-0.5, 0.5, 0.0)]],
         }
     }
 
-    pub fn offset(&self) -> XYZ<f32> {
-        match self {
-            LiftBuildingClass::PickUpStation => xyz(4.0, 2.0, 3.0),
-            LiftBuildingClass::Pylon => xyz(0.0, 0.0, 0.0),
-            LiftBuildingClass::DropOffStation => xyz(4.0, 2.0, 3.0),
+    pub fn offset(&self, lift_type: &LiftType) -> XYZ<f32> {
+        match (lift_type, self) {
+            (_, LiftBuildingClass::Pylon) => xyz(0.0, 0.0, 0.0),
+            (LiftType::DragLift, _) => xyz(2.0, 1.0, 3.0),
+            (LiftType::Chairlift, _) => xyz(4.0, 2.0, 3.0),
+            (LiftType::Gondola, _) => xyz(5.0, 3.0, 4.0),
+            (LiftType::MagicCarpet, _) => xyz(1.0, 0.5, 0.2),
         }
     }
 
-    pub fn scale(&self) -> XYZ<f32> {
-        match self {
-            LiftBuildingClass::PickUpStation => xyz(8.0, 4.0, 1.0),
-            LiftBuildingClass::Pylon => xyz(4.0, 3.0, 12.0),
-            LiftBuildingClass::DropOffStation => xyz(8.0, 4.0, 1.0),
+    pub fn scale(&self, lift_type: &LiftType) -> XYZ<f32> {
+        match (lift_type, self) {
+            (LiftType::DragLift, LiftBuildingClass::Pylon) => xyz(2.0, 2.0, 5.0),
+            (LiftType::Chairlift, LiftBuildingClass::Pylon) => xyz(4.0, 3.0, 12.0),
+            (LiftType::Gondola, LiftBuildingClass::Pylon) => xyz(6.0, 5.0, 20.0),
+            (LiftType::MagicCarpet, LiftBuildingClass::Pylon) => xyz(1.0, 1.0, 0.2),
+            (LiftType::DragLift, _) => xyz(4.0, 2.0, 1.0),
+            (LiftType::Chairlift, _) => xyz(8.0, 4.0, 1.0),
+            (LiftType::Gondola, _) => xyz(10.0, 6.0, 1.0),
+            (LiftType::MagicCarpet, _) => xyz(2.0, 1.0, 0.1),
         }
     }
 
@@ -191,3 +313,116 @@ impl LiftBuildingClass {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use commons::almost_eq::assert_almost_eq;
+
+    use super::*;
+
+    fn building(position: XY<u32>, manual_yaw: bool) -> LiftBuilding {
+        LiftBuilding {
+            class: LiftBuildingClass::Pylon,
+            position,
+            yaw: 0.0,
+            manual_yaw,
+        }
+    }
+
+    #[test]
+    fn test_catenary_level_cable_sags_in_the_middle() {
+        // given
+        let from = xyz(0.0, 0.0, 10.0);
+        let to = xyz(100.0, 0.0, 10.0);
+
+        // when
+        let result = catenary(&from, &to, 250.0, 4);
+
+        // then
+        assert_eq!(result.len(), 4);
+        assert_eq!(result[0][0], from);
+        assert_eq!(result[3][1], to);
+        let middle = result[1][1];
+        assert_almost_eq(middle.x, 50.0);
+        // sag of a catenary is a * (cosh(run / 2a) - 1)
+        assert_almost_eq(middle.z, 10.0 - 250.0 * ((0.2f32).cosh() - 1.0));
+    }
+
+    #[test]
+    fn test_catenary_sloping_cable_passes_through_both_ends() {
+        // given
+        let from = xyz(0.0, 0.0, 0.0);
+        let to = xyz(30.0, 40.0, 25.0);
+
+        // when
+        let result = catenary(&from, &to, 100.0, 10);
+
+        // then
+        let mut previous = from;
+        for [a, b] in result.iter() {
+            assert_eq!(*a, previous);
+            previous = *b;
+        }
+        assert_eq!(previous, to);
+        // cable hangs below the straight line
+        assert!(result[5][0].z < 12.5);
+    }
+
+    #[test]
+    fn test_catenary_vertical_cable_is_straight() {
+        // given
+        let from = xyz(0.0, 0.0, 0.0);
+        let to = xyz(0.0, 0.0, 10.0);
+
+        // when
+        let result = catenary(&from, &to, 100.0, 2);
+
+        // then
+        assert_eq!(
+            result,
+            vec![[from, xyz(0.0, 0.0, 5.0)], [xyz(0.0, 0.0, 5.0), to]]
+        );
+    }
+
+    #[test]
+    fn test_align_yaws() {
+        // given
+        let mut lift_buildings = LiftBuildings {
+            lift_type: LiftType::Chairlift,
+            cable_tension: 1000.0,
+            buildings: vec![
+                building(xy(0, 0), false),
+                building(xy(10, 0), false),
+                building(xy(10, 10), false),
+            ],
+        };
+
+        // when
+        lift_buildings.align_yaws();
+
+        // then
+        assert_almost_eq(lift_buildings.buildings[0].yaw, 0.0);
+        assert_almost_eq(lift_buildings.buildings[1].yaw, PI / 4.0);
+        assert_almost_eq(lift_buildings.buildings[2].yaw, PI / 2.0);
+    }
+
+    #[test]
+    fn test_align_yaws_skips_manual_yaw() {
+        // given
+        let mut lift_buildings = LiftBuildings {
+            lift_type: LiftType::Chairlift,
+            cable_tension: 1000.0,
+            buildings: vec![building(xy(0, 0), true), building(xy(0, 10), false)],
+        };
+        lift_buildings.buildings[0].yaw = 1.0;
+
+        // when
+        lift_buildings.align_yaws();
+
+        // then
+        assert_almost_eq(lift_buildings.buildings[0].yaw, 1.0);
+        assert_almost_eq(lift_buildings.buildings[1].yaw, PI / 2.0);
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+const WAIT_TIME_SAMPLES: usize = 16;
+
+#[derive(Serialize, Deserialize)]
+pub struct LiftQueue {
+    pub maze: Maze,
+    pub skiers: VecDeque<QueuedSkier>,
+    pub wait_times: VecDeque<u128>,
+    pub expected_wait_micros: u128,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Maze {
+    pub lanes: u32,
+    pub lane_length: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct QueuedSkier {
+    pub skier_id: usize,
+    pub joined_micros: u128,
+}
+
+impl LiftQueue {
+    pub fn new(maze: Maze) -> LiftQueue {
+        LiftQueue {
+            maze,
+            skiers: VecDeque::new(),
+            wait_times: VecDeque::with_capacity(WAIT_TIME_SAMPLES),
+            expected_wait_micros: 0,
+        }
+    }
+
+    /// Removes the skiers from the queue, returning how long each of them waited
+    pub fn remove(&mut self, skier_ids: &[usize], micros: &u128) -> Vec<(usize, u128)> {
+        let mut wait_times = Vec::with_capacity(skier_ids.len());
+        self.skiers.retain(|queued| {
+            if !skier_ids.contains(&queued.skier_id) {
+                return true;
+            }
+            wait_times.push((queued.skier_id, micros.saturating_sub(queued.joined_micros)));
+            false
+        });
+
+        for (_, wait_micros) in wait_times.iter() {
+            self.record_wait(*wait_micros);
+        }
+
+        wait_times
+    }
+
+    pub fn record_wait(&mut self, wait_micros: u128) {
+        if self.wait_times.len() == WAIT_TIME_SAMPLES {
+            self.wait_times.pop_front();
+        }
+        self.wait_times.push_back(wait_micros);
+    }
+
+    pub fn average_wait_micros(&self) -> Option<u128> {
+        if self.wait_times.is_empty() {
+            return None;
+        }
+        Some(self.wait_times.iter().sum::<u128>() / self.wait_times.len() as u128)
+    }
+}
+
+impl Maze {
+    pub fn capacity(&self) -> usize {
+        (self.lanes * self.lane_length) as usize
+    }
+}
@@ -0,0 +1,195 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::ability::Ability;
+use crate::model::carousel::CarClass;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum LiftType {
+    DragLift,
+    Chairlift,
+    Gondola,
+    MagicCarpet,
+}
+
+pub const LIFT_TYPES: [LiftType; 4] = [
+    LiftType::DragLift,
+    LiftType::Chairlift,
+    LiftType::Gondola,
+    LiftType::MagicCarpet,
+];
+
+impl LiftType {
+    pub fn velocity(&self) -> f32 {
+        match self {
+            LiftType::DragLift => 3.0,
+            LiftType::Chairlift => 2.5,
+            LiftType::Gondola => 5.0,
+            LiftType::MagicCarpet => 0.75,
+        }
+    }
+
+    pub fn car_interval_meters(&self) -> f32 {
+        match self {
+            LiftType::DragLift => 12.0,
+            LiftType::Chairlift => 12.0,
+            LiftType::Gondola => 40.0,
+            LiftType::MagicCarpet => 1.5,
+        }
+    }
+
+    pub fn car_classes(&self) -> &'static [CarClass] {
+        match self {
+            LiftType::DragLift => &[CarClass::TBar],
+            LiftType::Chairlift => &[
+                CarClass::Single,
+                CarClass::Double,
+                CarClass::Quad,
+                CarClass::SixPack,
+            ],
+            LiftType::Gondola => &[CarClass::Gondola],
+            LiftType::MagicCarpet => &[CarClass::Belt],
+        }
+    }
+
+    pub fn default_car_class(&self) -> CarClass {
+        match self {
+            LiftType::DragLift => CarClass::TBar,
+            LiftType::Chairlift => CarClass::Double,
+            LiftType::Gondola => CarClass::Gondola,
+            LiftType::MagicCarpet => CarClass::Belt,
+        }
+    }
+
+    /// Minimum ability needed to ride the lift
+    pub fn ability(&self) -> Ability {
+        match self {
+            LiftType::DragLift => Ability::Intermediate,
+            LiftType::Chairlift => Ability::Beginner,
+            LiftType::Gondola => Ability::Beginner,
+            LiftType::MagicCarpet => Ability::Beginner,
+        }
+    }
+
+    /// Wind above which the lift must stop loading, if it is exposed to the wind at all
+    pub fn max_wind_meters_per_second(&self) -> Option<f32> {
+        match self {
+            LiftType::DragLift => Some(22.0),
+            LiftType::Chairlift => Some(18.0),
+            LiftType::Gondola => Some(20.0),
+            LiftType::MagicCarpet => None,
+        }
+    }
+
+    pub fn max_length_meters(&self) -> f32 {
+        match self {
+            LiftType::DragLift => 1000.0,
+            LiftType::Chairlift => 2500.0,
+            LiftType::Gondola => 5000.0,
+            LiftType::MagicCarpet => 100.0,
+        }
+    }
+
+    /// Maximum rise over run between pick up and drop off
+    pub fn max_slope(&self) -> f32 {
+        match self {
+            LiftType::DragLift => 0.5,
+            LiftType::Chairlift => 1.0,
+            LiftType::Gondola => 1.5,
+            LiftType::MagicCarpet => 0.15,
+        }
+    }
+
+    /// Minimum height of the cable above the ground, or None if the lift runs along the ground
+    pub fn min_clearance_meters(&self) -> Option<f32> {
+        match self {
+            LiftType::DragLift => Some(1.0),
+            LiftType::Chairlift => Some(2.0),
+            LiftType::Gondola => Some(2.5),
+            LiftType::MagicCarpet => None,
+        }
+    }
+
+    /// Maximum horizontal distance between consecutive points supporting the cable
+    pub fn max_span_meters(&self) -> f32 {
+        match self {
+            LiftType::DragLift => 100.0,
+            LiftType::Chairlift => 300.0,
+            LiftType::Gondola => 1000.0,
+            LiftType::MagicCarpet => 20.0,
+        }
+    }
+
+    /// Maximum rise over run of any single cable segment
+    pub fn max_cable_incline(&self) -> f32 {
+        match self {
+            LiftType::DragLift => 0.6,
+            LiftType::Chairlift => 1.2,
+            LiftType::Gondola => 2.0,
+            LiftType::MagicCarpet => 0.2,
+        }
+    }
+
+    /// Default catenary parameter of the cable in meters - the higher the tension, the less the cable sags
+    pub fn cable_tension(&self) -> f32 {
+        match self {
+            LiftType::DragLift => 800.0,
+            LiftType::Chairlift => 1000.0,
+            LiftType::Gondola => 4000.0,
+            LiftType::MagicCarpet => 100_000.0,
+        }
+    }
+
+    /// Price of each meter of the lift, measured from building to building
+    pub fn price_per_meter(&self) -> f32 {
+        match self {
+            LiftType::DragLift => 30.0,
+            LiftType::Chairlift => 100.0,
+            LiftType::Gondola => 200.0,
+            LiftType::MagicCarpet => 50.0,
+        }
+    }
+
+    /// Price of each pylon holding up the cable
+    pub fn pylon_price(&self) -> f32 {
+        match self {
+            LiftType::DragLift => 2_000.0,
+            LiftType::Chairlift => 5_000.0,
+            LiftType::Gondola => 15_000.0,
+            LiftType::MagicCarpet => 0.0,
+        }
+    }
+
+    /// Price of each pick up, mid or drop off station
+    pub fn station_price(&self) -> f32 {
+        match self {
+            LiftType::DragLift => 5_000.0,
+            LiftType::Chairlift => 20_000.0,
+            LiftType::Gondola => 50_000.0,
+            LiftType::MagicCarpet => 2_000.0,
+        }
+    }
+
+    /// Whether riders sit in the car, rather than standing on their skis
+    pub fn is_seated(&self) -> bool {
+        match self {
+            LiftType::DragLift => false,
+            LiftType::Chairlift => true,
+            LiftType::Gondola => true,
+            LiftType::MagicCarpet => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_car_class_is_supported() {
+        for lift_type in LIFT_TYPES {
+            assert!(lift_type
+                .car_classes()
+                .contains(&lift_type.default_car_class()));
+        }
+    }
+}
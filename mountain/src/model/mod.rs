@@ -1,6 +1,7 @@
 pub mod ability;
 pub mod building;
 pub mod carousel;
+pub mod construction;
 pub mod costs;
 pub mod direction;
 pub mod door;
@@ -8,14 +9,31 @@ pub mod entrance;
 pub mod exit;
 pub mod frame;
 pub mod gate;
+pub mod groomer;
+pub mod group;
 pub mod hash_vec;
+pub mod hotel;
+pub mod incident;
+pub mod ledger;
 pub mod lift;
 pub mod lift_building;
+pub mod lift_queue;
+pub mod lift_type;
 pub mod message;
+pub mod needs;
 pub mod open;
+pub mod patroller;
 pub mod piste;
+pub mod reliability;
 pub mod reservation;
+pub mod satisfaction;
+pub mod scenario;
 pub mod selection;
 pub mod skier;
 pub mod skiing;
+pub mod snow;
+pub mod snowmaking;
+pub mod time_of_day;
 pub mod tree;
+pub mod visitors;
+pub mod weather;
@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::building::Service;
+
+/// Hunger gained per hour out on the mountain
+const HUNGER_PER_HOUR: f32 = 0.25;
+/// Tiredness gained per hour out on the mountain
+const TIREDNESS_PER_HOUR: f32 = 0.2;
+/// Needs above this send skiers looking for a service building
+pub const PRESSING: f32 = 0.7;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Need {
+    Hunger,
+    Rest,
+}
+
+/// From 0 for no need to 1 for the most pressing need
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Needs {
+    pub hunger: f32,
+    pub rest: f32,
+}
+
+impl Need {
+    pub fn is_relieved_by(&self, service: &Service) -> bool {
+        match self {
+            Need::Hunger => service.hunger > 0.0,
+            Need::Rest => service.rest > 0.0,
+        }
+    }
+}
+
+impl Needs {
+    pub fn grow(&mut self, hours: f32) {
+        self.hunger = (self.hunger + HUNGER_PER_HOUR * hours).min(1.0);
+        self.rest = (self.rest + TIREDNESS_PER_HOUR * hours).min(1.0);
+    }
+
+    /// The most pressing need, if any need is pressing
+    pub fn pressing(&self) -> Option<Need> {
+        let (need, value) = if self.hunger >= self.rest {
+            (Need::Hunger, self.hunger)
+        } else {
+            (Need::Rest, self.rest)
+        };
+        (value >= PRESSING).then_some(need)
+    }
+
+    pub fn relieve(&mut self, service: &Service) {
+        self.hunger = (self.hunger - service.hunger).max(0.0);
+        self.rest = (self.rest - service.rest).max(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::building::Kind;
+
+    use super::*;
+
+    #[test]
+    fn test_needs_grow_up_to_one() {
+        // given
+        let mut needs = Needs::default();
+
+        // when
+        needs.grow(2.0);
+        let after_two_hours = needs;
+        needs.grow(100.0);
+
+        // then
+        assert_eq!(
+            after_two_hours,
+            Needs {
+                hunger: 0.5,
+                rest: 0.4
+            }
+        );
+        assert_eq!(
+            needs,
+            Needs {
+                hunger: 1.0,
+                rest: 1.0
+            }
+        );
+    }
+
+    #[test]
+    fn test_pressing() {
+        assert_eq!(Needs::default().pressing(), None);
+        assert_eq!(
+            Needs {
+                hunger: 0.8,
+                rest: 0.9
+            }
+            .pressing(),
+            Some(Need::Rest)
+        );
+        assert_eq!(
+            Needs {
+                hunger: PRESSING,
+                rest: 0.0
+            }
+            .pressing(),
+            Some(Need::Hunger)
+        );
+    }
+
+    #[test]
+    fn test_restaurant_relieves_hunger_and_some_rest() {
+        // given
+        let restaurant = Kind::Restaurant.service().unwrap();
+        let mut needs = Needs {
+            hunger: 0.9,
+            rest: 0.9,
+        };
+
+        // when
+        needs.relieve(&restaurant);
+
+        // then
+        assert_eq!(needs.hunger, 0.0);
+        assert!(needs.rest > 0.0 && needs.rest < 0.9);
+        assert!(Need::Hunger.is_relieved_by(&restaurant));
+        assert!(!Need::Hunger.is_relieved_by(&Kind::Toilets.service().unwrap()));
+    }
+}
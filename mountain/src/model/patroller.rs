@@ -0,0 +1,86 @@
+use commons::color::Rgb;
+use serde::{Deserialize, Serialize};
+
+use crate::model::skier::Clothes;
+use crate::model::skiing::{Plan, State};
+
+/// Patrollers each patrol hut can have out on the mountain at once
+pub const PATROLLERS_PER_HUT: usize = 2;
+
+const RED: Rgb<f32> = Rgb::new(0.9, 0.1, 0.1);
+const WHITE: Rgb<f32> = Rgb::new(1.0, 1.0, 1.0);
+const BLACK: Rgb<f32> = Rgb::new(0.0, 0.0, 0.0);
+pub const CLOTHES: Clothes<Rgb<f32>> = Clothes {
+    skis: BLACK,
+    trousers: BLACK,
+    jacket: RED,
+    helmet: WHITE,
+};
+
+#[derive(Serialize, Deserialize)]
+pub struct Patroller {
+    pub hut_id: usize,
+    /// The injured skier the patroller is helping
+    pub skier_id: usize,
+    pub leg: Leg,
+    pub plan: Plan,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Leg {
+    ToIncident,
+    /// Hauling the injured skier on a sled
+    ToFirstAid {
+        building_id: usize,
+    },
+}
+
+impl Patroller {
+    pub fn is_arrived(&self, micros: &u128) -> bool {
+        match &self.plan {
+            Plan::Stationary(_) => true,
+            Plan::Moving(events) => events.last().is_none_or(|event| event.micros <= *micros),
+        }
+    }
+
+    pub fn last_state(&self) -> Option<State> {
+        match &self.plan {
+            Plan::Stationary(state) => Some(*state),
+            Plan::Moving(events) => events.last().map(|event| event.state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::geometry::xy;
+
+    use crate::model::direction::Direction;
+    use crate::model::skiing::Event;
+
+    use super::*;
+
+    #[test]
+    fn test_is_arrived() {
+        // given
+        let state = State {
+            position: xy(1, 2),
+            velocity: 0,
+            travel_direction: Direction::North,
+        };
+        let patroller = Patroller {
+            hut_id: 0,
+            skier_id: 1,
+            leg: Leg::ToIncident,
+            plan: Plan::Moving(vec![
+                Event { micros: 10, state },
+                Event { micros: 20, state },
+            ]),
+        };
+
+        // then
+        assert!(!patroller.is_arrived(&19));
+        assert!(patroller.is_arrived(&20));
+        assert_eq!(patroller.last_state(), Some(state));
+    }
+}
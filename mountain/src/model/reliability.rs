@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize)]
+pub struct Reliability {
+    pub mean_micros_between_breakdowns: u128,
+    pub repair_micros: u128,
+    pub maintenance_interval_micros: u128,
+    pub maintenance_micros: u128,
+    pub last_maintenance_micros: Option<u128>,
+    pub status: Status,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Status {
+    Running,
+    BrokenDown { until_micros: u128 },
+    AwaitingMaintenance,
+    UnderMaintenance { until_micros: u128 },
+}
+
+impl Reliability {
+    /// Whether the carousel is stopped, with any riders stuck where they are
+    pub fn is_stalled(&self) -> bool {
+        matches!(
+            self.status,
+            Status::BrokenDown { .. } | Status::UnderMaintenance { .. }
+        )
+    }
+
+    /// Chance of at least one breakdown in the elapsed time, treating breakdowns as a Poisson process
+    pub fn breakdown_probability(&self, elapsed_micros: u128) -> f64 {
+        if self.mean_micros_between_breakdowns == 0 {
+            return 1.0;
+        }
+        let rate = elapsed_micros as f64 / self.mean_micros_between_breakdowns as f64;
+        1.0 - (-rate).exp()
+    }
+
+    pub fn is_maintenance_due(&self, micros: u128) -> bool {
+        self.last_maintenance_micros
+            .map(|last| micros.saturating_sub(last) >= self.maintenance_interval_micros)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::almost_eq::assert_almost_eq;
+
+    use super::*;
+
+    fn reliability() -> Reliability {
+        Reliability {
+            mean_micros_between_breakdowns: 1_000,
+            repair_micros: 0,
+            maintenance_interval_micros: 100,
+            maintenance_micros: 0,
+            last_maintenance_micros: Some(50),
+            status: Status::Running,
+        }
+    }
+
+    #[test]
+    fn test_breakdown_probability() {
+        // given
+        let reliability = reliability();
+
+        // then
+        assert_almost_eq(reliability.breakdown_probability(0), 0.0);
+        assert_almost_eq(
+            reliability.breakdown_probability(1_000),
+            1.0 - (-1.0f64).exp(),
+        );
+    }
+
+    #[test]
+    fn test_is_maintenance_due() {
+        // given
+        let reliability = reliability();
+
+        // then
+        assert!(!reliability.is_maintenance_due(149));
+        assert!(reliability.is_maintenance_due(150));
+    }
+}
@@ -0,0 +1,397 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::ability::Ability;
+use crate::model::skier::Equipment;
+use crate::model::weather::Weather;
+
+/// Thoughts each skier remembers, the oldest are forgotten first
+pub const MAX_THOUGHTS: usize = 5;
+/// Skiers do not dwell on the same thought again until this long after they last had it
+pub const THOUGHT_COOLDOWN_MICROS: u128 = 30 * 60 * 1_000_000;
+pub const DEFAULT_SATISFACTION: f32 = 0.75;
+pub const MAX_STARS: f32 = 5.0;
+pub const DEFAULT_STARS: f32 = MAX_STARS * DEFAULT_SATISFACTION;
+/// Lift queues shorter than this are a pleasant surprise
+pub const SHORT_WAIT_MICROS: u128 = 60 * 1_000_000;
+/// Lift queues longer than this are a disappointment
+pub const LONG_WAIT_MICROS: u128 = 5 * 60 * 1_000_000;
+/// Weather at least this appealing makes for a great day out
+pub const GOOD_WEATHER_APPEAL: f32 = 0.9;
+/// Weather less appealing than this spoils the day
+pub const BAD_WEATHER_APPEAL: f32 = 0.5;
+/// Share of the gap to the current average satisfaction closed by each rating update
+const RATING_SMOOTHING: f32 = 0.1;
+/// Satisfaction taken off the average for each incident per guest in the last day
+const INCIDENT_PENALTY: f32 = 10.0;
+/// Share of rooms booked at a rating of zero stars, rising to all rooms at the maximum
+const MIN_OCCUPANCY: f32 = 0.2;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Thought {
+    ShortWait,
+    LongWait,
+    Blocked,
+    GoodRun,
+    TooEasy,
+    TooHard,
+    TooFlat,
+    Crowded,
+    CannotGetHome,
+    Injured,
+    SlowRescue,
+    GoodWeather,
+    BadWeather,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Memory {
+    pub micros: u128,
+    pub thought: Thought,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Satisfaction {
+    /// From 0 to 1
+    pub score: f32,
+    /// Most recent last
+    pub thoughts: VecDeque<Memory>,
+}
+
+/// The resort's star rating, following the average satisfaction of its guests and its safety
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Rating {
+    pub stars: f32,
+}
+
+impl Thought {
+    pub fn effect(&self) -> f32 {
+        match self {
+            Thought::ShortWait => 0.02,
+            Thought::LongWait => -0.1,
+            Thought::Blocked => -0.03,
+            Thought::GoodRun => 0.05,
+            Thought::TooEasy => -0.02,
+            Thought::TooHard => -0.08,
+            Thought::TooFlat => -0.04,
+            Thought::Crowded => -0.05,
+            Thought::CannotGetHome => -0.2,
+            Thought::Injured => -0.3,
+            Thought::SlowRescue => -0.1,
+            Thought::GoodWeather => 0.03,
+            Thought::BadWeather => -0.05,
+        }
+    }
+
+    pub fn text(&self) -> &'static str {
+        match self {
+            Thought::ShortWait => "Hardly any queue for that lift",
+            Thought::LongWait => "That lift queue took forever",
+            Thought::Blocked => "Someone is in my way",
+            Thought::GoodRun => "This run is just right for me",
+            Thought::TooEasy => "This run is too easy for me",
+            Thought::TooHard => "This run is too hard for me",
+            Thought::TooFlat => "This is too flat for my board",
+            Thought::Crowded => "The slopes are too crowded",
+            Thought::CannotGetHome => "I cannot find my way back",
+            Thought::Injured => "I have hurt myself",
+            Thought::SlowRescue => "The ski patrol took ages to reach me",
+            Thought::GoodWeather => "What a beautiful day for skiing",
+            Thought::BadWeather => "This weather is miserable",
+        }
+    }
+
+    /// How a skier feels about waiting in a lift queue, if they care at all
+    pub fn about_wait(wait_micros: &u128) -> Option<Thought> {
+        if *wait_micros < SHORT_WAIT_MICROS {
+            Some(Thought::ShortWait)
+        } else if *wait_micros > LONG_WAIT_MICROS {
+            Some(Thought::LongWait)
+        } else {
+            None
+        }
+    }
+
+    /// How a skier out on the mountain feels about the weather, if they care at all
+    pub fn about_weather(weather: &Weather) -> Option<Thought> {
+        let appeal = weather.appeal();
+        if appeal >= GOOD_WEATHER_APPEAL {
+            Some(Thought::GoodWeather)
+        } else if appeal < BAD_WEATHER_APPEAL {
+            Some(Thought::BadWeather)
+        } else {
+            None
+        }
+    }
+
+    /// How a skier feels about skiing a run of the given ability, snowboarders only
+    /// enjoying the steepest runs they can manage
+    pub fn about_run(equipment: &Equipment, skier: &Ability, run: &Ability) -> Thought {
+        if run > skier {
+            return Thought::TooHard;
+        }
+        let levels_too_easy = (*skier as usize) - (*run as usize);
+        match equipment {
+            Equipment::Skis if levels_too_easy >= 2 => Thought::TooEasy,
+            Equipment::Snowboard if levels_too_easy >= 1 => Thought::TooFlat,
+            _ => Thought::GoodRun,
+        }
+    }
+}
+
+impl Default for Satisfaction {
+    fn default() -> Self {
+        Satisfaction {
+            score: DEFAULT_SATISFACTION,
+            thoughts: VecDeque::with_capacity(MAX_THOUGHTS),
+        }
+    }
+}
+
+impl Satisfaction {
+    /// Has the thought unless it was had recently, returning whether it was had
+    pub fn think(&mut self, thought: Thought, micros: &u128) -> bool {
+        if self.thoughts.iter().any(|memory| {
+            memory.thought == thought
+                && micros.saturating_sub(memory.micros) < THOUGHT_COOLDOWN_MICROS
+        }) {
+            return false;
+        }
+
+        self.score = (self.score + thought.effect()).clamp(0.0, 1.0);
+        if self.thoughts.len() == MAX_THOUGHTS {
+            self.thoughts.pop_front();
+        }
+        self.thoughts.push_back(Memory {
+            micros: *micros,
+            thought,
+        });
+        true
+    }
+}
+
+impl Default for Rating {
+    fn default() -> Self {
+        Rating {
+            stars: DEFAULT_STARS,
+        }
+    }
+}
+
+impl Rating {
+    /// Moves the rating towards the average of the satisfaction scores, less a penalty for
+    /// the daily incidents per guest, if there are any scores
+    pub fn update(&mut self, scores: impl Iterator<Item = f32>, incident_rate: f32) {
+        let (total, count) =
+            scores.fold((0.0, 0), |(total, count), score| (total + score, count + 1));
+        if count == 0 {
+            return;
+        }
+        let safe = total / count as f32 - incident_rate * INCIDENT_PENALTY;
+        let current = MAX_STARS * safe.max(0.0);
+        self.stars += (current - self.stars) * RATING_SMOOTHING;
+    }
+
+    /// Share of rooms guests book at this rating
+    pub fn occupancy(&self) -> f32 {
+        MIN_OCCUPANCY + (1.0 - MIN_OCCUPANCY) * (self.stars / MAX_STARS).clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::almost_eq::assert_almost_eq;
+
+    use crate::model::weather::Sky;
+
+    use super::*;
+
+    #[test]
+    fn test_thought_changes_score() {
+        // given
+        let mut satisfaction = Satisfaction::default();
+
+        // when
+        let had = satisfaction.think(Thought::LongWait, &0);
+
+        // then
+        assert!(had);
+        assert_almost_eq(satisfaction.score, DEFAULT_SATISFACTION - 0.1);
+        assert_eq!(
+            satisfaction.thoughts,
+            VecDeque::from([Memory {
+                micros: 0,
+                thought: Thought::LongWait
+            }])
+        );
+    }
+
+    #[test]
+    fn test_same_thought_is_not_repeated_during_cooldown() {
+        // given
+        let mut satisfaction = Satisfaction::default();
+        satisfaction.think(Thought::Crowded, &0);
+
+        // when
+        let during = satisfaction.think(Thought::Crowded, &(THOUGHT_COOLDOWN_MICROS - 1));
+        let other = satisfaction.think(Thought::GoodRun, &(THOUGHT_COOLDOWN_MICROS - 1));
+        let after = satisfaction.think(Thought::Crowded, &THOUGHT_COOLDOWN_MICROS);
+
+        // then
+        assert!(!during);
+        assert!(other);
+        assert!(after);
+        assert_eq!(satisfaction.thoughts.len(), 3);
+    }
+
+    #[test]
+    fn test_oldest_thoughts_are_forgotten() {
+        // given
+        let mut satisfaction = Satisfaction::default();
+
+        // when
+        for i in 0..=MAX_THOUGHTS as u128 {
+            satisfaction.think(Thought::GoodRun, &(i * THOUGHT_COOLDOWN_MICROS));
+        }
+
+        // then
+        assert_eq!(satisfaction.thoughts.len(), MAX_THOUGHTS);
+        assert_eq!(satisfaction.thoughts[0].micros, THOUGHT_COOLDOWN_MICROS);
+        assert_eq!(satisfaction.score, 1.0);
+    }
+
+    #[test]
+    fn test_thought_about_wait() {
+        assert_eq!(Thought::about_wait(&0), Some(Thought::ShortWait));
+        assert_eq!(Thought::about_wait(&SHORT_WAIT_MICROS), None);
+        assert_eq!(Thought::about_wait(&LONG_WAIT_MICROS), None);
+        assert_eq!(
+            Thought::about_wait(&(LONG_WAIT_MICROS + 1)),
+            Some(Thought::LongWait)
+        );
+    }
+
+    #[test]
+    fn test_thought_about_weather() {
+        // given
+        let weather = |sky, visibility_meters| Weather {
+            sky,
+            wind_meters_per_second: 0.0,
+            visibility_meters,
+        };
+
+        // then
+        assert_eq!(
+            Thought::about_weather(&weather(Sky::Sunny, 10_000.0)),
+            Some(Thought::GoodWeather)
+        );
+        assert_eq!(
+            Thought::about_weather(&weather(Sky::Overcast, 10_000.0)),
+            None
+        );
+        assert_eq!(
+            Thought::about_weather(&weather(Sky::Storm, 10_000.0)),
+            Some(Thought::BadWeather)
+        );
+        assert_eq!(
+            Thought::about_weather(&weather(Sky::Overcast, 0.0)),
+            Some(Thought::BadWeather)
+        );
+    }
+
+    #[test]
+    fn test_thought_about_run() {
+        assert_eq!(
+            Thought::about_run(&Equipment::Skis, &Ability::Intermediate, &Ability::Advanced),
+            Thought::TooHard
+        );
+        assert_eq!(
+            Thought::about_run(&Equipment::Skis, &Ability::Advanced, &Ability::Intermediate),
+            Thought::GoodRun
+        );
+        assert_eq!(
+            Thought::about_run(&Equipment::Skis, &Ability::Expert, &Ability::Intermediate),
+            Thought::TooEasy
+        );
+    }
+
+    #[test]
+    fn test_snowboarders_want_steeper_runs() {
+        assert_eq!(
+            Thought::about_run(
+                &Equipment::Snowboard,
+                &Ability::Advanced,
+                &Ability::Advanced
+            ),
+            Thought::GoodRun
+        );
+        assert_eq!(
+            Thought::about_run(
+                &Equipment::Snowboard,
+                &Ability::Advanced,
+                &Ability::Intermediate
+            ),
+            Thought::TooFlat
+        );
+        assert_eq!(
+            Thought::about_run(
+                &Equipment::Snowboard,
+                &Ability::Intermediate,
+                &Ability::Expert
+            ),
+            Thought::TooHard
+        );
+    }
+
+    #[test]
+    fn test_rating_follows_satisfaction() {
+        // given
+        let mut rating = Rating::default();
+
+        // when
+        rating.update([1.0, 1.0].into_iter(), 0.0);
+
+        // then
+        assert_almost_eq(
+            rating.stars,
+            DEFAULT_STARS + (MAX_STARS - DEFAULT_STARS) * RATING_SMOOTHING,
+        );
+    }
+
+    #[test]
+    fn test_incidents_lower_rating() {
+        // given
+        let mut safe = Rating::default();
+        let mut dangerous = Rating::default();
+
+        // when
+        safe.update([DEFAULT_SATISFACTION].into_iter(), 0.0);
+        dangerous.update([DEFAULT_SATISFACTION].into_iter(), 0.05);
+
+        // then
+        assert_almost_eq(safe.stars, DEFAULT_STARS);
+        assert_almost_eq(
+            dangerous.stars,
+            DEFAULT_STARS - MAX_STARS * 0.05 * INCIDENT_PENALTY * RATING_SMOOTHING,
+        );
+    }
+
+    #[test]
+    fn test_rating_without_guests_is_unchanged() {
+        // given
+        let mut rating = Rating::default();
+
+        // when
+        rating.update([].into_iter(), 0.0);
+
+        // then
+        assert_eq!(rating, Rating::default());
+    }
+
+    #[test]
+    fn test_occupancy() {
+        assert_eq!(Rating { stars: 0.0 }.occupancy(), MIN_OCCUPANCY);
+        assert_eq!(Rating { stars: MAX_STARS }.occupancy(), 1.0);
+    }
+}
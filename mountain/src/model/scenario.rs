@@ -0,0 +1,274 @@
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_TREE_LINE_ELEVATION: f32 = 512.0;
+
+/// A resort to build from a scenario file, with goals to reach before a deadline
+#[derive(Clone, Deserialize)]
+pub struct Scenario {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub terrain: Terrain,
+    #[serde(default = "default_tree_line_elevation")]
+    pub tree_line_elevation: f32,
+    pub starting_balance: i64,
+    pub days: u32,
+    #[serde(default)]
+    pub structures: Vec<Structure>,
+    pub goals: Vec<Goal>,
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Terrain {
+    Generated {
+        power: u32,
+        seed: i32,
+    },
+    /// Greyscale image, with white at `max_elevation`, relative to the scenario directory
+    Heightmap {
+        file: String,
+        max_elevation: f32,
+        #[serde(default)]
+        seed: i32,
+    },
+}
+
+/// Structures in place when the scenario starts, `from` and `to` are inclusive
+#[derive(Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Structure {
+    Piste {
+        from: [u32; 2],
+        to: [u32; 2],
+    },
+    Path {
+        from: [u32; 2],
+        to: [u32; 2],
+    },
+    Hotel {
+        from: [u32; 2],
+        to: [u32; 2],
+        height: u32,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Goal {
+    /// Distinct skiers riding a lift in a single day
+    SkiersPerDay(usize),
+    /// Vertical drop of an open expert piste
+    ExpertRunVerticalMeters(f32),
+    /// Resort star rating
+    Rating(f32),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Outcome {
+    Won,
+    Lost,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GoalProgress {
+    pub goal: Goal,
+    pub best: f32,
+    pub achieved: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Progress {
+    pub name: String,
+    pub description: String,
+    pub days: u32,
+    pub goals: Vec<GoalProgress>,
+    pub outcome: Option<Outcome>,
+}
+
+fn default_tree_line_elevation() -> f32 {
+    DEFAULT_TREE_LINE_ELEVATION
+}
+
+impl Terrain {
+    pub fn seed(&self) -> i32 {
+        match self {
+            Terrain::Generated { seed, .. } => *seed,
+            Terrain::Heightmap { seed, .. } => *seed,
+        }
+    }
+}
+
+impl Goal {
+    pub fn target(&self) -> f32 {
+        match self {
+            Goal::SkiersPerDay(skiers) => *skiers as f32,
+            Goal::ExpertRunVerticalMeters(meters) => *meters,
+            Goal::Rating(stars) => *stars,
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Goal::SkiersPerDay(skiers) => format!("Serve {} skiers in a day", skiers),
+            Goal::ExpertRunVerticalMeters(meters) => {
+                format!("Open an expert run with {}m vertical", meters)
+            }
+            Goal::Rating(stars) => format!("Reach a rating of {} stars", stars),
+        }
+    }
+}
+
+impl Progress {
+    pub fn new(scenario: &Scenario) -> Progress {
+        Progress {
+            name: scenario.name.clone(),
+            description: scenario.description.clone(),
+            days: scenario.days,
+            goals: scenario
+                .goals
+                .iter()
+                .map(|goal| GoalProgress {
+                    goal: *goal,
+                    best: 0.0,
+                    achieved: false,
+                })
+                .collect(),
+            outcome: None,
+        }
+    }
+
+    /// Records the current value for the goal at `index`, returning whether it was just achieved
+    ///
+    /// Goals stay achieved once they are reached
+    pub fn record(&mut self, index: usize, value: f32) -> bool {
+        let Some(progress) = self.goals.get_mut(index) else {
+            return false;
+        };
+        progress.best = progress.best.max(value);
+        if progress.achieved || progress.best < progress.goal.target() {
+            return false;
+        }
+        progress.achieved = true;
+        true
+    }
+
+    /// Decides the outcome on `day` (counting from 0), returning it if it was just decided
+    pub fn evaluate(&mut self, day: u128) -> Option<Outcome> {
+        if self.outcome.is_some() {
+            return None;
+        }
+        if self.goals.iter().all(|progress| progress.achieved) {
+            self.outcome = Some(Outcome::Won);
+        } else if day >= self.days as u128 {
+            self.outcome = Some(Outcome::Lost);
+        }
+        self.outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENARIO: &str = r#"
+        name = "Test"
+        starting_balance = 100000
+        days = 10
+
+        [terrain]
+        type = "generated"
+        power = 8
+        seed = 3
+
+        [[structures]]
+        type = "hotel"
+        from = [10, 10]
+        to = [20, 16]
+        height = 12
+
+        [[goals]]
+        skiers_per_day = 500
+
+        [[goals]]
+        expert_run_vertical_meters = 400.0
+
+        [[goals]]
+        rating = 4.0
+    "#;
+
+    #[test]
+    fn test_parse_scenario() {
+        // when
+        let scenario: Scenario = toml::from_str(SCENARIO).unwrap();
+
+        // then
+        assert_eq!(scenario.name, "Test");
+        assert_eq!(scenario.terrain.seed(), 3);
+        assert_eq!(scenario.tree_line_elevation, DEFAULT_TREE_LINE_ELEVATION);
+        assert_eq!(scenario.structures.len(), 1);
+        assert_eq!(
+            scenario.goals,
+            vec![
+                Goal::SkiersPerDay(500),
+                Goal::ExpertRunVerticalMeters(400.0),
+                Goal::Rating(4.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_goals_stay_achieved() {
+        // given
+        let scenario: Scenario = toml::from_str(SCENARIO).unwrap();
+        let mut progress = Progress::new(&scenario);
+
+        // when
+        let below = progress.record(0, 499.0);
+        let reached = progress.record(0, 500.0);
+        let again = progress.record(0, 600.0);
+        progress.record(0, 0.0);
+
+        // then
+        assert!(!below);
+        assert!(reached);
+        assert!(!again);
+        assert!(progress.goals[0].achieved);
+        assert_eq!(progress.goals[0].best, 600.0);
+    }
+
+    #[test]
+    fn test_won_when_all_goals_achieved() {
+        // given
+        let scenario: Scenario = toml::from_str(SCENARIO).unwrap();
+        let mut progress = Progress::new(&scenario);
+        progress.record(0, 500.0);
+
+        // when
+        let before = progress.evaluate(2);
+        progress.record(1, 450.0);
+        progress.record(2, 4.5);
+        let after = progress.evaluate(3);
+
+        // then
+        assert_eq!(before, None);
+        assert_eq!(after, Some(Outcome::Won));
+        assert_eq!(progress.evaluate(4), None);
+    }
+
+    #[test]
+    fn test_lost_after_deadline() {
+        // given
+        let scenario: Scenario = toml::from_str(SCENARIO).unwrap();
+        let mut progress = Progress::new(&scenario);
+        progress.record(0, 500.0);
+
+        // when
+        let last_day = progress.evaluate(9);
+        let deadline = progress.evaluate(10);
+
+        // then
+        assert_eq!(last_day, None);
+        assert_eq!(deadline, Some(Outcome::Lost));
+    }
+}
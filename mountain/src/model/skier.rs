@@ -7,7 +7,15 @@ use crate::model::ability::Ability;
 pub struct Skier {
     pub clothes: Clothes<Color>,
     pub ability: Ability,
+    pub equipment: Equipment,
     pub hotel_id: usize,
+    pub group_id: usize,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Equipment {
+    Skis,
+    Snowboard,
 }
 
 #[derive(Clone, Copy, Default, Debug, Serialize, Deserialize)]
@@ -31,6 +39,15 @@ pub enum Color {
     Color5,
 }
 
+impl Equipment {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Equipment::Skis => "Skis",
+            Equipment::Snowboard => "Snowboard",
+        }
+    }
+}
+
 impl Color {
     pub fn rgb(&self) -> Rgb<f32> {
         match self {
@@ -0,0 +1,199 @@
+use serde::{Deserialize, Serialize};
+
+pub const MIN_SKIABLE_DEPTH_METERS: f32 = 0.1;
+pub const INITIAL_DEPTH_METERS: f32 = 1.0;
+
+pub const SEA_LEVEL_TEMPERATURE_CELSIUS: f32 = 5.0;
+const LAPSE_RATE_CELSIUS_PER_METER: f32 = 0.0065;
+const MELT_METERS_PER_DEGREE_HOUR: f32 = 0.0002;
+const ICY_PASSES: u32 = 50;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Snow {
+    pub depth_meters: f32,
+    pub quality: Quality,
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum Quality {
+    Powder,
+    Packed,
+    Icy,
+    Slush,
+}
+
+impl Default for Snow {
+    fn default() -> Self {
+        Snow {
+            depth_meters: INITIAL_DEPTH_METERS,
+            quality: Quality::Packed,
+        }
+    }
+}
+
+pub struct Conditions {
+    pub temperature_celsius: f32,
+    pub snowfall_meters: f32,
+    pub passes: u32,
+    pub hours: f32,
+}
+
+impl Snow {
+    pub fn is_skiable(&self) -> bool {
+        self.depth_meters >= MIN_SKIABLE_DEPTH_METERS
+    }
+
+    pub fn evolve(
+        &mut self,
+        Conditions {
+            temperature_celsius,
+            snowfall_meters,
+            passes,
+            hours,
+        }: &Conditions,
+    ) {
+        if *temperature_celsius > 0.0 {
+            // snow melts, and anything falling is rain
+            self.depth_meters = (self.depth_meters
+                - MELT_METERS_PER_DEGREE_HOUR * temperature_celsius * hours)
+                .max(0.0);
+            self.quality = Quality::Slush;
+            return;
+        }
+
+        if *snowfall_meters > 0.0 {
+            self.depth_meters += snowfall_meters;
+            self.quality = Quality::Powder;
+        } else if self.quality == Quality::Slush {
+            self.quality = Quality::Icy;
+        }
+
+        // skiers pack down fresh snow, and eventually scrape it to ice
+        if *passes > 0 && self.quality == Quality::Powder {
+            self.quality = Quality::Packed;
+        } else if *passes >= ICY_PASSES && self.quality == Quality::Packed {
+            self.quality = Quality::Icy;
+        }
+    }
+}
+
+impl Quality {
+    /// Friction of skis sliding over the snow
+    pub fn friction(&self) -> f32 {
+        match self {
+            Quality::Powder => 0.08,
+            Quality::Packed => 0.02,
+            Quality::Icy => 0.01,
+            Quality::Slush => 0.12,
+        }
+    }
+}
+
+/// Air temperature at the elevation, falling with height
+pub fn temperature_celsius(elevation_meters: f32, sea_level_temperature_celsius: f32) -> f32 {
+    sea_level_temperature_celsius - elevation_meters * LAPSE_RATE_CELSIUS_PER_METER
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::almost_eq::assert_almost_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_temperature_falls_with_elevation() {
+        assert_almost_eq(temperature_celsius(0.0, 5.0), 5.0);
+        assert_almost_eq(temperature_celsius(1000.0, 5.0), -1.5);
+    }
+
+    fn conditions() -> Conditions {
+        Conditions {
+            temperature_celsius: -5.0,
+            snowfall_meters: 0.0,
+            passes: 0,
+            hours: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_snowfall() {
+        // given
+        let mut snow = Snow::default();
+
+        // when
+        snow.evolve(&Conditions {
+            snowfall_meters: 0.1,
+            ..conditions()
+        });
+
+        // then
+        assert_almost_eq(snow.depth_meters, INITIAL_DEPTH_METERS + 0.1);
+        assert_eq!(snow.quality, Quality::Powder);
+    }
+
+    #[test]
+    fn test_melting() {
+        // given
+        let mut snow = Snow::default();
+
+        // when
+        snow.evolve(&Conditions {
+            temperature_celsius: 10.0,
+            hours: 100.0,
+            ..conditions()
+        });
+
+        // then
+        assert_almost_eq(snow.depth_meters, INITIAL_DEPTH_METERS - 0.2);
+        assert_eq!(snow.quality, Quality::Slush);
+    }
+
+    #[test]
+    fn test_slush_refreezes() {
+        // given
+        let mut snow = Snow {
+            depth_meters: 1.0,
+            quality: Quality::Slush,
+        };
+
+        // when
+        snow.evolve(&conditions());
+
+        // then
+        assert_eq!(snow.quality, Quality::Icy);
+    }
+
+    #[test]
+    fn test_skier_traffic() {
+        // given
+        let mut powder = Snow {
+            depth_meters: 1.0,
+            quality: Quality::Powder,
+        };
+        let mut packed = Snow::default();
+
+        // when
+        powder.evolve(&Conditions {
+            passes: 1,
+            ..conditions()
+        });
+        packed.evolve(&Conditions {
+            passes: ICY_PASSES,
+            ..conditions()
+        });
+
+        // then
+        assert_eq!(powder.quality, Quality::Packed);
+        assert_eq!(packed.quality, Quality::Icy);
+    }
+
+    #[test]
+    fn test_is_skiable() {
+        assert!(Snow::default().is_skiable());
+        assert!(!Snow {
+            depth_meters: 0.05,
+            quality: Quality::Packed
+        }
+        .is_skiable());
+    }
+}
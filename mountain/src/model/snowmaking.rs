@@ -0,0 +1,131 @@
+use std::f32::consts::PI;
+
+use commons::geometry::XY;
+use serde::{Deserialize, Serialize};
+
+/// Cells that must drain through a cell before it can feed a water source
+pub const MIN_SOURCE_RAIN: usize = 1024;
+const WATER_M3_PER_HOUR_PER_RAIN: f32 = 0.01;
+const MAX_SOURCE_CAPACITY_M3_PER_HOUR: f32 = 500.0;
+
+pub const CANNON_WATER_M3_PER_HOUR: f32 = 20.0;
+pub const CANNON_RADIUS_METERS: u32 = 10;
+pub const MAX_PIPE_METERS: f32 = 200.0;
+pub const MAX_TEMPERATURE_CELSIUS: f32 = -2.0;
+/// Cannons stop once the snow around them is this deep
+pub const TARGET_DEPTH_METERS: f32 = 1.0;
+const SNOW_M3_PER_WATER_M3: f32 = 2.0;
+
+#[derive(Serialize, Deserialize)]
+pub struct WaterSource {
+    pub position: XY<u32>,
+    pub capacity_m3_per_hour: f32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Cannon {
+    pub position: XY<u32>,
+    pub source_id: usize,
+    /// Where the pipe feeding this cannon joins the network
+    pub pipe_from: XY<u32>,
+    pub status: Status,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Status {
+    Idle,
+    TooWarm,
+    Running { supply: f32 },
+}
+
+impl WaterSource {
+    pub fn new(position: XY<u32>, rain: usize) -> WaterSource {
+        WaterSource {
+            position,
+            capacity_m3_per_hour: (rain as f32 * WATER_M3_PER_HOUR_PER_RAIN)
+                .min(MAX_SOURCE_CAPACITY_M3_PER_HOUR),
+        }
+    }
+
+    /// Share of each running cannon's demand that this source can meet
+    pub fn supply(&self, running_cannons: usize) -> f32 {
+        if running_cannons == 0 {
+            return 1.0;
+        }
+        let demand = running_cannons as f32 * CANNON_WATER_M3_PER_HOUR;
+        (self.capacity_m3_per_hour / demand).min(1.0)
+    }
+}
+
+pub fn can_make_snow(temperature_celsius: f32) -> bool {
+    temperature_celsius <= MAX_TEMPERATURE_CELSIUS
+}
+
+/// Depth of snow a cannon spreads over its radius from the water it sprays
+pub fn depth_meters(water_m3: f32) -> f32 {
+    let area = PI * (CANNON_RADIUS_METERS * CANNON_RADIUS_METERS) as f32;
+    water_m3 * SNOW_M3_PER_WATER_M3 / area
+}
+
+/// Cells within reach of a cannon at the position
+pub fn coverage(position: &XY<u32>, width: u32, height: u32) -> impl Iterator<Item = XY<u32>> {
+    let radius = CANNON_RADIUS_METERS;
+    let XY { x, y } = *position;
+    let from_x = x.saturating_sub(radius);
+    let to_x = (x + radius).min(width - 1);
+    let from_y = y.saturating_sub(radius);
+    let to_y = (y + radius).min(height - 1);
+    (from_y..=to_y)
+        .flat_map(move |cy| (from_x..=to_x).map(move |cx| XY { x: cx, y: cy }))
+        .filter(move |cell| {
+            let dx = cell.x.abs_diff(x);
+            let dy = cell.y.abs_diff(y);
+            dx * dx + dy * dy <= radius * radius
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::almost_eq::assert_almost_eq;
+    use commons::geometry::xy;
+
+    use super::*;
+
+    #[test]
+    fn test_capacity_from_rain() {
+        assert_almost_eq(WaterSource::new(xy(0, 0), 2000).capacity_m3_per_hour, 20.0);
+        assert_almost_eq(
+            WaterSource::new(xy(0, 0), 1_000_000).capacity_m3_per_hour,
+            MAX_SOURCE_CAPACITY_M3_PER_HOUR,
+        );
+    }
+
+    #[test]
+    fn test_supply_shared_between_cannons() {
+        // given
+        let source = WaterSource::new(xy(0, 0), 4000);
+
+        // then
+        assert_almost_eq(source.supply(0), 1.0);
+        assert_almost_eq(source.supply(2), 1.0);
+        assert_almost_eq(source.supply(4), 0.5);
+    }
+
+    #[test]
+    fn test_can_make_snow() {
+        assert!(can_make_snow(-5.0));
+        assert!(!can_make_snow(0.0));
+    }
+
+    #[test]
+    fn test_coverage_is_clipped_to_grid() {
+        // when
+        let cells = coverage(&xy(0, 0), 100, 100).collect::<Vec<_>>();
+
+        // then
+        assert!(cells.contains(&xy(10, 0)));
+        assert!(cells.contains(&xy(7, 7)));
+        assert!(!cells.contains(&xy(8, 8)));
+        assert!(cells.iter().all(|cell| cell.x <= 10 && cell.y <= 10));
+    }
+}
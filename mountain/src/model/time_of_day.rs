@@ -0,0 +1,36 @@
+use crate::services::economy::MICROS_PER_DAY;
+
+pub const MICROS_PER_HOUR: u128 = 60 * 60 * 1_000_000;
+
+/// Whether the time of day `time_micros` passes after `from_micros` and no later than `to_micros`
+pub fn passed(time_micros: u128, from_micros: &u128, to_micros: &u128) -> bool {
+    occurrences(time_micros, to_micros) > occurrences(time_micros, from_micros)
+}
+
+fn occurrences(time_micros: u128, micros: &u128) -> u128 {
+    if *micros < time_micros {
+        0
+    } else {
+        (micros - time_micros) / MICROS_PER_DAY + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIME: u128 = 8 * MICROS_PER_HOUR;
+
+    #[test]
+    fn test_passed() {
+        assert!(passed(TIME, &0, &TIME));
+        assert!(!passed(TIME, &TIME, &(TIME + 1)));
+        assert!(!passed(TIME, &0, &(TIME - 1)));
+        assert!(passed(TIME, &(TIME + 1), &(MICROS_PER_DAY + TIME)));
+    }
+
+    #[test]
+    fn test_passed_over_several_days() {
+        assert!(passed(TIME, &(TIME + 1), &(3 * MICROS_PER_DAY)));
+    }
+}
@@ -0,0 +1,78 @@
+use commons::geometry::XYRectangle;
+
+use crate::model::building::Kind;
+use crate::model::satisfaction::Rating;
+use crate::model::time_of_day::MICROS_PER_HOUR;
+use crate::services::economy::MICROS_PER_DAY;
+
+/// Time of day when day visitors arrive
+pub const ARRIVAL_MICROS: u128 = 8 * MICROS_PER_HOUR;
+/// Time of day after which day visitors head back to where they arrived
+pub const DEPARTURE_MICROS: u128 = 16 * MICROS_PER_HOUR;
+/// Footprint area of a car park per visitor it can bring
+const CAR_PARK_AREA_PER_VISITOR: u32 = 4;
+/// Visitors each bus brings
+const BUS_CAPACITY: usize = 40;
+
+/// Whether day visitors should be heading back at `micros`
+pub fn is_evening(micros: &u128) -> bool {
+    micros % MICROS_PER_DAY >= DEPARTURE_MICROS
+}
+
+/// Most visitors that can arrive at a terminal in a day
+pub fn capacity(kind: &Kind, footprint: &XYRectangle<u32>) -> usize {
+    match kind {
+        Kind::CarPark => {
+            let area = (footprint.to.x - footprint.from.x) * (footprint.to.y - footprint.from.y);
+            (area / CAR_PARK_AREA_PER_VISITOR) as usize
+        }
+        Kind::BusStop => BUS_CAPACITY,
+        _ => 0,
+    }
+}
+
+/// Visitors arriving at a terminal, fewer for a poor rating or bad weather
+pub fn arrivals(capacity: usize, rating: &Rating, weather_appeal: f32) -> usize {
+    (capacity as f32 * rating.occupancy() * weather_appeal.clamp(0.0, 1.0)).round() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::geometry::xy;
+
+    use crate::model::satisfaction::MAX_STARS;
+
+    use super::*;
+
+    #[test]
+    fn test_is_evening() {
+        assert!(!is_evening(&ARRIVAL_MICROS));
+        assert!(is_evening(&DEPARTURE_MICROS));
+        assert!(!is_evening(&MICROS_PER_DAY));
+    }
+
+    #[test]
+    fn test_capacity() {
+        // given
+        let footprint = XYRectangle {
+            from: xy(10, 10),
+            to: xy(18, 15),
+        };
+
+        // then
+        assert_eq!(capacity(&Kind::CarPark, &footprint), 10);
+        assert_eq!(capacity(&Kind::BusStop, &footprint), BUS_CAPACITY);
+        assert_eq!(capacity(&Kind::Hotel, &footprint), 0);
+    }
+
+    #[test]
+    fn test_arrivals() {
+        // given
+        let rating = Rating { stars: MAX_STARS };
+
+        // then
+        assert_eq!(arrivals(40, &rating, 1.0), 40);
+        assert_eq!(arrivals(40, &rating, 0.5), 20);
+        assert_eq!(arrivals(40, &rating, 0.0), 0);
+    }
+}
@@ -0,0 +1,184 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+pub const FOG_VISIBILITY_METERS: f32 = 1000.0;
+pub const CLEAR_VISIBILITY_METERS: f32 = 10000.0;
+/// Wind picks up by this proportion for every meter of elevation
+const WIND_GRADIENT_PER_METER: f32 = 0.0005;
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub enum Sky {
+    Sunny,
+    Overcast,
+    Snowing,
+    Storm,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Weather {
+    pub sky: Sky,
+    pub wind_meters_per_second: f32,
+    pub visibility_meters: f32,
+}
+
+impl Sky {
+    pub fn snowfall_meters_per_hour(&self) -> f32 {
+        match self {
+            Sky::Sunny | Sky::Overcast => 0.0,
+            Sky::Snowing => 0.02,
+            Sky::Storm => 0.04,
+        }
+    }
+
+    /// Chance of each sky following this one, in the order Sunny, Overcast, Snowing, Storm
+    fn transitions(&self) -> [f32; 4] {
+        match self {
+            Sky::Sunny => [0.6, 0.3, 0.1, 0.0],
+            Sky::Overcast => [0.3, 0.35, 0.3, 0.05],
+            Sky::Snowing => [0.1, 0.3, 0.45, 0.15],
+            Sky::Storm => [0.0, 0.2, 0.5, 0.3],
+        }
+    }
+
+    fn wind_range_meters_per_second(&self) -> (f32, f32) {
+        match self {
+            Sky::Sunny => (0.0, 6.0),
+            Sky::Overcast => (2.0, 10.0),
+            Sky::Snowing => (3.0, 12.0),
+            Sky::Storm => (15.0, 30.0),
+        }
+    }
+
+    fn visibility_range_meters(&self) -> (f32, f32) {
+        match self {
+            Sky::Sunny => (5000.0, CLEAR_VISIBILITY_METERS),
+            Sky::Overcast => (200.0, CLEAR_VISIBILITY_METERS),
+            Sky::Snowing => (200.0, 3000.0),
+            Sky::Storm => (50.0, 500.0),
+        }
+    }
+}
+
+impl Default for Weather {
+    fn default() -> Self {
+        Weather {
+            sky: Sky::Sunny,
+            wind_meters_per_second: 0.0,
+            visibility_meters: CLEAR_VISIBILITY_METERS,
+        }
+    }
+}
+
+impl Weather {
+    /// Weather that could follow this weather
+    pub fn next<R: Rng>(&self, rng: &mut R) -> Weather {
+        let roll = rng.gen::<f32>();
+        let mut cumulative = 0.0;
+        let sky = [Sky::Sunny, Sky::Overcast, Sky::Snowing, Sky::Storm]
+            .into_iter()
+            .zip(self.sky.transitions())
+            .find(|(_, probability)| {
+                cumulative += probability;
+                roll < cumulative
+            })
+            .map(|(sky, _)| sky)
+            .unwrap_or(self.sky);
+
+        let (min_wind, max_wind) = sky.wind_range_meters_per_second();
+        let (min_visibility, max_visibility) = sky.visibility_range_meters();
+        Weather {
+            sky,
+            wind_meters_per_second: rng.gen_range(min_wind..=max_wind),
+            visibility_meters: rng.gen_range(min_visibility..=max_visibility),
+        }
+    }
+
+    pub fn is_foggy(&self) -> bool {
+        self.visibility_meters < FOG_VISIBILITY_METERS
+    }
+
+    pub fn wind_meters_per_second_at(&self, elevation_meters: f32) -> f32 {
+        self.wind_meters_per_second * (1.0 + elevation_meters.max(0.0) * WIND_GRADIENT_PER_METER)
+    }
+
+    /// How much the weather tempts skiers out onto the mountain, from 0 to 1
+    pub fn appeal(&self) -> f32 {
+        let sky = match self.sky {
+            Sky::Sunny => 1.0,
+            Sky::Overcast => 0.8,
+            Sky::Snowing => 0.7,
+            Sky::Storm => 0.1,
+        };
+        if self.is_foggy() {
+            sky * 0.6
+        } else {
+            sky
+        }
+    }
+
+    pub fn keeps_skiers_indoors(&self) -> bool {
+        self.sky == Sky::Storm
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::almost_eq::assert_almost_eq;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn test_transitions_sum_to_one() {
+        for sky in [Sky::Sunny, Sky::Overcast, Sky::Snowing, Sky::Storm] {
+            assert_almost_eq(sky.transitions().iter().sum(), 1.0);
+        }
+    }
+
+    #[test]
+    fn test_next_is_within_ranges_of_sky() {
+        // given
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut weather = Weather::default();
+
+        for _ in 0..100 {
+            // when
+            weather = weather.next(&mut rng);
+
+            // then
+            let (min_wind, max_wind) = weather.sky.wind_range_meters_per_second();
+            assert!(weather.wind_meters_per_second >= min_wind);
+            assert!(weather.wind_meters_per_second <= max_wind);
+            let (min_visibility, max_visibility) = weather.sky.visibility_range_meters();
+            assert!(weather.visibility_meters >= min_visibility);
+            assert!(weather.visibility_meters <= max_visibility);
+        }
+    }
+
+    #[test]
+    fn test_wind_increases_with_elevation() {
+        // given
+        let weather = Weather {
+            wind_meters_per_second: 10.0,
+            ..Weather::default()
+        };
+
+        // then
+        assert_almost_eq(weather.wind_meters_per_second_at(0.0), 10.0);
+        assert_almost_eq(weather.wind_meters_per_second_at(1000.0), 15.0);
+    }
+
+    #[test]
+    fn test_fog_reduces_appeal() {
+        // given
+        let clear = Weather::default();
+        let foggy = Weather {
+            visibility_meters: 100.0,
+            ..Weather::default()
+        };
+
+        // then
+        assert!(foggy.appeal() < clear.appeal());
+    }
+}
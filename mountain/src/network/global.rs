@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::iter::{empty, once};
+use std::iter::empty;
 
 use commons::map::ContainsKeyValue;
 use network::model::{Edge, OutNetwork};
@@ -8,7 +8,8 @@ use crate::model::ability::Ability;
 use crate::model::carousel::Carousel;
 use crate::model::costs::Costs;
 use crate::model::entrance::Entrance;
-use crate::model::lift::Lift;
+use crate::model::lift::{Lift, Portal};
+use crate::model::lift_queue::LiftQueue;
 use crate::model::open;
 use crate::model::skiing::State;
 
@@ -18,6 +19,7 @@ pub struct GlobalNetwork<'a> {
     pub lifts: &'a HashMap<usize, Lift>,
     pub pick_up_to_lift: &'a HashMap<usize, usize>,
     pub carousels: &'a HashMap<usize, Carousel>,
+    pub lift_queues: &'a HashMap<usize, LiftQueue>,
     pub entrances: &'a HashMap<usize, Entrance>,
     pub open: &'a HashMap<usize, open::Status>,
     pub costs: &'a HashMap<usize, Costs<State>>,
@@ -26,19 +28,23 @@ pub struct GlobalNetwork<'a> {
 }
 
 impl GlobalNetwork<'_> {
-    fn lift_travel_micros(&self, lift: &usize) -> u64 {
-        self.lifts
-            .get(lift)
-            .and_then(|lift| {
-                self.carousels
-                    .get(&lift.carousel_id)
-                    .map(|carousel| lift.ride_length_meters() / carousel.velocity)
-            })
+    fn lift_travel_micros(&self, lift: &Lift, pick_up: &Portal, drop_off: &Portal) -> u64 {
+        self.carousels
+            .get(&lift.carousel_id)
+            .map(|carousel| lift.ride_length_meters_between(pick_up, drop_off) / carousel.velocity)
             .map(|seconds| seconds * 1_000_000.0)
             .map(|micros| micros as u64)
             .map(|micros| micros / GLOBAL_COST_DIVISOR) // to avoid exceeding u32 limit
             .unwrap_or(0)
     }
+
+    fn queue_wait_micros(&self, pick_up: &usize) -> u64 {
+        self.lift_queues
+            .get(pick_up)
+            .map(|queue| queue.expected_wait_micros as u64)
+            .map(|micros| micros / GLOBAL_COST_DIVISOR) // to avoid exceeding u32 limit
+            .unwrap_or(0)
+    }
 }
 
 impl OutNetwork<usize> for GlobalNetwork<'_> {
@@ -46,13 +52,31 @@ impl OutNetwork<usize> for GlobalNetwork<'_> {
         &'b self,
         from: &'b usize,
     ) -> Box<dyn Iterator<Item = network::model::Edge<usize>> + 'b> {
-        // lift edge
+        // lift edges, one for each drop off after the pick up
         if let Some(lift_id) = self.pick_up_to_lift.get(from) {
-            return Box::new(once(Edge {
-                from: *from,
-                to: self.lifts[lift_id].drop_off.id,
-                cost: self.lift_travel_micros(from).try_into().unwrap(),
-            }));
+            let lift = &self.lifts[lift_id];
+            if lift.lift_type.ability() > self.ability {
+                return Box::new(empty());
+            }
+            let Some(pick_up) = lift.pick_ups().find(|pick_up| pick_up.id == *from) else {
+                return Box::new(empty());
+            };
+            let queue_wait_micros = self.queue_wait_micros(from);
+            return Box::new(
+                lift.drop_offs_after(pick_up)
+                    .filter(|drop_off| {
+                        self.open
+                            .contains_key_value(drop_off.id, open::Status::Open)
+                    })
+                    .map(move |drop_off| Edge {
+                        from: *from,
+                        to: drop_off.id,
+                        cost: (self.lift_travel_micros(lift, pick_up, drop_off)
+                            + queue_wait_micros)
+                            .try_into()
+                            .unwrap(),
+                    }),
+            );
         }
 
         let Some(Entrance {
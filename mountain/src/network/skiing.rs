@@ -8,7 +8,9 @@ use network::model::{Edge, InNetwork, OutNetwork};
 use crate::model::ability::Ability;
 use crate::model::direction::{Direction, DIRECTIONS};
 use crate::model::piste;
+use crate::model::skier::Equipment;
 use crate::model::skiing::State;
+use crate::model::snow::Snow;
 use crate::utils::ability::exposure;
 use crate::{
     network::velocity_encoding::{decode_velocity, encode_velocity},
@@ -24,10 +26,21 @@ const BRAKING_FRICTION: f32 = 1.0;
 const POLING_ACCELERATION: f32 = 1.0;
 const POLING_MAX_VELOCITY: f32 = 1.0;
 
+/// Snowboarders have no poles, so push along with one foot out of the binding
+const SNOWBOARD_POLING_ACCELERATION: f32 = 0.8;
+/// Snowboards lose speed gliding over terrain flatter than this along the direction of travel,
+/// as on flat runs and traverses
+const SNOWBOARD_FLAT_GRADIENT: f32 = 0.1;
+const SNOWBOARD_FLAT_FRICTION: f32 = 0.05;
+/// Snowboarders hop or skate along paths more slowly than skiers walk
+const SNOWBOARD_WALK_FACTOR: f32 = 1.5;
+
 pub struct SkiingNetwork<'a> {
     pub terrain: &'a Grid<f32>,
+    pub snow: &'a Grid<Snow>,
     pub class: piste::Class,
     pub ability: Ability,
+    pub equipment: Equipment,
     pub is_accessible_fn: &'a dyn Fn(&XY<u32>) -> bool,
     pub is_valid_edge_fn: &'a dyn Fn(&State, &State) -> bool,
 }
@@ -52,6 +65,7 @@ impl<'a> SkiingNetwork<'a> {
         self.poling_edges(from)
             .chain(self.skiing_edges(from))
             .chain(self.braking_edges(from))
+            .filter(|edge| self.snow[edge.to.position].is_skiable())
             .filter(|edge| exposure(self.terrain, &edge.to.position) <= self.ability.max_exposure())
             .chain(self.turning_edges(from))
             .filter(|edge| (self.is_valid_edge_fn)(&edge.from, &edge.to))
@@ -101,6 +115,8 @@ impl<'a> SkiingNetwork<'a> {
 
         let run = travel_direction.run();
         let rise = self.terrain[to_position] - self.terrain[from.position];
+        let friction =
+            friction + self.snow[to_position].quality.friction() + self.flat_friction(run, rise);
         let physics::skiing::Solution { velocity, duration } =
             physics::skiing::solve(initial_velocity, run, rise, 0.0, friction)?;
 
@@ -208,8 +224,14 @@ impl<'a> SkiingNetwork<'a> {
 
         let run = from.travel_direction.run();
         let rise = self.terrain[to_position] - self.terrain[from.position];
-        let physics::skiing::Solution { velocity, duration } =
-            physics::skiing::solve(initial_velocity, run, rise, POLING_ACCELERATION, 0.0)?;
+        let friction = self.snow[to_position].quality.friction();
+        let physics::skiing::Solution { velocity, duration } = physics::skiing::solve(
+            initial_velocity,
+            run,
+            rise,
+            self.poling_acceleration(),
+            friction,
+        )?;
 
         Some(Edge {
             from: *from,
@@ -222,6 +244,31 @@ impl<'a> SkiingNetwork<'a> {
         })
     }
 
+    fn poling_acceleration(&self) -> f32 {
+        match self.equipment {
+            Equipment::Skis => POLING_ACCELERATION,
+            Equipment::Snowboard => SNOWBOARD_POLING_ACCELERATION,
+        }
+    }
+
+    fn flat_friction(&self, run: f32, rise: f32) -> f32 {
+        match self.equipment {
+            Equipment::Skis => 0.0,
+            Equipment::Snowboard if -rise / run < SNOWBOARD_FLAT_GRADIENT => {
+                SNOWBOARD_FLAT_FRICTION
+            }
+            Equipment::Snowboard => 0.0,
+        }
+    }
+
+    fn walk_cost(&self, direction: Direction) -> u32 {
+        let duration = match self.equipment {
+            Equipment::Skis => walk_duration(direction),
+            Equipment::Snowboard => walk_duration(direction).mul_f32(SNOWBOARD_WALK_FACTOR),
+        };
+        duration.as_micros().try_into().unwrap()
+    }
+
     fn get_to_position(&self, position: &XY<u32>, travel_direction: &Direction) -> Option<XY<u32>> {
         let offset = travel_direction.offset();
         self.terrain.offset(position, offset)
@@ -249,7 +296,7 @@ impl<'a> SkiingNetwork<'a> {
                             travel_direction: direction,
                             velocity: 0,
                         },
-                        cost: walk_duration(direction).as_micros().try_into().unwrap(),
+                        cost: self.walk_cost(direction),
                     })
             })
     }
@@ -309,3 +356,86 @@ impl InNetwork<State> for StationaryNetwork {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use commons::geometry::xy;
+
+    use super::*;
+
+    fn flat_and_steep_terrain() -> Grid<f32> {
+        let mut terrain = Grid::from_element(3, 3, 0.0);
+        terrain[xy(0, 2)] = 2.0;
+        terrain[xy(1, 2)] = 1.0;
+        terrain
+    }
+
+    fn skiing_edge_cost(equipment: Equipment, from: &State) -> u32 {
+        let terrain = flat_and_steep_terrain();
+        let snow = Grid::from_element(3, 3, Snow::default());
+        let network = SkiingNetwork {
+            terrain: &terrain,
+            snow: &snow,
+            class: piste::Class::Piste,
+            ability: Ability::Expert,
+            equipment,
+            is_accessible_fn: &|_| true,
+            is_valid_edge_fn: &|_, _| true,
+        };
+        network
+            .get_skiing_edge(from, Direction::East, from.velocity, 0.0)
+            .unwrap()
+            .cost
+    }
+
+    #[test]
+    fn test_snowboarders_are_slower_gliding_on_the_flat() {
+        // given
+        let flat = State {
+            position: xy(0, 0),
+            velocity: 3,
+            travel_direction: Direction::East,
+        };
+        let steep = State {
+            position: xy(0, 2),
+            ..flat
+        };
+
+        // then
+        assert!(
+            skiing_edge_cost(Equipment::Snowboard, &flat)
+                > skiing_edge_cost(Equipment::Skis, &flat)
+        );
+        assert_eq!(
+            skiing_edge_cost(Equipment::Snowboard, &steep),
+            skiing_edge_cost(Equipment::Skis, &steep)
+        );
+    }
+
+    #[test]
+    fn test_snowboarders_push_off_more_slowly() {
+        // given
+        let terrain = Grid::from_element(3, 3, 0.0);
+        let snow = Grid::from_element(3, 3, Snow::default());
+        let from = State {
+            position: xy(0, 1),
+            velocity: 0,
+            travel_direction: Direction::East,
+        };
+        let poling_cost = |equipment| {
+            let network = SkiingNetwork {
+                terrain: &terrain,
+                snow: &snow,
+                class: piste::Class::Piste,
+                ability: Ability::Expert,
+                equipment,
+                is_accessible_fn: &|_| true,
+                is_valid_edge_fn: &|_, _| true,
+            };
+            network.get_poling_edge(&from, &from.velocity).unwrap().cost
+        };
+
+        // then
+        assert!(poling_cost(Equipment::Snowboard) > poling_cost(Equipment::Skis));
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::model::ledger::{Account, Ledger};
+
+pub const MICROS_PER_DAY: u128 = 24 * 60 * 60 * 1_000_000;
+pub const STARTING_BALANCE: i64 = 500_000;
+pub const RIDE_PRICE: i64 = 5;
+pub const DAY_PASS_PRICE: i64 = 40;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Ticket {
+    PerRide,
+    DayPass,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Service {
+    ledger: Ledger,
+    ticket: Ticket,
+    day: u128,
+    /// Skiers who have bought a pass for the current day
+    pass_holders: HashSet<usize>,
+    /// Skiers who have ridden a lift on the current day
+    riders: HashSet<usize>,
+    bankrupt: bool,
+}
+
+impl Ticket {
+    pub fn price(&self) -> i64 {
+        match self {
+            Ticket::PerRide => RIDE_PRICE,
+            Ticket::DayPass => DAY_PASS_PRICE,
+        }
+    }
+}
+
+impl Service {
+    pub fn new() -> Service {
+        Service::with_balance(STARTING_BALANCE)
+    }
+
+    pub fn with_balance(balance: i64) -> Service {
+        Service {
+            ledger: Ledger::new(balance),
+            ticket: Ticket::DayPass,
+            day: 0,
+            pass_holders: HashSet::default(),
+            riders: HashSet::default(),
+            bankrupt: false,
+        }
+    }
+
+    pub fn ledger(&self) -> &Ledger {
+        &self.ledger
+    }
+
+    pub fn ticket(&self) -> Ticket {
+        self.ticket
+    }
+
+    pub fn set_ticket(&mut self, ticket: Ticket) {
+        self.ticket = ticket;
+    }
+
+    pub fn is_bankrupt(&self) -> bool {
+        self.bankrupt
+    }
+
+    pub fn riders_today(&self) -> usize {
+        self.riders.len()
+    }
+
+    pub fn record(&mut self, account: Account, amount: i64) {
+        self.ledger.record(account, amount);
+    }
+
+    /// Charges the skier for boarding a lift
+    pub fn sell_ticket(&mut self, skier_id: &usize) {
+        self.riders.insert(*skier_id);
+        match self.ticket {
+            Ticket::PerRide => self.ledger.record(Account::Tickets, RIDE_PRICE),
+            Ticket::DayPass => {
+                if self.pass_holders.insert(*skier_id) {
+                    self.ledger.record(Account::Tickets, DAY_PASS_PRICE);
+                }
+            }
+        }
+    }
+
+    /// Closes the ledger for the day if `micros` is in a later day, returning whether it closed
+    ///
+    /// The resort goes bankrupt if it closes a day in debt
+    pub fn update(&mut self, micros: &u128) -> bool {
+        let day = micros / MICROS_PER_DAY;
+        if day <= self.day {
+            return false;
+        }
+        self.day = day;
+        self.ledger.close_day(day);
+        self.pass_holders.clear();
+        self.riders.clear();
+        if self.ledger.balance() < 0 {
+            self.bankrupt = true;
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_pass_is_sold_once_per_day() {
+        // given
+        let mut service = Service::new();
+        service.set_ticket(Ticket::DayPass);
+
+        // when
+        service.sell_ticket(&1);
+        service.sell_ticket(&1);
+        service.sell_ticket(&2);
+        service.update(&MICROS_PER_DAY);
+        service.sell_ticket(&1);
+
+        // then
+        assert_eq!(
+            service.ledger().balance(),
+            STARTING_BALANCE + DAY_PASS_PRICE * 3
+        );
+    }
+
+    #[test]
+    fn test_ride_tickets() {
+        // given
+        let mut service = Service::new();
+        service.set_ticket(Ticket::PerRide);
+
+        // when
+        service.sell_ticket(&1);
+        service.sell_ticket(&1);
+
+        // then
+        assert_eq!(
+            service.ledger().today().total(&Account::Tickets),
+            RIDE_PRICE * 2
+        );
+    }
+
+    #[test]
+    fn test_riders_are_counted_once_per_day() {
+        // given
+        let mut service = Service::new();
+        service.set_ticket(Ticket::PerRide);
+
+        // when
+        service.sell_ticket(&1);
+        service.sell_ticket(&1);
+        service.sell_ticket(&2);
+        let today = service.riders_today();
+        service.update(&MICROS_PER_DAY);
+
+        // then
+        assert_eq!(today, 2);
+        assert_eq!(service.riders_today(), 0);
+    }
+
+    #[test]
+    fn test_bankrupt_when_day_closes_in_debt() {
+        // given
+        let mut service = Service::new();
+        service.record(Account::Lifts, -STARTING_BALANCE - 1);
+
+        // when
+        let closed_early = service.update(&(MICROS_PER_DAY - 1));
+        let bankrupt_early = service.is_bankrupt();
+        let closed = service.update(&MICROS_PER_DAY);
+
+        // then
+        assert!(!closed_early);
+        assert!(!bankrupt_early);
+        assert!(closed);
+        assert!(service.is_bankrupt());
+    }
+}
@@ -1,3 +1,5 @@
 pub mod clock;
+pub mod economy;
 pub mod id_allocator;
 pub mod mode;
+pub mod weather;
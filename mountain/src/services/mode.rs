@@ -15,6 +15,7 @@ pub enum Mode {
     Gate,
     Building,
     Door,
+    Snowmaking,
     Demolish,
 }
 
@@ -110,6 +111,8 @@ fn try_to_handle(
                 lifts: &game.components.lifts,
                 gates: &game.components.gates,
                 pistes: &game.components.pistes,
+                cannons: &game.components.cannons,
+                water_sources: &game.components.water_sources,
                 buildings: &game.components.buildings,
                 windows: &mut game.widgets.windows,
                 graphics,
@@ -119,6 +122,8 @@ fn try_to_handle(
             .controllers
             .piste_builder
             .trigger(controllers::piste_builder::Parameters {
+                terrain: &game.components.terrain,
+                trees: &game.components.trees,
                 pistes: &mut game.components.pistes,
                 piste_map: &mut game.components.piste_map,
                 open: &mut game.components.open,
@@ -126,12 +131,16 @@ fn try_to_handle(
                 terrain_artist: &mut game.systems.terrain_artist,
                 tree_artist: &mut game.systems.tree_artist,
                 id_allocator: &mut game.components.services.id_allocator,
+                constructions: &mut game.components.constructions,
+                economy: &mut game.components.services.economy,
                 messenger: &mut game.systems.messenger,
             })
             .then_try(|| {
                 game.controllers
                     .piste_eraser
                     .trigger(controllers::piste_eraser::Parameters {
+                        terrain: &game.components.terrain,
+                        trees: &game.components.trees,
                         open: &game.components.open,
                         lifts: &game.components.lifts,
                         gates: &game.components.gates,
@@ -141,6 +150,7 @@ fn try_to_handle(
                         selection: &mut game.components.selection,
                         terrain_artist: &mut game.systems.terrain_artist,
                         tree_artist: &mut game.systems.tree_artist,
+                        economy: &mut game.components.services.economy,
                         messenger: &mut game.systems.messenger,
                     })
             }),
@@ -148,6 +158,8 @@ fn try_to_handle(
             .controllers
             .path_builder
             .trigger(controllers::piste_builder::Parameters {
+                terrain: &game.components.terrain,
+                trees: &game.components.trees,
                 pistes: &mut game.components.pistes,
                 piste_map: &mut game.components.piste_map,
                 open: &mut game.components.open,
@@ -155,12 +167,16 @@ fn try_to_handle(
                 terrain_artist: &mut game.systems.terrain_artist,
                 tree_artist: &mut game.systems.tree_artist,
                 id_allocator: &mut game.components.services.id_allocator,
+                constructions: &mut game.components.constructions,
+                economy: &mut game.components.services.economy,
                 messenger: &mut game.systems.messenger,
             })
             .then_try(|| {
                 game.controllers
                     .piste_eraser
                     .trigger(controllers::piste_eraser::Parameters {
+                        terrain: &game.components.terrain,
+                        trees: &game.components.trees,
                         open: &game.components.open,
                         lifts: &game.components.lifts,
                         gates: &game.components.gates,
@@ -170,6 +186,7 @@ fn try_to_handle(
                         selection: &mut game.components.selection,
                         terrain_artist: &mut game.systems.terrain_artist,
                         tree_artist: &mut game.systems.tree_artist,
+                        economy: &mut game.components.services.economy,
                         messenger: &mut game.systems.messenger,
                     })
             }),
@@ -186,12 +203,18 @@ fn try_to_handle(
                     id_allocator: &mut game.components.services.id_allocator,
                     carousels: &mut game.components.carousels,
                     cars: &mut game.components.cars,
+                    frames: &mut game.components.frames,
+                    lift_queues: &mut game.components.lift_queues,
+                    reliabilities: &mut game.components.reliabilities,
                     exits: &mut game.components.exits,
                     entrances: &mut game.components.entrances,
                     reservations: &mut game.components.reservations,
                     parents: &mut game.components.parents,
                     children: &mut game.components.children,
+                    drawings: &mut game.components.drawings,
                     piste_computer: &mut game.systems.piste_computer,
+                    constructions: &mut game.components.constructions,
+                    economy: &mut game.components.services.economy,
                     messenger: &mut game.systems.messenger,
                     lift_building_artist: &mut game.systems.lift_building_artist,
                     graphics,
@@ -216,6 +239,8 @@ fn try_to_handle(
             open: &mut game.components.open,
             reservations: &mut game.components.reservations,
             piste_computer: &mut game.systems.piste_computer,
+            constructions: &mut game.components.constructions,
+            economy: &mut game.components.services.economy,
             messenger: &mut game.systems.messenger,
         }),
         Mode::Door => controllers::door_builder::trigger(controllers::door_builder::Parameters {
@@ -230,8 +255,22 @@ fn try_to_handle(
             parents: &mut game.components.parents,
             children: &mut game.components.children,
             piste_computer: &mut game.systems.piste_computer,
+            constructions: &mut game.components.constructions,
+            economy: &mut game.components.services.economy,
             messenger: &mut game.systems.messenger,
         }),
+        Mode::Snowmaking => {
+            controllers::snowmaking_builder::trigger(controllers::snowmaking_builder::Parameters {
+                mouse_xy: &game.mouse_xy,
+                rain: &game.components.rain,
+                piste_map: &game.components.piste_map,
+                id_allocator: &mut game.components.services.id_allocator,
+                water_sources: &mut game.components.water_sources,
+                cannons: &mut game.components.cannons,
+                messenger: &mut game.systems.messenger,
+                graphics,
+            })
+        }
         Mode::Demolish => try_to_demolish(game, graphics),
         _ => NoAction,
     }
@@ -262,4 +301,12 @@ fn try_to_demolish(game: &mut Game, graphics: &mut dyn engine::graphics::Graphic
             graphics,
         )
     })
+    .then_try(|| {
+        controllers::snowmaking_remover::trigger(
+            &game.mouse_xy,
+            &mut game.components,
+            &mut game.systems.messenger,
+            graphics,
+        )
+    })
 }
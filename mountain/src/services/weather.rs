@@ -0,0 +1,140 @@
+use std::collections::VecDeque;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::model::weather::Weather;
+
+pub const PERIOD_MICROS: u128 = 6 * 60 * 60 * 1_000_000;
+const FORECAST_PERIODS: usize = 4;
+
+#[derive(Serialize, Deserialize)]
+pub struct Service {
+    seed: u64,
+    period: u128,
+    /// Weather for the current period followed by the periods after it
+    forecast: VecDeque<Weather>,
+}
+
+impl Service {
+    pub fn new(seed: u64) -> Service {
+        let mut service = Service {
+            seed,
+            period: 0,
+            forecast: VecDeque::from([Weather::default()]),
+        };
+        service.extend_forecast();
+        service
+    }
+
+    pub fn current(&self) -> &Weather {
+        &self.forecast[0]
+    }
+
+    pub fn forecast(&self) -> impl Iterator<Item = &Weather> {
+        self.forecast.iter().skip(1)
+    }
+
+    /// Average appeal of the forecast weather, which decides how keen people are to book a stay
+    pub fn outlook(&self) -> f32 {
+        let (total, count) = self.forecast().fold((0.0, 0), |(total, count), weather| {
+            (total + weather.appeal(), count + 1)
+        });
+        if count == 0 {
+            return self.current().appeal();
+        }
+        total / count as f32
+    }
+
+    /// Moves the forecast on to the period containing `micros`, returning whether it moved
+    pub fn update(&mut self, micros: &u128) -> bool {
+        let period = micros / PERIOD_MICROS;
+        if period <= self.period {
+            return false;
+        }
+        while self.period < period {
+            self.period += 1;
+            self.forecast.pop_front();
+            self.extend_forecast();
+        }
+        true
+    }
+
+    fn extend_forecast(&mut self) {
+        while self.forecast.len() < FORECAST_PERIODS {
+            let period = self.period + self.forecast.len() as u128;
+            // each period has its own generator so the forecast only depends on the seed
+            let mut rng = StdRng::seed_from_u64(
+                self.seed ^ (period as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15),
+            );
+            let last = self.forecast.back().copied().unwrap_or_default();
+            self.forecast.push_back(last.next(&mut rng));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_forecast_depends_only_on_seed() {
+        // given
+        let mut a = Service::new(1);
+        let mut b = Service::new(1);
+
+        // when
+        a.update(&(PERIOD_MICROS * 3));
+        b.update(&PERIOD_MICROS);
+        b.update(&(PERIOD_MICROS * 3));
+
+        // then
+        assert_eq!(a.current(), b.current());
+        assert_eq!(
+            a.forecast().collect::<Vec<_>>(),
+            b.forecast().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_forecast_moves_on() {
+        // given
+        let mut service = Service::new(1);
+        let next = *service.forecast().next().unwrap();
+
+        // when
+        let moved = service.update(&PERIOD_MICROS);
+
+        // then
+        assert!(moved);
+        assert_eq!(*service.current(), next);
+        assert_eq!(service.forecast().count(), FORECAST_PERIODS - 1);
+    }
+
+    #[test]
+    fn test_outlook_is_average_forecast_appeal() {
+        // given
+        let service = Service::new(1);
+
+        // when
+        let outlook = service.outlook();
+
+        // then
+        let appeals = service
+            .forecast()
+            .map(|weather| weather.appeal())
+            .collect::<Vec<_>>();
+        let expected = appeals.iter().sum::<f32>() / appeals.len() as f32;
+        assert_eq!(outlook, expected);
+    }
+
+    #[test]
+    fn test_same_period() {
+        // given
+        let mut service = Service::new(1);
+
+        // then
+        assert!(!service.update(&(PERIOD_MICROS - 1)));
+    }
+}
@@ -5,12 +5,20 @@ use commons::grid::Grid;
 use commons::map::ContainsKeyValue;
 
 use crate::model::carousel::{Car, Carousel};
+use crate::model::costs::Costs;
 use crate::model::entrance::Entrance;
-use crate::model::lift::Lift;
+use crate::model::group::Group;
+use crate::model::lift::{Lift, Portal};
+use crate::model::lift_queue::LiftQueue;
 use crate::model::open;
+use crate::model::reliability::Reliability;
 use crate::model::reservation::{Reservation, ReservationPeriod};
+use crate::model::satisfaction::{Satisfaction, Thought};
+use crate::model::skier::Skier;
 use crate::model::skiing::Plan;
+use crate::services::economy;
 use crate::utils::carousel::{revolve, RevolveAction, RevolveEvent, RevolveResult};
+use crate::utils::group;
 
 pub struct System {
     last_micros: Option<u128>,
@@ -18,6 +26,8 @@ pub struct System {
 
 pub struct Parameters<'a> {
     pub micros: &'a u128,
+    pub skiers: &'a HashMap<usize, Skier>,
+    pub groups: &'a HashMap<usize, Group>,
     pub lifts: &'a HashMap<usize, Lift>,
     pub open: &'a HashMap<usize, open::Status>,
     pub carousels: &'a HashMap<usize, Carousel>,
@@ -28,6 +38,11 @@ pub struct Parameters<'a> {
     pub targets: &'a mut HashMap<usize, usize>,
     pub global_targets: &'a mut HashMap<usize, usize>,
     pub cars: &'a mut HashMap<usize, Car>,
+    pub lift_queues: &'a mut HashMap<usize, LiftQueue>,
+    pub global_costs: &'a Costs<usize>,
+    pub reliabilities: &'a HashMap<usize, Reliability>,
+    pub satisfaction: &'a mut HashMap<usize, Satisfaction>,
+    pub economy: &'a mut economy::Service,
 }
 
 impl System {
@@ -39,6 +54,8 @@ impl System {
         &mut self,
         Parameters {
             micros,
+            skiers,
+            groups,
             lifts,
             open,
             carousels,
@@ -49,6 +66,11 @@ impl System {
             targets,
             global_targets,
             cars,
+            lift_queues,
+            global_costs,
+            reliabilities,
+            satisfaction,
+            economy,
         }: Parameters<'_>,
     ) {
         let Some(last_micros) = self.last_micros else {
@@ -64,13 +86,13 @@ impl System {
                 continue;
             };
 
-            let Some(Entrance {
-                destination_piste_id,
-                ..
-            }) = entrances.get(&lift.drop_off.id)
-            else {
+            if reliabilities
+                .get(&carousel.lift_id)
+                .map(Reliability::is_stalled)
+                .unwrap_or_default()
+            {
                 continue;
-            };
+            }
 
             // get cars
 
@@ -92,18 +114,32 @@ impl System {
 
             // check for blocked drop off
 
-            let occupied_locations = locations.values().collect::<HashSet<_>>();
+            let mut riders: HashMap<usize, Vec<usize>> = HashMap::new();
+            for (skier_id, location) in locations.iter() {
+                riders.entry(*location).or_default().push(*skier_id);
+            }
 
-            if reservations[lift.drop_off.state.position]
-                .values()
-                .any(|reservation| reservation.includes(micros))
-            {
-                if let Some(first_drop_off) = revolve_result.events.iter().find(|event| {
-                    let car_id = car_ids[event.car_index];
-                    event.action == RevolveAction::DropOff && occupied_locations.contains(car_id)
-                }) {
-                    revolve_result = revolve(lift, &current_cars, first_drop_off.revolve_meters);
-                }
+            if let Some(first_blocked_drop_off) = revolve_result.events.iter().find(|event| {
+                let RevolveAction::DropOff(drop_off_id) = event.action else {
+                    return false;
+                };
+                let Some(drop_off) = lift.drop_offs().find(|portal| portal.id == drop_off_id)
+                else {
+                    return false;
+                };
+                let car_id = car_ids[event.car_index];
+                reservations[drop_off.state.position]
+                    .values()
+                    .any(|reservation| reservation.includes(micros))
+                    && riders
+                        .get(car_id)
+                        .iter()
+                        .copied()
+                        .flatten()
+                        .any(|skier_id| alights(skier_id, lift, drop_off, entrances, targets))
+            }) {
+                revolve_result =
+                    revolve(lift, &current_cars, first_blocked_drop_off.revolve_meters);
             }
 
             // process events
@@ -119,40 +155,87 @@ impl System {
             {
                 let car_id = car_ids[car_index];
                 match action {
-                    RevolveAction::PickUp => {
-                        if !open.contains_key_value(lift.pick_up.id, open::Status::Open) {
+                    RevolveAction::PickUp(pick_up_id) => {
+                        if !open.contains_key_value(pick_up_id, open::Status::Open) {
                             continue;
                         }
-                        plans.retain(|skier_id, plan| {
-                            if !matches!(targets.get(skier_id), Some(&target) if target == lift.pick_up.id) {
-                                return true;
+                        let Some(pick_up) = lift.pick_ups().find(|portal| portal.id == pick_up_id)
+                        else {
+                            continue;
+                        };
+                        let Some(queue) = lift_queues.get_mut(&pick_up_id) else {
+                            continue;
+                        };
+                        let seats = carousel
+                            .car_class
+                            .capacity()
+                            .saturating_sub(riders.get(car_id).map(Vec::len).unwrap_or_default());
+                        let is_riding = |skier_id: &usize| {
+                            locations
+                                .get(skier_id)
+                                .is_some_and(|location| carousel.car_ids.contains(location))
+                        };
+                        let boarding =
+                            boarding_skiers(seats, queue, skiers, groups, plans, is_riding);
+                        for (skier_id, wait_micros) in queue.remove(&boarding, micros) {
+                            if let Some(thought) = Thought::about_wait(&wait_micros) {
+                                satisfaction
+                                    .entry(skier_id)
+                                    .or_default()
+                                    .think(thought, micros);
                             }
-                            if !matches!(plan, Plan::Stationary(state) if *state == lift.pick_up.state) {
-                                return true;
+                        }
+                        for skier_id in boarding {
+                            economy.sell_ticket(&skier_id);
+                            let drop_off = choose_drop_off(
+                                &skier_id,
+                                lift,
+                                pick_up,
+                                DropOffParameters {
+                                    skiers,
+                                    groups,
+                                    open,
+                                    global_costs,
+                                    global_targets,
+                                },
+                            );
+                            if let Some(Entrance {
+                                destination_piste_id,
+                                ..
+                            }) = entrances.get(&drop_off.id)
+                            {
+                                targets.insert(skier_id, *destination_piste_id);
                             }
-                            targets.insert(*skier_id, *destination_piste_id);
-                            if let Entry::Occupied(entry) = global_targets.entry(*skier_id) {
-                                if *entry.get() == lift.pick_up.id {
+                            if let Entry::Occupied(entry) = global_targets.entry(skier_id) {
+                                if *entry.get() == pick_up_id {
                                     entry.remove();
                                 }
                             }
-                            locations.insert(*skier_id, *car_id);
-                            reservations[lift.pick_up.state.position].remove(skier_id);
-                            false
-                        });
+                            locations.insert(skier_id, *car_id);
+                            riders.entry(*car_id).or_default().push(skier_id);
+                        }
                     }
-                    RevolveAction::DropOff => {
-                        locations.retain(|skier_id, location| {
-                            if *location != *car_id {
+                    RevolveAction::DropOff(drop_off_id) => {
+                        let Some(drop_off) =
+                            lift.drop_offs().find(|portal| portal.id == drop_off_id)
+                        else {
+                            continue;
+                        };
+                        let Some(car_riders) = riders.get_mut(car_id) else {
+                            continue;
+                        };
+                        car_riders.retain(|skier_id| {
+                            if !alights(skier_id, lift, drop_off, entrances, targets) {
                                 return true;
                             }
+                            locations.remove(skier_id);
                             if let Entry::Occupied(entry) = global_targets.entry(*skier_id) {
-                                if *entry.get() == lift.drop_off.id {
+                                if *entry.get() == drop_off_id {
                                     entry.remove();
                                 }
                             }
-                            plans.insert(*skier_id, Plan::Stationary(lift.drop_off.state));
-                            reservations[lift.drop_off.state.position].insert(
+                            plans.insert(*skier_id, Plan::Stationary(drop_off.state));
+                            reservations[drop_off.state.position].insert(
                                 *skier_id,
                                 Reservation::Mobile(ReservationPeriod::Permanent),
                             );
@@ -170,3 +253,230 @@ impl System {
         }
     }
 }
+
+fn alights(
+    skier_id: &usize,
+    lift: &Lift,
+    drop_off: &Portal,
+    entrances: &HashMap<usize, Entrance>,
+    targets: &HashMap<usize, usize>,
+) -> bool {
+    // everyone gets off at the final drop off, otherwise only skiers heading for the piste
+    drop_off.id == lift.drop_off.id
+        || entrances
+            .get(&drop_off.id)
+            .map(|entrance| targets.get(skier_id) == Some(&entrance.destination_piste_id))
+            .unwrap_or_default()
+}
+
+struct DropOffParameters<'a> {
+    skiers: &'a HashMap<usize, Skier>,
+    groups: &'a HashMap<usize, Group>,
+    open: &'a HashMap<usize, open::Status>,
+    global_costs: &'a Costs<usize>,
+    global_targets: &'a HashMap<usize, usize>,
+}
+
+fn choose_drop_off<'a>(
+    skier_id: &usize,
+    lift: &'a Lift,
+    pick_up: &'a Portal,
+    DropOffParameters {
+        skiers,
+        groups,
+        open,
+        global_costs,
+        global_targets,
+    }: DropOffParameters<'_>,
+) -> &'a Portal {
+    // drop off closest to global target, or the final drop off if skier has nowhere in mind
+
+    let Some(global_target) = global_targets.get(skier_id) else {
+        return &lift.drop_off;
+    };
+    let Some(ability) = group::ability(skier_id, skiers, groups) else {
+        return &lift.drop_off;
+    };
+    let Some(costs_to_global_target) = global_costs.costs(*global_target, ability) else {
+        return &lift.drop_off;
+    };
+
+    lift.drop_offs_after(pick_up)
+        .filter(|drop_off| open.contains_key_value(drop_off.id, open::Status::Open))
+        .flat_map(|drop_off| {
+            if drop_off.id == *global_target {
+                return Some((drop_off, 0));
+            }
+            costs_to_global_target
+                .get(&drop_off.id)
+                .map(|cost| (drop_off, *cost))
+        })
+        .min_by_key(|(_, cost)| *cost)
+        .map(|(drop_off, _)| drop_off)
+        .unwrap_or(&lift.drop_off)
+}
+
+fn boarding_skiers(
+    seats: usize,
+    queue: &LiftQueue,
+    skiers: &HashMap<usize, Skier>,
+    groups: &HashMap<usize, Group>,
+    plans: &HashMap<usize, Plan>,
+    is_riding: impl Fn(&usize) -> bool,
+) -> Vec<usize> {
+    // groups whose members are all queued board in queue order while they fit in the car
+    // a group too big for the car fills an empty one and the rest of the group takes the next,
+    // counting the members already riding as regrouped
+
+    let mut boarding: Vec<usize> = Vec::with_capacity(seats);
+    let mut considered = HashSet::new();
+
+    for queued in queue.skiers.iter() {
+        if boarding.len() == seats {
+            break;
+        }
+        if boarding.contains(&queued.skier_id) || !considered.insert(queued.skier_id) {
+            continue;
+        }
+        if !group::is_regrouped(
+            &queued.skier_id,
+            skiers,
+            groups,
+            plans,
+            |member_id| {
+                is_riding(member_id)
+                    || queue
+                        .skiers
+                        .iter()
+                        .any(|queued| queued.skier_id == *member_id)
+            },
+            |_| false,
+        ) {
+            continue;
+        }
+
+        let members = group::members(&queued.skier_id, skiers, groups);
+        considered.extend(members.iter().copied());
+        let queued_members = queue
+            .skiers
+            .iter()
+            .map(|queued| queued.skier_id)
+            .filter(|skier_id| members.contains(skier_id))
+            .collect::<Vec<_>>();
+
+        let free_seats = seats - boarding.len();
+        if queued_members.len() <= free_seats {
+            boarding.extend(queued_members);
+        } else if boarding.is_empty() {
+            boarding.extend(queued_members.into_iter().take(seats));
+        }
+    }
+
+    boarding
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::ability::Ability;
+    use crate::model::lift_queue::{Maze, QueuedSkier};
+    use crate::model::skier::{Clothes, Equipment};
+
+    use super::*;
+
+    fn skier(group_id: usize) -> Skier {
+        Skier {
+            clothes: Clothes::default(),
+            ability: Ability::Intermediate,
+            equipment: Equipment::Skis,
+            hotel_id: 0,
+            group_id,
+        }
+    }
+
+    fn queue(skier_ids: &[usize]) -> LiftQueue {
+        let mut queue = LiftQueue::new(Maze {
+            lanes: 1,
+            lane_length: 8,
+        });
+        queue.skiers = skier_ids
+            .iter()
+            .map(|&skier_id| QueuedSkier {
+                skier_id,
+                joined_micros: 0,
+            })
+            .collect();
+        queue
+    }
+
+    #[test]
+    fn test_group_that_fits_boards_together() {
+        // given
+        let skiers = HashMap::from([(1, skier(0)), (2, skier(0)), (3, skier(1))]);
+        let groups = HashMap::from([(
+            0,
+            Group {
+                member_ids: vec![1, 2],
+            },
+        )]);
+        let queue = queue(&[1, 2, 3]);
+
+        // when
+        let result = boarding_skiers(4, &queue, &skiers, &groups, &HashMap::new(), |_| false);
+
+        // then
+        assert_eq!(result, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_group_larger_than_car_boards_over_two_cars() {
+        // given
+        let skiers = HashMap::from([(1, skier(0)), (2, skier(0)), (3, skier(0))]);
+        let groups = HashMap::from([(
+            0,
+            Group {
+                member_ids: vec![1, 2, 3],
+            },
+        )]);
+        let mut queue = queue(&[1, 2, 3]);
+
+        // when
+        let first_car = boarding_skiers(2, &queue, &skiers, &groups, &HashMap::new(), |_| false);
+
+        // then
+        assert_eq!(first_car, vec![1, 2]);
+
+        // when
+        queue.remove(&first_car, &0);
+        let second_car = boarding_skiers(2, &queue, &skiers, &groups, &HashMap::new(), |id| {
+            first_car.contains(id)
+        });
+
+        // then
+        assert_eq!(second_car, vec![3]);
+    }
+
+    #[test]
+    fn test_group_that_does_not_fit_remaining_seats_waits() {
+        // given
+        let skiers = HashMap::from([
+            (1, skier(1)),
+            (2, skier(0)),
+            (3, skier(0)),
+            (4, skier(0)),
+            (5, skier(2)),
+        ]);
+        let groups = HashMap::from([(
+            0,
+            Group {
+                member_ids: vec![2, 3, 4],
+            },
+        )]);
+        let queue = queue(&[1, 2, 3, 4, 5]);
+
+        // when
+        let result = boarding_skiers(3, &queue, &skiers, &groups, &HashMap::new(), |_| false);
+
+        // then
+        assert_eq!(result, vec![1, 5]);
+    }
+}
@@ -1,11 +1,13 @@
 use std::collections::HashMap;
 
 use commons::color::Rgb;
+use commons::geometry::{xy, xyz, XYZ};
+use commons::grid::Grid;
 
 use crate::model::carousel::{Car, Carousel};
 use crate::model::frame::{Frame, Model};
 use crate::model::lift::Lift;
-use crate::model::skier::Clothes;
+use crate::model::skier::{Clothes, Equipment, Skier};
 
 const MISSING_COLOR: Rgb<f32> = Rgb::new(1.0, 1.0, 0.0);
 lazy_static! {
@@ -17,18 +19,33 @@ lazy_static! {
     };
 }
 
+pub struct Parameters<'a> {
+    pub terrain: &'a Grid<f32>,
+    pub carousels: &'a HashMap<usize, Carousel>,
+    pub lifts: &'a HashMap<usize, Lift>,
+    pub cars: &'a HashMap<usize, Car>,
+    pub locations: &'a HashMap<usize, usize>,
+    pub skiers: &'a HashMap<usize, Skier>,
+    pub clothes: &'a HashMap<usize, Clothes<Rgb<f32>>>,
+    pub frames: &'a mut HashMap<usize, Option<Frame>>,
+}
+
 pub fn run(
-    carousels: &HashMap<usize, Carousel>,
-    lifts: &HashMap<usize, Lift>,
-    cars: &HashMap<usize, Car>,
-    locations: &HashMap<usize, usize>,
-    clothes: &HashMap<usize, Clothes<Rgb<f32>>>,
-    frames: &mut HashMap<usize, Option<Frame>>,
+    Parameters {
+        terrain,
+        carousels,
+        lifts,
+        cars,
+        locations,
+        skiers,
+        clothes,
+        frames,
+    }: Parameters<'_>,
 ) {
-    let location_reverse_map = locations
-        .iter()
-        .map(|(k, v)| (*v, *k))
-        .collect::<HashMap<_, _>>();
+    let mut location_reverse_map: HashMap<usize, Vec<usize>> = HashMap::new();
+    for (id, location) in locations {
+        location_reverse_map.entry(*location).or_default().push(*id);
+    }
 
     for carousel in carousels.values() {
         let Some(lift) = lifts.get(&carousel.lift_id) else {
@@ -54,24 +71,51 @@ pub fn run(
                     position,
                     yaw,
                     pitch: 0.0,
-                    model: Model::Chair,
+                    model: Model::Car(carousel.car_class),
                 }),
             );
 
-            // skier riding in chair
-            let Some(id) = location_reverse_map.get(car_id) else {
+            // skiers riding in car, one per seat, either sitting or standing on the ground
+            let Some(ids) = location_reverse_map.get(car_id) else {
                 continue;
             };
-            let clothes = clothes.get(id).unwrap_or(&MISSING_CLOTHES);
-            frames.insert(
-                *id,
-                Some(Frame {
-                    position,
-                    yaw,
-                    pitch: 0.0,
-                    model: Model::Sitting { clothes: *clothes },
-                }),
-            );
+            let (sin, cos) = yaw.sin_cos();
+            for (seat, id) in carousel.car_class.seats().iter().zip(ids) {
+                let clothes = clothes.get(id).unwrap_or(&MISSING_CLOTHES);
+                let equipment = skiers
+                    .get(id)
+                    .map(|skier| skier.equipment)
+                    .unwrap_or(Equipment::Skis);
+                let offset = xyz(
+                    seat.offset.x * cos - seat.offset.y * sin,
+                    seat.offset.x * sin + seat.offset.y * cos,
+                    0.0,
+                );
+                let frame = if lift.lift_type.is_seated() {
+                    Frame {
+                        position: position + offset,
+                        yaw: yaw + seat.yaw,
+                        pitch: 0.0,
+                        model: Model::Sitting { clothes: *clothes },
+                    }
+                } else {
+                    Frame {
+                        position: on_ground(terrain, position + offset),
+                        yaw: yaw + seat.yaw,
+                        pitch: 0.0,
+                        model: Model::on_snow(&equipment, *clothes),
+                    }
+                };
+                frames.insert(*id, Some(frame));
+            }
         }
     }
 }
+
+fn on_ground(terrain: &Grid<f32>, position: XYZ<f32>) -> XYZ<f32> {
+    let cell = xy(position.x.round() as u32, position.y.round() as u32);
+    if !terrain.in_bounds(cell) {
+        return position;
+    }
+    xyz(position.x, position.y, terrain[cell])
+}
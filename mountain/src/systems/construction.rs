@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use commons::geometry::{xy, XYRectangle};
+use commons::grid::Grid;
+
+use crate::controllers::building_builder::{self, CompleteParameters};
+use crate::model::building::Building;
+use crate::model::construction::{self, Kind};
+use crate::model::entrance::Entrance;
+use crate::model::exit::Exit;
+use crate::model::group::Group;
+use crate::model::lift::Lift;
+use crate::model::open;
+use crate::model::piste::Piste;
+use crate::model::satisfaction::Rating;
+use crate::model::skier::Skier;
+use crate::services::id_allocator;
+use crate::systems::{
+    building_artist, lift_building_artist, messenger, piste_computer, terrain_artist, window_artist,
+};
+
+pub struct System {
+    last_micros: Option<u128>,
+}
+
+pub struct Parameters<'a> {
+    pub micros: &'a u128,
+    pub terrain: &'a Grid<f32>,
+    pub pistes: &'a HashMap<usize, Piste>,
+    pub lifts: &'a HashMap<usize, Lift>,
+    pub entrances: &'a HashMap<usize, Entrance>,
+    pub exits: &'a HashMap<usize, Exit>,
+    pub constructions: &'a mut construction::Queue,
+    pub open: &'a mut HashMap<usize, open::Status>,
+    pub id_allocator: &'a mut id_allocator::Service,
+    pub buildings: &'a mut HashMap<usize, Building>,
+    pub locations: &'a mut HashMap<usize, usize>,
+    pub skiers: &'a mut HashMap<usize, Skier>,
+    pub groups: &'a mut HashMap<usize, Group>,
+    pub rating: &'a Rating,
+    pub piste_computer: &'a mut piste_computer::System,
+    pub terrain_artist: &'a mut terrain_artist::System,
+    pub building_artist: &'a mut building_artist::System,
+    pub lift_building_artist: &'a mut lift_building_artist::System,
+    pub window_artist: &'a mut window_artist::System,
+    pub messenger: &'a mut messenger::System,
+}
+
+impl System {
+    pub fn new() -> System {
+        System { last_micros: None }
+    }
+
+    pub fn run(
+        &mut self,
+        Parameters {
+            micros,
+            terrain,
+            pistes,
+            lifts,
+            entrances,
+            exits,
+            constructions,
+            open,
+            id_allocator,
+            buildings,
+            locations,
+            skiers,
+            groups,
+            rating,
+            piste_computer,
+            terrain_artist,
+            building_artist,
+            lift_building_artist,
+            window_artist,
+            messenger,
+        }: Parameters<'_>,
+    ) {
+        let last_micros = *self.last_micros.get_or_insert(*micros);
+        self.last_micros = Some(*micros);
+        let elapsed_micros = micros.saturating_sub(last_micros);
+        if elapsed_micros == 0 || constructions.is_empty() {
+            return;
+        }
+
+        for construction::Construction { id, kind, .. } in constructions.work(elapsed_micros) {
+            match kind {
+                Kind::Piste | Kind::Path => {
+                    if let Some(piste) = pistes.get(&id) {
+                        let grid = &piste.grid;
+                        terrain_artist.update_overlay(XYRectangle {
+                            from: *grid.origin(),
+                            to: *grid.origin() + xy(grid.width() - 2, grid.height() - 2),
+                        });
+                    }
+                }
+                Kind::Lift => {
+                    if let Some(lift) = lifts.get(&id) {
+                        lift_building_artist.redraw(lift.buildings_id);
+                    }
+                }
+                Kind::Gate | Kind::Door => {
+                    // gates and doors open as soon as they are built
+                    if open.contains_key(&id) {
+                        open.insert(id, open::Status::Open);
+                    }
+                    if let Some(entrance) = entrances.get(&id) {
+                        piste_computer.compute(entrance.destination_piste_id);
+                    }
+                    if let Some(exit) = exits.get(&id) {
+                        piste_computer.compute(exit.origin_piste_id);
+                    }
+                }
+                Kind::Building => building_builder::complete(
+                    &id,
+                    CompleteParameters {
+                        terrain,
+                        id_allocator,
+                        buildings,
+                        locations,
+                        skiers,
+                        groups,
+                        rating,
+                        building_artist,
+                        window_artist,
+                        messenger,
+                    },
+                ),
+            }
+            messenger.send(format!("{} {} is complete", kind.name(), id));
+        }
+    }
+}
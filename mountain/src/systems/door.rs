@@ -1,53 +1,103 @@
 use std::collections::HashMap;
 
 use commons::grid::Grid;
+use commons::map::ContainsKeyValue;
 
 use crate::model::door::Door;
+use crate::model::open;
 use crate::model::reservation::{Reservation, ReservationPeriod};
+use crate::model::skier::Skier;
 use crate::model::skiing::{Plan, State};
+use crate::model::weather::Weather;
 
 pub struct Parameters<'a> {
+    pub micros: &'a u128,
     pub doors: &'a HashMap<usize, Door>,
+    pub open: &'a HashMap<usize, open::Status>,
+    pub skiers: &'a HashMap<usize, Skier>,
+    pub weather: &'a Weather,
     pub locations: &'a mut HashMap<usize, usize>,
     pub reservations: &'a mut Grid<HashMap<usize, Reservation>>,
     pub plans: &'a mut HashMap<usize, Plan>,
+    pub visits: &'a mut HashMap<usize, u128>,
 }
 
 pub fn run(
     Parameters {
+        micros,
         doors,
+        open,
+        skiers,
+        weather,
         locations,
         reservations,
         plans,
+        visits,
     }: Parameters<'_>,
 ) {
-    for door in doors.values() {
-        let mut skiers_to_spawn = locations
+    if weather.keeps_skiers_indoors() {
+        return;
+    }
+
+    // doors still under construction are closed
+    for (_, door) in doors
+        .iter()
+        .filter(|&(door_id, _)| open.contains_key_value(door_id, open::Status::Open))
+    {
+        let skiers_in_building = locations
             .iter()
             .filter(|&(_, location_id)| *location_id == door.building_id)
+            // skiers visiting a service building stay until they are done
+            .filter(|&(skier_id, _)| {
+                visits
+                    .get(skier_id)
+                    .is_none_or(|until_micros| until_micros <= micros)
+            })
+            .flat_map(|(skier_id, _)| skiers.get(skier_id).map(|skier| (*skier_id, skier)))
+            .collect::<Vec<_>>();
+
+        let Some((_, Skier { group_id, .. })) = skiers_in_building.first() else {
+            continue;
+        };
+
+        // groups leave the building together
+
+        let mut skiers_to_spawn = skiers_in_building
+            .iter()
+            .filter(|(_, skier)| skier.group_id == *group_id)
             .map(|(skier_id, _)| *skier_id)
             .collect::<Vec<_>>();
 
-        if skiers_to_spawn.is_empty() {
+        let free_positions = door
+            .aperture
+            .iter()
+            .filter(|&position| reservations[position].is_empty())
+            .collect::<Vec<_>>();
+
+        // unless the group is too big to ever fit in the aperture
+        if skiers_to_spawn.len() > free_positions.len()
+            && free_positions.len() < door.aperture.len()
+        {
             continue;
         }
 
-        for position in &door.aperture {
-            if !skiers_to_spawn.is_empty() && reservations[position].is_empty() {
-                let skier_id = skiers_to_spawn.pop().unwrap();
-
-                locations.remove(&skier_id);
-                reservations[position]
-                    .insert(skier_id, Reservation::Mobile(ReservationPeriod::Permanent));
-                plans.insert(
-                    skier_id,
-                    Plan::Stationary(State {
-                        position: *position,
-                        velocity: 0,
-                        travel_direction: door.direction,
-                    }),
-                );
-            }
+        for position in free_positions {
+            let Some(skier_id) = skiers_to_spawn.pop() else {
+                break;
+            };
+
+            locations.remove(&skier_id);
+            visits.remove(&skier_id);
+            reservations[position]
+                .insert(skier_id, Reservation::Mobile(ReservationPeriod::Permanent));
+            plans.insert(
+                skier_id,
+                Plan::Stationary(State {
+                    position: *position,
+                    velocity: 0,
+                    travel_direction: door.direction,
+                }),
+            );
         }
     }
 }
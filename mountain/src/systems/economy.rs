@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use commons::map::ContainsKeyValue;
+
+use crate::model::building::Building;
+use crate::model::ledger::Account;
+use crate::model::lift::Lift;
+use crate::model::open;
+use crate::model::piste::{self, Piste};
+use crate::model::skier::Skier;
+use crate::services::economy;
+use crate::systems::messenger;
+
+const UPDATE_INTERVAL_MICROS: u128 = 10 * 60 * 1_000_000;
+const MICROS_PER_HOUR: f32 = 3_600_000_000.0;
+
+pub const ROOM_PRICE_PER_NIGHT: f32 = 120.0;
+pub const LIFT_COST_PER_HOUR: f32 = 150.0;
+pub const PISTE_CELL_COST_PER_HOUR: f32 = 0.002;
+
+pub struct System {
+    last_update_micros: Option<u128>,
+}
+
+pub struct Parameters<'a> {
+    pub micros: &'a u128,
+    pub skiers: &'a HashMap<usize, Skier>,
+    pub buildings: &'a HashMap<usize, Building>,
+    pub lifts: &'a HashMap<usize, Lift>,
+    pub pistes: &'a HashMap<usize, Piste>,
+    pub open: &'a HashMap<usize, open::Status>,
+    pub economy: &'a mut economy::Service,
+    pub messenger: &'a mut messenger::System,
+}
+
+impl System {
+    pub fn new() -> System {
+        System {
+            last_update_micros: None,
+        }
+    }
+
+    pub fn run(
+        &mut self,
+        Parameters {
+            micros,
+            skiers,
+            buildings,
+            lifts,
+            pistes,
+            open,
+            economy,
+            messenger,
+        }: Parameters<'_>,
+    ) {
+        let last_update_micros = *self.last_update_micros.get_or_insert(*micros);
+        let elapsed_micros = micros.saturating_sub(last_update_micros);
+        if elapsed_micros < UPDATE_INTERVAL_MICROS {
+            return;
+        }
+        self.last_update_micros = Some(*micros);
+        let hours = elapsed_micros as f32 / MICROS_PER_HOUR;
+
+        // each window of a hotel is a room, let to one of the hotel's guests
+
+        let mut guests: HashMap<usize, usize> = HashMap::new();
+        for skier in skiers.values() {
+            *guests.entry(skier.hotel_id).or_default() += 1;
+        }
+        let occupied_rooms = buildings
+            .iter()
+            .filter(|(_, building)| !building.under_construction)
+            .map(|(building_id, building)| {
+                guests
+                    .get(building_id)
+                    .copied()
+                    .unwrap_or_default()
+                    .min(building.windows.len())
+            })
+            .sum::<usize>();
+        economy.record(
+            Account::Rooms,
+            charge(occupied_rooms as f32 * ROOM_PRICE_PER_NIGHT * hours / 24.0),
+        );
+
+        let open_lifts = lifts
+            .keys()
+            .filter(|&lift_id| open.contains_key_value(lift_id, open::Status::Open))
+            .count();
+        economy.record(
+            Account::Lifts,
+            -charge(open_lifts as f32 * LIFT_COST_PER_HOUR * hours),
+        );
+
+        let piste_cells = pistes
+            .values()
+            .filter(|piste| piste.class == piste::Class::Piste)
+            .map(|piste| piste.grid.iter().filter(|&cell| piste.grid[cell]).count())
+            .sum::<usize>();
+        economy.record(
+            Account::Pistes,
+            -charge(piste_cells as f32 * PISTE_CELL_COST_PER_HOUR * hours),
+        );
+
+        let was_bankrupt = economy.is_bankrupt();
+        if economy.update(micros) && economy.is_bankrupt() && !was_bankrupt {
+            messenger.send("The resort has gone bankrupt");
+        }
+    }
+}
+
+fn charge(amount: f32) -> i64 {
+    amount.round() as i64
+}
@@ -13,7 +13,7 @@ pub fn run(
     for (id, frame) in frames {
         match frame {
             Some(frame) => {
-                if matches!(frame.model, Model::Chair) {
+                if matches!(frame.model, Model::Car(_)) {
                     continue;
                 }
                 let triangles = frame::draw(frame);
@@ -5,17 +5,36 @@ use commons::map::ContainsKeyValue;
 
 use crate::model::entrance::Entrance;
 use crate::model::gate::Gate;
+use crate::model::group::Group;
 use crate::model::open;
+use crate::model::skier::Skier;
 use crate::model::skiing::{Plan, State};
+use crate::utils::group;
+
+pub struct Parameters<'a> {
+    pub skiers: &'a HashMap<usize, Skier>,
+    pub groups: &'a HashMap<usize, Group>,
+    pub plans: &'a HashMap<usize, Plan>,
+    pub gates: &'a HashMap<usize, Gate>,
+    pub entrances: &'a HashMap<usize, Entrance>,
+    pub open: &'a HashMap<usize, open::Status>,
+    pub targets: &'a mut HashMap<usize, usize>,
+    pub global_targets: &'a mut HashMap<usize, usize>,
+    pub locations: &'a mut HashMap<usize, usize>,
+}
 
 pub fn run(
-    plans: &HashMap<usize, Plan>,
-    gates: &HashMap<usize, Gate>,
-    entrances: &HashMap<usize, Entrance>,
-    open: &HashMap<usize, open::Status>,
-    targets: &mut HashMap<usize, usize>,
-    global_targets: &mut HashMap<usize, usize>,
-    locations: &mut HashMap<usize, usize>,
+    Parameters {
+        skiers,
+        groups,
+        plans,
+        gates,
+        entrances,
+        open,
+        targets,
+        global_targets,
+        locations,
+    }: Parameters<'_>,
 ) {
     for (plan_id, plan) in plans {
         let Plan::Stationary(State {
@@ -42,18 +61,38 @@ pub fn run(
             continue;
         }
 
-        if gate
+        if !gate
             .footprint
             .iter()
             .any(|position| position == *plan_position)
         {
-            targets.remove(plan_id);
-            if let Entry::Occupied(entry) = global_targets.entry(*plan_id) {
-                if *entry.get() == target_id {
-                    entry.remove();
+            continue;
+        }
+
+        // waiting for rest of group to catch up
+
+        if !group::is_regrouped(
+            plan_id,
+            skiers,
+            groups,
+            plans,
+            |_| false,
+            |position| gate.footprint.contains(position),
+        ) {
+            continue;
+        }
+
+        targets.remove(plan_id);
+        if global_targets.get(plan_id) == Some(&target_id) {
+            // whole group moves on to next global target together
+            for member_id in group::members(plan_id, skiers, groups) {
+                if let Entry::Occupied(entry) = global_targets.entry(*member_id) {
+                    if *entry.get() == target_id {
+                        entry.remove();
+                    }
                 }
             }
-            locations.insert(*plan_id, entrance.destination_piste_id);
         }
+        locations.insert(*plan_id, entrance.destination_piste_id);
     }
 }
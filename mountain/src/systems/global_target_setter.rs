@@ -3,34 +3,55 @@ use std::collections::{HashMap, HashSet};
 use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng};
 
+use crate::model::building::{Building, Kind};
 use crate::model::costs::Costs;
 use crate::model::door::Door;
+use crate::model::group::Group;
+use crate::model::hotel;
 use crate::model::lift::Lift;
+use crate::model::needs::Needs;
 use crate::model::skier::Skier;
 use crate::model::skiing::{Plan, State};
+use crate::model::visitors;
+use crate::model::weather::Weather;
+use crate::utils::group::weakest_ability;
 
 const EXPLORE_RATIO: f32 = 0.75;
+/// Chance a group without pressing needs drops into a ticket office or rental shop
+const SHOPPING_RATIO: f32 = 0.05;
 
 pub struct Parameters<'a> {
+    pub micros: &'a u128,
     pub skiers: &'a HashMap<usize, Skier>,
+    pub groups: &'a HashMap<usize, Group>,
     pub plans: &'a HashMap<usize, Plan>,
     pub locations: &'a HashMap<usize, usize>,
     pub lifts: &'a HashMap<usize, Lift>,
+    pub buildings: &'a HashMap<usize, Building>,
     pub doors: &'a HashMap<usize, Door>,
     pub costs: &'a HashMap<usize, Costs<State>>,
     pub global_costs: &'a Costs<usize>,
+    pub needs: &'a HashMap<usize, Needs>,
+    pub check_outs: &'a HashMap<usize, u128>,
+    pub weather: &'a Weather,
     pub global_targets: &'a mut HashMap<usize, usize>,
 }
 
 pub fn run(
     Parameters {
+        micros,
         skiers,
+        groups,
         plans,
         locations,
         lifts,
+        buildings,
         doors,
         costs,
         global_costs,
+        needs,
+        check_outs,
+        weather,
         global_targets,
     }: Parameters<'_>,
 ) {
@@ -40,39 +61,117 @@ pub fn run(
 
     let lift_drop_offs = lifts
         .values()
-        .map(|lift| lift.drop_off.id)
+        .flat_map(|lift| lift.drop_offs())
+        .map(|drop_off| drop_off.id)
         .collect::<HashSet<_>>();
 
-    for (
-        skier_id,
-        Skier {
-            ability: skier_ability,
-            hotel_id,
-            ..
-        },
-    ) in skiers
-    {
-        if global_targets.contains_key(skier_id) {
+    for Group { member_ids } in groups.values() {
+        // group only chooses a new target once every member has reached the last one
+
+        if member_ids
+            .iter()
+            .any(|member_id| global_targets.contains_key(member_id))
+        {
             continue;
         }
 
-        let Some(Plan::Stationary(state)) = plans.get(skier_id) else {
+        let Some(group_ability) = weakest_ability(member_ids, skiers) else {
             continue;
         };
 
-        let Some(location) = locations.get(skier_id) else {
-            continue;
-        };
+        // first stationary member chooses target on behalf of the group
 
-        let Some(costs) = costs.get(location) else {
+        let Some((hotel_id, state, costs)) = member_ids.iter().find_map(|member_id| {
+            let Skier { hotel_id, .. } = skiers.get(member_id)?;
+            let Some(Plan::Stationary(state)) = plans.get(member_id) else {
+                return None;
+            };
+            let costs = costs.get(locations.get(member_id)?)?;
+            Some((hotel_id, state, costs))
+        }) else {
             continue;
         };
+
         let stationary_state = state.stationary();
 
         let targets_on_this_piste = costs
-            .targets_reachable_from_node(&stationary_state, skier_ability)
+            .targets_reachable_from_node(&stationary_state, &group_ability)
             .map(|(piste_target, _)| piste_target);
 
+        let reachable_doors = |door_ids: &HashSet<&usize>| {
+            costs
+                .targets_reachable_from_node(&stationary_state, &group_ability)
+                .map(|(piste_target, _)| piste_target)
+                .flat_map(|piste_target| {
+                    global_costs
+                        .targets_reachable_from_node(piste_target, &group_ability)
+                        .map(|(target, _)| target)
+                        .chain(Some(piste_target))
+                })
+                .filter(|target| door_ids.contains(target))
+                .copied()
+                .collect::<Vec<_>>()
+        };
+
+        // hungry or tired groups look for a service building that can help
+
+        let need = member_ids
+            .iter()
+            .flat_map(|member_id| needs.get(member_id))
+            .find_map(|needs| needs.pressing());
+        if let Some(need) = need {
+            let service_door_ids = service_door_ids(doors, buildings, |kind| {
+                kind.service()
+                    .is_some_and(|service| need.is_relieved_by(&service))
+            });
+            if let Some(door_id) = reachable_doors(&service_door_ids).choose(&mut rng) {
+                for member_id in member_ids {
+                    global_targets.insert(*member_id, *door_id);
+                }
+                continue;
+            }
+        }
+
+        let hotel_door_ids = doors
+            .iter()
+            .filter(|(_, door)| door.building_id == *hotel_id)
+            .map(|(door_id, _)| door_id)
+            .collect::<HashSet<_>>();
+
+        // the worse the weather, the more likely groups head back to their hotel,
+        // while day visitors head back in the evening and guests when they check out
+
+        let is_leaving = (visitors::is_evening(micros)
+            && buildings
+                .get(hotel_id)
+                .is_some_and(|building| building.kind.is_terminal()))
+            || member_ids.iter().any(|member_id| {
+                check_outs
+                    .get(member_id)
+                    .is_some_and(|check_out_day| hotel::is_checking_out(check_out_day, micros))
+            });
+
+        if is_leaving || rng.gen::<f32>() > weather.appeal() {
+            if let Some(door_id) = reachable_doors(&hotel_door_ids).choose(&mut rng) {
+                for member_id in member_ids {
+                    global_targets.insert(*member_id, *door_id);
+                }
+                continue;
+            }
+        }
+
+        if rng.gen::<f32>() < SHOPPING_RATIO {
+            let shop_door_ids = service_door_ids(doors, buildings, |kind| {
+                matches!(kind, Kind::TicketOffice | Kind::RentalShop)
+            });
+            if let Some(door_id) = reachable_doors(&shop_door_ids).choose(&mut rng) {
+                for member_id in member_ids {
+                    global_targets.insert(*member_id, *door_id);
+                }
+                continue;
+            }
+        }
+
         let explore = rng.gen::<f32>() <= EXPLORE_RATIO;
 
         let candidates: HashSet<usize> = if explore {
@@ -84,7 +183,7 @@ pub fn run(
             targets_on_this_piste
                 .flat_map(|piste_target| {
                     global_costs
-                        .targets_reachable_from_node(piste_target, skier_ability)
+                        .targets_reachable_from_node(piste_target, &group_ability)
                         .map(|(target, _)| target)
                         .filter(|target| lift_drop_offs.contains(target))
                 })
@@ -92,40 +191,55 @@ pub fn run(
                 .collect()
         };
 
-        // Trying to find "safe" candidate from which skier can return to own hotel
-
-        let hotel_door_ids = doors
-            .iter()
-            .filter(|(_, door)| door.building_id == *hotel_id)
-            .map(|(door_id, _)| door_id)
-            .collect::<HashSet<_>>();
+        // Trying to find "safe" candidate from which group can return to own hotel
 
         let safe_candidates: Vec<&usize> = candidates
             .iter()
             .filter(|global_target| {
                 global_costs
-                    .targets_reachable_from_node(global_target, skier_ability)
+                    .targets_reachable_from_node(global_target, &group_ability)
                     .any(|(target, _)| hotel_door_ids.contains(target))
             })
             .collect();
 
-        if let Some(&new_target) = safe_candidates.choose(&mut rng) {
-            global_targets.insert(*skier_id, *new_target);
-        }
+        let mut new_target = safe_candidates.choose(&mut rng).copied().copied();
 
-        // Alternatively finding candidate from which skier can return to any hotel
+        // Alternatively finding candidate from which group can return to any hotel
 
-        let safe_candidates: Vec<usize> = candidates
-            .into_iter()
-            .filter(|global_target| {
-                global_costs
-                    .targets_reachable_from_node(global_target, skier_ability)
-                    .any(|(target, _)| all_door_ids.contains(target))
-            })
-            .collect();
+        if new_target.is_none() {
+            let safe_candidates: Vec<usize> = candidates
+                .into_iter()
+                .filter(|global_target| {
+                    global_costs
+                        .targets_reachable_from_node(global_target, &group_ability)
+                        .any(|(target, _)| all_door_ids.contains(target))
+                })
+                .collect();
+
+            new_target = safe_candidates.choose(&mut rng).copied();
+        }
 
-        if let Some(&new_target) = safe_candidates.choose(&mut rng) {
-            global_targets.insert(*skier_id, new_target);
+        if let Some(new_target) = new_target {
+            for member_id in member_ids {
+                global_targets.insert(*member_id, new_target);
+            }
         }
     }
 }
+
+/// Doors of open service buildings whose kind matches
+fn service_door_ids<'a>(
+    doors: &'a HashMap<usize, Door>,
+    buildings: &HashMap<usize, Building>,
+    is_match: impl Fn(&Kind) -> bool,
+) -> HashSet<&'a usize> {
+    doors
+        .iter()
+        .filter(|(_, door)| {
+            buildings
+                .get(&door.building_id)
+                .is_some_and(|building| !building.under_construction && is_match(&building.kind))
+        })
+        .map(|(door_id, _)| door_id)
+        .collect()
+}
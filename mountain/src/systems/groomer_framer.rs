@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use commons::geometry::{xy, xyz, XY};
+use commons::grid::Grid;
+
+use crate::model::frame::{self, Frame};
+use crate::model::groomer::Groomer;
+use crate::model::open;
+
+pub fn run(
+    terrain: &Grid<f32>,
+    groomers: &HashMap<usize, Groomer>,
+    open: &HashMap<usize, open::Status>,
+    frames: &mut HashMap<usize, Option<Frame>>,
+) {
+    for (id, groomer) in groomers {
+        // groomers wait out of sight while their piste is open
+        if open.get(&groomer.piste_id) != Some(&open::Status::Closed) || groomer.is_finished() {
+            continue;
+        }
+
+        let direction = xy(groomer.yaw.cos(), groomer.yaw.sin());
+        let behind = cell(terrain, groomer.position - direction);
+        let ahead = cell(terrain, groomer.position + direction);
+        let position = cell(terrain, groomer.position);
+        let pitch = (-(terrain[ahead] - terrain[behind]) / 2.0).atan();
+
+        frames.insert(
+            *id,
+            Some(Frame {
+                position: xyz(groomer.position.x, groomer.position.y, terrain[position]),
+                yaw: groomer.yaw,
+                pitch,
+                model: frame::Model::Groomer,
+            }),
+        );
+    }
+}
+
+fn cell(terrain: &Grid<f32>, position: XY<f32>) -> XY<u32> {
+    xy(
+        (position.x.round().max(0.0) as u32).min(terrain.width() - 1),
+        (position.y.round().max(0.0) as u32).min(terrain.height() - 1),
+    )
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+
+use commons::geometry::{xy, XYRectangle};
+use commons::grid::Grid;
+
+use crate::model::groomer::{self, Groomer};
+use crate::model::open;
+use crate::model::piste::Piste;
+use crate::model::snow::{Quality, Snow};
+use crate::systems::{messenger, terrain_artist};
+
+const OVERLAY_REFRESH_MICROS: u128 = 10 * 60 * 1_000_000;
+
+pub struct System {
+    last_micros: Option<u128>,
+    last_overlay_refresh_micros: u128,
+}
+
+pub struct Parameters<'a> {
+    pub micros: &'a u128,
+    pub pistes: &'a HashMap<usize, Piste>,
+    pub piste_map: &'a Grid<Option<usize>>,
+    pub open: &'a HashMap<usize, open::Status>,
+    pub groomers: &'a mut HashMap<usize, Groomer>,
+    pub groomed: &'a mut Grid<Option<u128>>,
+    pub snow: &'a mut Grid<Snow>,
+    pub terrain_artist: &'a mut terrain_artist::System,
+    pub messenger: &'a mut messenger::System,
+}
+
+impl System {
+    pub fn new() -> System {
+        System {
+            last_micros: None,
+            last_overlay_refresh_micros: 0,
+        }
+    }
+
+    pub fn run(
+        &mut self,
+        Parameters {
+            micros,
+            pistes,
+            piste_map,
+            open,
+            groomers,
+            groomed,
+            snow,
+            terrain_artist,
+            messenger,
+        }: Parameters<'_>,
+    ) {
+        let last_micros = self.last_micros.replace(*micros).unwrap_or(*micros);
+        let meters = groomer::VELOCITY * micros.saturating_sub(last_micros) as f32 / 1_000_000.0;
+
+        let mut finished = vec![];
+        for (id, groomer) in groomers.iter_mut() {
+            let Some(piste) = pistes.get(&groomer.piste_id) else {
+                finished.push(*id);
+                continue;
+            };
+            if open.get(&groomer.piste_id) != Some(&open::Status::Closed) {
+                continue;
+            }
+
+            // keeping off any other piste that is open
+            let reached = groomer.drive(meters, |stop| {
+                piste_map[stop]
+                    .and_then(|piste_id| open.get(&piste_id))
+                    .map(|status| *status == open::Status::Closed)
+                    .unwrap_or(true)
+            });
+
+            for stop in reached {
+                for cell in groomer::swath(&piste.grid, &stop) {
+                    groomed[cell] = Some(*micros);
+                    if snow[cell].is_skiable() {
+                        snow[cell].quality = Quality::Packed;
+                    }
+                }
+                terrain_artist.update_overlay(XYRectangle {
+                    from: xy(stop.x, stop.y.saturating_sub(groomer::SWATH_RADIUS)),
+                    to: xy(
+                        stop.x,
+                        (stop.y + groomer::SWATH_RADIUS).min(groomed.height() - 1),
+                    ),
+                });
+            }
+
+            if groomer.is_finished() {
+                messenger.send(format!("Piste {} has been groomed", groomer.piste_id));
+                finished.push(*id);
+            }
+        }
+
+        for id in finished {
+            groomers.remove(&id);
+        }
+
+        // groomed cells fade as they age
+        if terrain_artist.is_showing_grooming()
+            && micros.saturating_sub(self.last_overlay_refresh_micros) >= OVERLAY_REFRESH_MICROS
+        {
+            self.last_overlay_refresh_micros = *micros;
+            terrain_artist.update_whole_overlay();
+        }
+    }
+}
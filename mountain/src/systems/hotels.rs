@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use rand::{thread_rng, Rng};
+
+use crate::controllers::building_builder::spawn_skiers;
+use crate::model::building::{Building, Kind};
+use crate::model::door::Door;
+use crate::model::group::Group;
+use crate::model::hotel::{self, CHECK_IN_MICROS, STAY_DAYS};
+use crate::model::satisfaction::Rating;
+use crate::model::skier::Skier;
+use crate::model::skiing::Plan;
+use crate::model::time_of_day;
+use crate::services::economy::MICROS_PER_DAY;
+use crate::services::id_allocator;
+use crate::systems::messenger;
+use crate::utils::skiers::is_at_home_door;
+
+pub struct System {
+    last_micros: Option<u128>,
+}
+
+pub struct Parameters<'a> {
+    pub micros: &'a u128,
+    pub buildings: &'a HashMap<usize, Building>,
+    pub doors: &'a HashMap<usize, Door>,
+    pub plans: &'a HashMap<usize, Plan>,
+    pub targets: &'a HashMap<usize, usize>,
+    /// From 0 to 1, how keen people are to book a stay
+    pub demand: f32,
+    pub rating: &'a Rating,
+    pub id_allocator: &'a mut id_allocator::Service,
+    pub locations: &'a mut HashMap<usize, usize>,
+    pub skiers: &'a mut HashMap<usize, Skier>,
+    pub groups: &'a mut HashMap<usize, Group>,
+    pub check_outs: &'a mut HashMap<usize, u128>,
+    pub messenger: &'a mut messenger::System,
+}
+
+impl System {
+    pub fn new() -> System {
+        System { last_micros: None }
+    }
+
+    /// Checks new guests in each afternoon, returning the guests who have checked out
+    pub fn run(
+        &mut self,
+        Parameters {
+            micros,
+            buildings,
+            doors,
+            plans,
+            targets,
+            demand,
+            rating,
+            id_allocator,
+            locations,
+            skiers,
+            groups,
+            check_outs,
+            messenger,
+        }: Parameters<'_>,
+    ) -> Vec<usize> {
+        let last_micros = self.last_micros.replace(*micros).unwrap_or(*micros);
+        let is_hotel = |building_id: &usize| {
+            buildings
+                .get(building_id)
+                .is_some_and(|building| building.kind == Kind::Hotel)
+        };
+
+        // check ins, with each room let to one guest
+
+        if time_of_day::passed(CHECK_IN_MICROS, &last_micros, micros) {
+            let mut guests: HashMap<usize, usize> = HashMap::new();
+            for skier in skiers.values() {
+                *guests.entry(skier.hotel_id).or_default() += 1;
+            }
+            for (building_id, building) in buildings
+                .iter()
+                .filter(|(_, building)| building.kind == Kind::Hotel)
+                .filter(|(_, building)| !building.under_construction)
+            {
+                let check_ins = hotel::check_ins(
+                    building.windows.len(),
+                    guests.get(building_id).copied().unwrap_or_default(),
+                    rating,
+                    demand,
+                );
+                if check_ins == 0 {
+                    continue;
+                }
+                spawn_skiers(
+                    building_id,
+                    check_ins,
+                    id_allocator,
+                    locations,
+                    skiers,
+                    groups,
+                );
+                messenger.send(format!(
+                    "{} guests checked in to Hotel {}",
+                    check_ins, building_id
+                ));
+            }
+        }
+
+        // guests without a stay yet book one, each group leaving together
+
+        let mut rng = thread_rng();
+        let today = micros / MICROS_PER_DAY;
+        for Group { member_ids } in groups.values() {
+            let is_new_guest = |member_id: &usize| {
+                !check_outs.contains_key(member_id)
+                    && skiers
+                        .get(member_id)
+                        .is_some_and(|skier| is_hotel(&skier.hotel_id))
+            };
+            if !member_ids.iter().any(is_new_guest) {
+                continue;
+            }
+            let check_out_day = today + rng.gen_range(STAY_DAYS);
+            for member_id in member_ids {
+                check_outs.insert(*member_id, check_out_day);
+            }
+        }
+
+        // check outs, with any guests still out at the end of the day leaving regardless
+
+        let end_of_day = time_of_day::passed(0, &last_micros, micros);
+
+        let departed = skiers
+            .iter()
+            .filter(|(skier_id, _)| {
+                check_outs
+                    .get(skier_id)
+                    .is_some_and(|check_out_day| hotel::is_checking_out(check_out_day, micros))
+            })
+            .filter(|(skier_id, skier)| {
+                end_of_day
+                    || locations.get(skier_id) == Some(&skier.hotel_id)
+                    || is_at_home_door(skier_id, skier, plans, targets, doors)
+            })
+            .map(|(skier_id, _)| *skier_id)
+            .collect::<Vec<_>>();
+
+        if !departed.is_empty() {
+            messenger.send(format!("{} guests checked out", departed.len()));
+        }
+
+        departed
+    }
+}
@@ -0,0 +1,429 @@
+use std::collections::{HashMap, HashSet};
+
+use commons::geometry::{xy, XYRectangle, XY};
+use commons::grid::Grid;
+use commons::map::ContainsKeyValue;
+use network::algorithms::find_path::FindPath;
+use rand::{thread_rng, Rng};
+
+use crate::model::ability::Ability;
+use crate::model::building::{Building, Kind};
+use crate::model::direction::DIRECTIONS;
+use crate::model::door::Door;
+use crate::model::incident::{self, Incident, Statistics, SLOW_RESCUE_MICROS, TREATMENT_MICROS};
+use crate::model::open;
+use crate::model::patroller::{Leg, Patroller, PATROLLERS_PER_HUT};
+use crate::model::piste::{self, Piste};
+use crate::model::reservation::{Reservation, ReservationPeriod};
+use crate::model::satisfaction::{Satisfaction, Thought};
+use crate::model::skier::{Equipment, Skier};
+use crate::model::skiing::{Plan, State};
+use crate::model::snow::Snow;
+use crate::network::skiing::SkiingNetwork;
+use crate::network::velocity_encoding::decode_velocity;
+use crate::services::id_allocator;
+use crate::systems::{messenger, planner};
+
+const FALL_CHECK_INTERVAL_MICROS: u128 = 60 * 1_000_000;
+/// How long to wait before searching again for a route that could not be found
+const ROUTE_RETRY_MICROS: u128 = 60 * 1_000_000;
+/// Patrollers stay within this many cells of the area around where they are and where they are going
+const ROUTE_MARGIN_CELLS: u32 = 64;
+/// Lower bound on the time patrollers take to walk a cell, to guide their search
+const WALK_MICROS_PER_CELL: u64 = 1_000_000;
+
+pub struct System {
+    last_fall_check_micros: Option<u128>,
+    /// When the route search last failed for each waiting incident or arrived patroller
+    failed_searches: HashMap<usize, u128>,
+}
+
+pub struct Parameters<'a> {
+    pub micros: &'a u128,
+    pub terrain: &'a Grid<f32>,
+    pub snow: &'a Grid<Snow>,
+    pub skiers: &'a HashMap<usize, Skier>,
+    pub pistes: &'a HashMap<usize, Piste>,
+    pub abilities: &'a HashMap<usize, Ability>,
+    pub buildings: &'a HashMap<usize, Building>,
+    pub doors: &'a HashMap<usize, Door>,
+    pub open: &'a HashMap<usize, open::Status>,
+    pub id_allocator: &'a mut id_allocator::Service,
+    pub plans: &'a mut HashMap<usize, Plan>,
+    pub locations: &'a mut HashMap<usize, usize>,
+    pub reservations: &'a mut Grid<HashMap<usize, Reservation>>,
+    pub visits: &'a mut HashMap<usize, u128>,
+    pub incidents: &'a mut HashMap<usize, Incident>,
+    pub incident_statistics: &'a mut Statistics,
+    pub patrollers: &'a mut HashMap<usize, Patroller>,
+    pub satisfaction: &'a mut HashMap<usize, Satisfaction>,
+    pub messenger: &'a mut messenger::System,
+}
+
+impl System {
+    pub fn new() -> System {
+        System {
+            last_fall_check_micros: None,
+            failed_searches: HashMap::new(),
+        }
+    }
+
+    pub fn run(
+        &mut self,
+        Parameters {
+            micros,
+            terrain,
+            snow,
+            skiers,
+            pistes,
+            abilities,
+            buildings,
+            doors,
+            open,
+            id_allocator,
+            plans,
+            locations,
+            reservations,
+            visits,
+            incidents,
+            incident_statistics,
+            patrollers,
+            satisfaction,
+            messenger,
+        }: Parameters<'_>,
+    ) {
+        incident_statistics.forget(micros);
+        self.failed_searches
+            .retain(|id, _| incidents.contains_key(id) || patrollers.contains_key(id));
+
+        // skiers on runs that are too hard for them, or going too fast, sometimes fall
+
+        let last_fall_check_micros = *self.last_fall_check_micros.get_or_insert(*micros);
+        let elapsed_micros = micros.saturating_sub(last_fall_check_micros);
+        if elapsed_micros >= FALL_CHECK_INTERVAL_MICROS {
+            self.last_fall_check_micros = Some(*micros);
+            let minutes = elapsed_micros as f32 / 60_000_000.0;
+            let mut rng = thread_rng();
+
+            let falls = plans
+                .iter()
+                .filter(|(skier_id, _)| !incidents.contains_key(skier_id))
+                .flat_map(|(skier_id, plan)| {
+                    let Plan::Moving(events) = plan else {
+                        return None;
+                    };
+                    let skier = skiers.get(skier_id)?;
+                    let piste_id = locations.get(skier_id)?;
+                    if pistes.get(piste_id)?.class != piste::Class::Piste {
+                        return None;
+                    }
+                    let run = abilities.get(piste_id)?;
+                    let state = events
+                        .iter()
+                        .rev()
+                        .find(|event| event.micros <= *micros)?
+                        .state;
+                    let velocity = decode_velocity(&state.velocity)?;
+                    let chance = incident::fall_chance(&skier.ability, run, velocity, minutes);
+                    (rng.gen::<f32>() < chance).then_some((*skier_id, state.stationary()))
+                })
+                .collect::<Vec<_>>();
+
+            for (skier_id, state) in falls {
+                if let Some(Plan::Moving(events)) = plans.get(&skier_id) {
+                    for event in events {
+                        reservations[event.state.position].remove(&skier_id);
+                    }
+                }
+                reservations[state.position]
+                    .insert(skier_id, Reservation::Mobile(ReservationPeriod::Permanent));
+                plans.insert(skier_id, Plan::Stationary(state));
+                incidents.insert(
+                    skier_id,
+                    Incident {
+                        micros: *micros,
+                        state,
+                        patroller_id: None,
+                    },
+                );
+                incident_statistics.record_incident(micros);
+                satisfaction
+                    .entry(skier_id)
+                    .or_default()
+                    .think(Thought::Injured, micros);
+                messenger.send(format!("Skier {} has fallen and is injured", skier_id));
+            }
+        }
+
+        // patrol huts with patrollers to spare send one to each injured skier
+
+        let is_open_door_of = |door_id: &usize, door: &Door, kind: Kind| {
+            open.contains_key_value(door_id, open::Status::Open)
+                && buildings
+                    .get(&door.building_id)
+                    .is_some_and(|building| building.kind == kind && !building.under_construction)
+        };
+
+        let mut waiting = incidents
+            .iter()
+            .filter(|(_, incident)| incident.patroller_id.is_none())
+            .map(|(skier_id, incident)| (*skier_id, incident.micros, incident.state.position))
+            .collect::<Vec<_>>();
+        // the longest waiting are helped first
+        waiting.sort_by_key(|(_, micros, _)| *micros);
+
+        for (skier_id, _, position) in waiting {
+            if !self.is_search_due(&skier_id, micros) {
+                continue;
+            }
+
+            let mut patrollers_out: HashMap<usize, usize> = HashMap::new();
+            for patroller in patrollers.values() {
+                *patrollers_out.entry(patroller.hut_id).or_default() += 1;
+            }
+
+            let huts = doors
+                .iter()
+                .filter(|(door_id, door)| is_open_door_of(door_id, door, Kind::PatrolHut))
+                .filter(|(_, door)| {
+                    patrollers_out
+                        .get(&door.building_id)
+                        .copied()
+                        .unwrap_or_default()
+                        < PATROLLERS_PER_HUT
+                })
+                .flat_map(|(_, door)| {
+                    door.aperture.iter().map(|aperture| {
+                        (
+                            State {
+                                position: *aperture,
+                                velocity: 0,
+                                travel_direction: door.direction,
+                            },
+                            door.building_id,
+                        )
+                    })
+                })
+                .collect::<HashMap<_, _>>();
+            if huts.is_empty() {
+                break;
+            }
+
+            let Some(plan) = find_route(
+                terrain,
+                snow,
+                micros,
+                huts.keys().copied().collect(),
+                &HashSet::from([position]),
+            ) else {
+                self.failed_searches.insert(skier_id, *micros);
+                continue;
+            };
+            let Some(hut_id) = first_state(&plan).and_then(|state| huts.get(&state)) else {
+                continue;
+            };
+
+            let patroller_id = id_allocator.next_id();
+            patrollers.insert(
+                patroller_id,
+                Patroller {
+                    hut_id: *hut_id,
+                    skier_id,
+                    leg: Leg::ToIncident,
+                    plan,
+                },
+            );
+            if let Some(incident) = incidents.get_mut(&skier_id) {
+                incident.patroller_id = Some(patroller_id);
+            }
+            messenger.send(format!(
+                "Patroller sent from Patrol Hut {} to injured skier {}",
+                hut_id, skier_id
+            ));
+        }
+
+        // patrollers carry injured skiers by sled to the nearest first-aid building
+
+        let first_aid = doors
+            .iter()
+            .filter(|(door_id, door)| is_open_door_of(door_id, door, Kind::FirstAid))
+            .flat_map(|(_, door)| {
+                door.aperture
+                    .iter()
+                    .map(|aperture| (*aperture, door.building_id))
+            })
+            .collect::<HashMap<_, _>>();
+        let first_aid_positions = first_aid.keys().copied().collect::<HashSet<_>>();
+
+        let arrived = patrollers
+            .iter()
+            .filter(|(_, patroller)| patroller.is_arrived(micros))
+            .map(|(patroller_id, _)| *patroller_id)
+            .collect::<Vec<_>>();
+
+        for patroller_id in arrived {
+            let Some(patroller) = patrollers.get_mut(&patroller_id) else {
+                continue;
+            };
+            // the injured skier has gone, so the patroller heads back to their hut out of sight
+            let Some(incident) = incidents.get(&patroller.skier_id).copied() else {
+                patrollers.remove(&patroller_id);
+                continue;
+            };
+            let skier_id = patroller.skier_id;
+
+            if let Leg::ToFirstAid { building_id } = patroller.leg {
+                if first_aid
+                    .values()
+                    .any(|first_aid_id| *first_aid_id == building_id)
+                {
+                    locations.insert(skier_id, building_id);
+                    visits.insert(skier_id, micros + TREATMENT_MICROS);
+                    incidents.remove(&skier_id);
+                    patrollers.remove(&patroller_id);
+                    messenger.send(format!(
+                        "Injured skier {} has been taken to First Aid {}",
+                        skier_id, building_id
+                    ));
+                    continue;
+                }
+            }
+
+            let Some(from) = patroller.last_state() else {
+                continue;
+            };
+            // waiting where they are until there is a first-aid building to go to
+            if first_aid_positions.is_empty() {
+                patroller.plan = Plan::Stationary(from.stationary());
+                continue;
+            }
+            if !self.is_search_due(&patroller_id, micros) {
+                continue;
+            }
+            let Some(plan) = find_route(
+                terrain,
+                snow,
+                micros,
+                HashSet::from([from.stationary()]),
+                &first_aid_positions,
+            ) else {
+                self.failed_searches.insert(patroller_id, *micros);
+                continue;
+            };
+            let Some(building_id) =
+                last_state(&plan).and_then(|state| first_aid.get(&state.position))
+            else {
+                continue;
+            };
+
+            if patroller.leg == Leg::ToIncident {
+                plans.remove(&skier_id);
+                reservations[incident.state.position].remove(&skier_id);
+                locations.remove(&skier_id);
+                let wait_micros = micros.saturating_sub(incident.micros);
+                incident_statistics.record_rescue(&wait_micros);
+                if wait_micros > SLOW_RESCUE_MICROS {
+                    satisfaction
+                        .entry(skier_id)
+                        .or_default()
+                        .think(Thought::SlowRescue, micros);
+                }
+            }
+
+            patroller.leg = Leg::ToFirstAid {
+                building_id: *building_id,
+            };
+            patroller.plan = plan;
+        }
+    }
+
+    /// Whether to search for a route, which is not retried for a while after failing
+    fn is_search_due(&self, id: &usize, micros: &u128) -> bool {
+        self.failed_searches
+            .get(id)
+            .is_none_or(|failed_micros| micros.saturating_sub(*failed_micros) >= ROUTE_RETRY_MICROS)
+    }
+}
+
+/// Patrollers walk with their sled wherever they need to go, off piste if need be
+fn find_route(
+    terrain: &Grid<f32>,
+    snow: &Grid<Snow>,
+    micros: &u128,
+    from: HashSet<State>,
+    to: &HashSet<XY<u32>>,
+) -> Option<Plan> {
+    // keeping the search from exploring the whole mountain when there is no route
+    let positions = from
+        .iter()
+        .map(|state| state.position)
+        .chain(to.iter().copied())
+        .collect::<Vec<_>>();
+    let bounds = XYRectangle {
+        from: xy(
+            positions.iter().map(|position| position.x).min()?,
+            positions.iter().map(|position| position.y).min()?,
+        ),
+        to: xy(
+            positions.iter().map(|position| position.x).max()?,
+            positions.iter().map(|position| position.y).max()?,
+        ),
+    }
+    .expand(ROUTE_MARGIN_CELLS);
+
+    let network = SkiingNetwork {
+        terrain,
+        snow,
+        class: piste::Class::Path,
+        ability: Ability::Expert,
+        equipment: Equipment::Skis,
+        is_accessible_fn: &|position| bounds.contains(position),
+        is_valid_edge_fn: &|_, _| true,
+    };
+
+    let to_states = to
+        .iter()
+        .flat_map(|position| {
+            DIRECTIONS.iter().map(|travel_direction| State {
+                position: *position,
+                velocity: 0,
+                travel_direction: *travel_direction,
+            })
+        })
+        .collect::<HashSet<_>>();
+
+    let edges = network.find_path(from.clone(), to_states, &|_, state| {
+        to.iter()
+            .map(|position| {
+                let dx = position.x.abs_diff(state.position.x);
+                let dy = position.y.abs_diff(state.position.y);
+                dx.max(dy) as u64 * WALK_MICROS_PER_CELL
+            })
+            .min()
+            .unwrap_or_default()
+    })?;
+
+    if edges.is_empty() {
+        // already there
+        let state = from
+            .into_iter()
+            .find(|state| to.contains(&state.position))?;
+        Some(Plan::Stationary(state))
+    } else {
+        Some(Plan::Moving(planner::events(micros, edges)))
+    }
+}
+
+fn first_state(plan: &Plan) -> Option<State> {
+    match plan {
+        Plan::Stationary(state) => Some(*state),
+        Plan::Moving(events) => events.first().map(|event| event.state),
+    }
+}
+
+fn last_state(plan: &Plan) -> Option<State> {
+    match plan {
+        Plan::Stationary(state) => Some(*state),
+        Plan::Moving(events) => events.last().map(|event| event.state),
+    }
+}
@@ -5,6 +5,8 @@ use commons::grid::Grid;
 use engine::graphics::Graphics;
 
 use crate::draw::lift_building::draw;
+use crate::model::construction;
+use crate::model::lift::Lift;
 use crate::model::lift_building::LiftBuildings;
 
 #[derive(Default)]
@@ -21,14 +23,28 @@ impl System {
         &mut self,
         graphics: &mut dyn Graphics,
         lift_buildings: &HashMap<usize, LiftBuildings>,
+        lifts: &HashMap<usize, Lift>,
+        constructions: &construction::Queue,
         terrain: &Grid<f32>,
         drawings: &mut HashMap<usize, usize>,
     ) {
+        let under_construction = |lift_building_id: &usize| {
+            lifts.iter().any(|(lift_id, lift)| {
+                lift.buildings_id == *lift_building_id && constructions.contains(lift_id)
+            })
+        };
+
         for (lift_building_id, lift_buildings) in lift_buildings {
             if let Entry::Vacant(cell) = drawings.entry(*lift_building_id) {
                 if let Ok(graphics_index) = graphics.create_triangles() {
                     cell.insert(graphics_index);
-                    draw(graphics, &graphics_index, lift_buildings, terrain);
+                    draw(
+                        graphics,
+                        &graphics_index,
+                        lift_buildings,
+                        terrain,
+                        under_construction(lift_building_id),
+                    );
                 }
             }
         }
@@ -40,7 +56,13 @@ impl System {
             let Some(drawing_id) = drawings.get(&lift_building_id) else {
                 continue;
             };
-            draw(graphics, drawing_id, lift_buildings, terrain);
+            draw(
+                graphics,
+                drawing_id,
+                lift_buildings,
+                terrain,
+                under_construction(&lift_building_id),
+            );
         }
     }
 }
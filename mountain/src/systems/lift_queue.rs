@@ -0,0 +1,136 @@
+use std::collections::HashMap;
+
+use commons::grid::Grid;
+use commons::map::ContainsKeyValue;
+
+use crate::model::carousel::Carousel;
+use crate::model::exit::Exit;
+use crate::model::group::Group;
+use crate::model::lift::Lift;
+use crate::model::lift_queue::{LiftQueue, QueuedSkier};
+use crate::model::open;
+use crate::model::reservation::{Reservation, ReservationPeriod};
+use crate::model::skier::Skier;
+use crate::model::skiing::Plan;
+use crate::systems::global_computer;
+use crate::utils::group;
+use crate::utils::lift_queue::micros_per_skier;
+
+const MAX_EXPECTED_WAIT_MICROS: u128 = 5 * 60 * 1_000_000;
+const WAIT_COST_INTERVAL_MICROS: u128 = 30 * 1_000_000;
+
+pub struct Parameters<'a> {
+    pub micros: &'a u128,
+    pub skiers: &'a HashMap<usize, Skier>,
+    pub groups: &'a HashMap<usize, Group>,
+    pub lifts: &'a HashMap<usize, Lift>,
+    pub carousels: &'a HashMap<usize, Carousel>,
+    pub exits: &'a HashMap<usize, Exit>,
+    pub open: &'a HashMap<usize, open::Status>,
+    pub reservations: &'a mut Grid<HashMap<usize, Reservation>>,
+    pub plans: &'a mut HashMap<usize, Plan>,
+    pub locations: &'a mut HashMap<usize, usize>,
+    pub targets: &'a mut HashMap<usize, usize>,
+    pub global_targets: &'a mut HashMap<usize, usize>,
+    pub lift_queues: &'a mut HashMap<usize, LiftQueue>,
+    pub global_computer: &'a mut global_computer::System,
+}
+
+pub fn run(
+    Parameters {
+        micros,
+        skiers,
+        groups,
+        lifts,
+        carousels,
+        exits,
+        open,
+        reservations,
+        plans,
+        locations,
+        targets,
+        global_targets,
+        lift_queues,
+        global_computer,
+    }: Parameters<'_>,
+) {
+    for (lift, pick_up) in lifts
+        .values()
+        .flat_map(|lift| lift.pick_ups().map(move |pick_up| (lift, pick_up)))
+    {
+        let Some(queue) = lift_queues.get_mut(&pick_up.id) else {
+            continue;
+        };
+        let micros_per_skier = carousels
+            .get(&lift.carousel_id)
+            .and_then(|carousel| micros_per_skier(lift, carousel))
+            .unwrap_or_default();
+
+        if !open.contains_key_value(pick_up.id, open::Status::Open) {
+            // sending queue back onto the piste one skier at a time
+            let Some(Exit {
+                origin_piste_id, ..
+            }) = exits.get(&pick_up.id)
+            else {
+                continue;
+            };
+            let position = pick_up.state.position;
+            if reservations[position]
+                .values()
+                .any(|reservation| matches!(reservation, Reservation::Mobile(_)))
+            {
+                continue;
+            }
+            let Some(QueuedSkier { skier_id, .. }) = queue.skiers.pop_front() else {
+                continue;
+            };
+            targets.remove(&skier_id);
+            global_targets.remove(&skier_id);
+            locations.insert(skier_id, *origin_piste_id);
+            plans.insert(skier_id, Plan::Stationary(pick_up.state));
+            reservations[position]
+                .insert(skier_id, Reservation::Mobile(ReservationPeriod::Permanent));
+        } else {
+            let arrivals = plans
+                .iter()
+                .filter(
+                    |(_, plan)| matches!(plan, Plan::Stationary(state) if *state == pick_up.state),
+                )
+                .map(|(skier_id, _)| *skier_id)
+                .filter(|skier_id| targets.get(skier_id) == Some(&pick_up.id))
+                .collect::<Vec<_>>();
+
+            for skier_id in arrivals {
+                let expected_wait_micros = micros_per_skier * queue.skiers.len() as u128;
+                if queue.skiers.len() >= queue.maze.capacity()
+                    || expected_wait_micros > MAX_EXPECTED_WAIT_MICROS
+                {
+                    // queue is too long, whole group looks for something else to do
+                    for member_id in group::members(&skier_id, skiers, groups) {
+                        if targets.get(member_id) == Some(&pick_up.id) {
+                            targets.remove(member_id);
+                        }
+                        global_targets.remove(member_id);
+                    }
+                    continue;
+                }
+
+                plans.remove(&skier_id);
+                reservations[pick_up.state.position].remove(&skier_id);
+                locations.insert(skier_id, pick_up.id);
+                queue.skiers.push_back(QueuedSkier {
+                    skier_id,
+                    joined_micros: *micros,
+                });
+            }
+        }
+
+        // publishing expected wait to global costs
+
+        let expected_wait_micros = micros_per_skier * queue.skiers.len() as u128;
+        if expected_wait_micros.abs_diff(queue.expected_wait_micros) >= WAIT_COST_INTERVAL_MICROS {
+            queue.expected_wait_micros = expected_wait_micros;
+            global_computer.update();
+        }
+    }
+}
@@ -3,26 +3,45 @@ pub mod carousel;
 pub mod chair_artist;
 pub mod chair_framer;
 pub mod closer;
+pub mod construction;
 pub mod door;
 pub mod door_artist;
+pub mod economy;
 pub mod frame_artist;
 pub mod frame_wiper;
 pub mod gate;
 pub mod gate_artist;
 pub mod global_computer;
 pub mod global_target_setter;
+pub mod groomer_framer;
+pub mod grooming;
+pub mod hotels;
+pub mod incidents;
 pub mod lift_artist;
 pub mod lift_building_artist;
+pub mod lift_queue;
 pub mod log;
 pub mod messenger;
+pub mod patrol_framer;
 pub mod piste_adopter;
 pub mod piste_computer;
 pub mod planner;
+pub mod queue_framer;
+pub mod reliability;
+pub mod satisfaction;
+pub mod scenario;
 pub mod selection_rasterizer;
+pub mod services;
 pub mod skier_colors;
 pub mod skiing_framer;
+pub mod snow;
+pub mod snowmaking;
+pub mod snowmaking_artist;
 pub mod target_checker;
 pub mod target_setter;
 pub mod terrain_artist;
 pub mod tree_artist;
+pub mod visitors;
+pub mod weather;
+pub mod weather_artist;
 pub mod window_artist;
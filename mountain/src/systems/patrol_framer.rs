@@ -0,0 +1,68 @@
+use std::collections::HashMap;
+
+use commons::color::Rgb;
+use commons::geometry::xyz;
+use commons::grid::Grid;
+
+use crate::model::frame::{Frame, Model};
+use crate::model::incident::Incident;
+use crate::model::patroller::{self, Leg, Patroller};
+use crate::model::skier::{Clothes, Equipment};
+use crate::systems::skiing_framer::frame_from_plan;
+
+/// How far behind the patroller the sled is hauled
+const SLED_OFFSET: f32 = 1.5;
+
+pub fn run(
+    terrain: &Grid<f32>,
+    micros: &u128,
+    incidents: &HashMap<usize, Incident>,
+    patrollers: &HashMap<usize, Patroller>,
+    clothes: &HashMap<usize, Clothes<Rgb<f32>>>,
+    frames: &mut HashMap<usize, Option<Frame>>,
+) {
+    for (skier_id, Incident { state, .. }) in incidents {
+        let Some(clothes) = clothes.get(skier_id) else {
+            continue;
+        };
+        let position = state.position;
+        frames.insert(
+            *skier_id,
+            Some(Frame {
+                position: xyz(position.x as f32, position.y as f32, terrain[position]),
+                yaw: state.travel_direction.angle(),
+                pitch: 0.0,
+                model: Model::Lying { clothes: *clothes },
+            }),
+        );
+    }
+
+    let patroller_model = Model::on_snow(&Equipment::Skis, patroller::CLOTHES);
+    for (patroller_id, patroller) in patrollers {
+        let Some(frame) = frame_from_plan(terrain, micros, &patroller.plan, &patroller_model)
+        else {
+            continue;
+        };
+        frames.insert(*patroller_id, Some(frame));
+
+        // the injured skier rides on the sled
+        let Leg::ToFirstAid { .. } = patroller.leg else {
+            continue;
+        };
+        let Some(clothes) = clothes.get(&patroller.skier_id) else {
+            continue;
+        };
+        frames.insert(
+            patroller.skier_id,
+            Some(Frame {
+                position: xyz(
+                    frame.position.x - frame.yaw.cos() * SLED_OFFSET,
+                    frame.position.y - frame.yaw.sin() * SLED_OFFSET,
+                    frame.position.z,
+                ),
+                model: Model::Lying { clothes: *clothes },
+                ..frame
+            }),
+        );
+    }
+}
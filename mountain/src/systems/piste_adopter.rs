@@ -5,13 +5,16 @@ use rand::seq::SliceRandom;
 use rand::thread_rng;
 
 use crate::model::ability::Ability;
-use crate::model::skier::Skier;
+use crate::model::group::Group;
+use crate::model::skier::{Equipment, Skier};
 use crate::model::skiing::{Plan, State};
+use crate::utils::group;
 
 pub fn run(
     plans: &HashMap<usize, Plan>,
     piste_map: &Grid<Option<usize>>,
     skiers: &HashMap<usize, Skier>,
+    groups: &HashMap<usize, Group>,
     abilities: &HashMap<usize, Ability>,
     locations: &mut HashMap<usize, usize>,
 ) {
@@ -30,25 +33,35 @@ pub fn run(
             }
         }
 
-        let Some(Skier {
-            ability: skier_ability,
-            ..
-        }) = skiers.get(id)
-        else {
+        let Some(skier_ability) = group::ability(id, skiers, groups) else {
             continue;
         };
 
-        let candidates = piste_map
+        let mut candidates = piste_map
             .offsets(position, &CORNERS_INVERSE)
             .flat_map(|cell| piste_map[cell])
             .filter(|piste| {
                 abilities
                     .get(piste)
-                    .map(|piste_ability| piste_ability <= skier_ability)
+                    .map(|piste_ability| *piste_ability <= skier_ability)
                     .unwrap_or_default()
             })
             .collect::<Vec<_>>();
 
+        // snowboarders would rather ride the steepest runs they can manage
+        if skiers
+            .get(id)
+            .is_some_and(|skier| skier.equipment == Equipment::Snowboard)
+        {
+            let steepest = candidates
+                .iter()
+                .flat_map(|piste| abilities.get(piste))
+                .max();
+            if let Some(&steepest) = steepest {
+                candidates.retain(|piste| abilities.get(piste) == Some(&steepest));
+            }
+        }
+
         let piste = candidates.choose(&mut rng);
 
         if let Some(&piste) = piste {
@@ -9,6 +9,7 @@ use crate::model::exit::Exit;
 use crate::model::piste::Piste;
 use crate::model::reservation::Reservation;
 use crate::model::skiing::State;
+use crate::model::snow::Snow;
 use crate::services::clock;
 use crate::systems::{global_computer, terrain_artist};
 use crate::utils::computer;
@@ -22,6 +23,7 @@ pub struct Parameters<'a> {
     pub entrances: &'a HashMap<usize, Entrance>,
     pub exits: &'a HashMap<usize, Exit>,
     pub terrain: &'a Grid<f32>,
+    pub snow: &'a Grid<Snow>,
     pub reservations: &'a Grid<HashMap<usize, Reservation>>,
     pub costs: &'a mut HashMap<usize, Costs<State>>,
     pub abilities: &'a mut HashMap<usize, Ability>,
@@ -66,6 +68,7 @@ fn recompute_piste(
         entrances,
         exits,
         terrain,
+        snow,
         reservations,
         abilities,
         costs,
@@ -73,7 +76,7 @@ fn recompute_piste(
         ..
     }: &mut Parameters<'_>,
 ) {
-    computer::costs::compute_piste(id, pistes, terrain, exits, reservations, costs);
+    computer::costs::compute_piste(id, pistes, terrain, snow, exits, reservations, costs);
     computer::piste_ability::compute_piste(
         id,
         pistes,
@@ -8,12 +8,17 @@ use rand::Rng;
 
 use crate::model::ability::Ability;
 use crate::model::costs::Costs;
+use crate::model::group::Group;
 use crate::model::hash_vec::HashVec;
+use crate::model::incident::Incident;
 use crate::model::piste::{self, Piste};
 use crate::model::reservation::{Reservation, ReservationPeriod};
-use crate::model::skier::Skier;
+use crate::model::satisfaction::{Satisfaction, Thought};
+use crate::model::skier::{Equipment, Skier};
 use crate::model::skiing::{Event, Plan, State};
+use crate::model::snow::Snow;
 use crate::network::skiing::SkiingNetwork;
+use crate::utils::group;
 
 use network::algorithms::find_best_within_steps::FindBestWithinSteps;
 
@@ -22,8 +27,10 @@ const MAX_DETOUR: u64 = 2;
 
 pub struct Parameters<'a> {
     pub terrain: &'a Grid<f32>,
+    pub snow: &'a Grid<Snow>,
     pub micros: &'a u128,
     pub skiers: &'a HashMap<usize, Skier>,
+    pub groups: &'a HashMap<usize, Group>,
     pub locations: &'a HashMap<usize, usize>,
     pub targets: &'a HashMap<usize, usize>,
     pub pistes: &'a HashMap<usize, Piste>,
@@ -31,13 +38,17 @@ pub struct Parameters<'a> {
     pub plans: &'a mut HashMap<usize, Plan>,
     pub reservations: &'a mut Grid<HashMap<usize, Reservation>>,
     pub planning_queue: &'a mut HashVec<usize>,
+    pub satisfaction: &'a mut HashMap<usize, Satisfaction>,
+    pub incidents: &'a HashMap<usize, Incident>,
 }
 
 pub fn run(
     Parameters {
         terrain,
+        snow,
         micros,
         skiers,
+        groups,
         locations,
         targets,
         pistes,
@@ -45,16 +56,27 @@ pub fn run(
         plans,
         reservations,
         planning_queue,
+        satisfaction,
+        incidents,
     }: Parameters<'_>,
 ) {
     add_new_finished(planning_queue, plans, micros);
 
     planning_queue.retain(|id| {
+        // injured skiers stay where they fell until the ski patrol reaches them
+        if incidents.contains_key(id) {
+            return false;
+        }
+
         let Some(current_plan) = plans.get_mut(id) else {
             return false;
         };
 
-        let Some(Skier { ability, .. }) = skiers.get(id) else {
+        let Some(ability) = group::ability(id, skiers, groups) else {
+            return false;
+        };
+
+        let Some(Skier { equipment, .. }) = skiers.get(id) else {
             return false;
         };
 
@@ -69,17 +91,27 @@ pub fn run(
         free(id, current_plan, reservations);
 
         let from = last_state(current_plan);
-        *current_plan = match get_target_and_costs(id, *ability, locations, targets, costs) {
+        *current_plan = match get_target_and_costs(id, ability, locations, targets, costs) {
             Some((target, costs)) => new_plan(PathfindingParameters {
-                ability: *ability,
+                ability,
+                equipment: *equipment,
                 class: piste.class,
                 terrain,
+                snow,
                 micros,
                 from,
                 piste,
                 reservations,
                 target,
                 costs,
+            })
+            .unwrap_or_else(|| {
+                // no way forward, because other skiers have reserved it
+                satisfaction
+                    .entry(*id)
+                    .or_default()
+                    .think(Thought::Blocked, micros);
+                brake(*from)
             }),
             _ => brake(*from),
         };
@@ -179,25 +211,24 @@ fn get_target_and_costs<'a>(
     Some((target, costs.costs(*target, ability)?))
 }
 
-fn new_plan(params: PathfindingParameters) -> Plan {
+/// Returns `None` if no path could be found
+fn new_plan(params: PathfindingParameters) -> Option<Plan> {
     let from = *params.from;
     let micros = params.micros;
-    match find_path(params) {
-        Some(edges) => {
-            if edges.is_empty() {
-                brake(from)
-            } else {
-                Plan::Moving(events(micros, edges))
-            }
-        }
-        None => brake(from),
+    let edges = find_path(params)?;
+    if edges.is_empty() {
+        Some(brake(from))
+    } else {
+        Some(Plan::Moving(events(micros, edges)))
     }
 }
 
 struct PathfindingParameters<'a> {
     ability: Ability,
+    equipment: Equipment,
     class: piste::Class,
     terrain: &'a Grid<f32>,
+    snow: &'a Grid<Snow>,
     micros: &'a u128,
     from: &'a State,
     piste: &'a Piste,
@@ -209,8 +240,10 @@ struct PathfindingParameters<'a> {
 fn find_path(
     PathfindingParameters {
         ability,
+        equipment,
         class,
         terrain,
+        snow,
         micros,
         from,
         piste,
@@ -221,8 +254,10 @@ fn find_path(
 ) -> Option<Vec<Edge<State>>> {
     let network = SkiingNetwork {
         terrain,
+        snow,
         class,
         ability,
+        equipment,
         is_accessible_fn: &|position| {
             !reservations[position]
                 .iter()
@@ -283,7 +318,7 @@ fn brake(state: State) -> Plan {
     Plan::Stationary(state.stationary())
 }
 
-fn events(start: &u128, edges: Vec<Edge<State>>) -> Vec<Event> {
+pub fn events(start: &u128, edges: Vec<Edge<State>>) -> Vec<Event> {
     let mut out = Vec::with_capacity(edges.len());
     let mut micros = *start;
     let last_i = edges.len() - 1;
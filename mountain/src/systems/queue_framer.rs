@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use commons::color::Rgb;
+use commons::geometry::xyz;
+use commons::grid::Grid;
+
+use crate::model::frame::{Frame, Model};
+use crate::model::lift::Lift;
+use crate::model::lift_queue::LiftQueue;
+use crate::model::skier::{Clothes, Equipment, Skier};
+use crate::utils::lift_queue::footprint;
+
+const MISSING_COLOR: Rgb<f32> = Rgb::new(1.0, 1.0, 0.0);
+lazy_static! {
+    static ref MISSING_CLOTHES: Clothes<Rgb<f32>> = Clothes {
+        skis: MISSING_COLOR,
+        trousers: MISSING_COLOR,
+        jacket: MISSING_COLOR,
+        helmet: MISSING_COLOR,
+    };
+}
+
+pub fn run(
+    terrain: &Grid<f32>,
+    lifts: &HashMap<usize, Lift>,
+    lift_queues: &HashMap<usize, LiftQueue>,
+    skiers: &HashMap<usize, Skier>,
+    clothes: &HashMap<usize, Clothes<Rgb<f32>>>,
+    frames: &mut HashMap<usize, Option<Frame>>,
+) {
+    for pick_up in lifts.values().flat_map(|lift| lift.pick_ups()) {
+        let Some(queue) = lift_queues.get(&pick_up.id) else {
+            continue;
+        };
+
+        let footprint = footprint(&queue.maze, &pick_up.state, terrain);
+        let yaw = pick_up.state.travel_direction.angle();
+
+        for (index, queued) in queue.skiers.iter().enumerate() {
+            // any overflow from a shrunk maze waits at the back
+            let Some(position) = footprint.get(index).or(footprint.last()) else {
+                continue;
+            };
+            let clothes = clothes.get(&queued.skier_id).unwrap_or(&MISSING_CLOTHES);
+            let equipment = skiers
+                .get(&queued.skier_id)
+                .map(|skier| skier.equipment)
+                .unwrap_or(Equipment::Skis);
+            frames.insert(
+                queued.skier_id,
+                Some(Frame {
+                    position: xyz(position.x as f32, position.y as f32, terrain[position]),
+                    yaw,
+                    pitch: 0.0,
+                    model: Model::on_snow(&equipment, *clothes),
+                }),
+            );
+        }
+    }
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+
+use commons::map::ContainsKeyValue;
+use rand::{thread_rng, Rng};
+
+use crate::model::lift::Lift;
+use crate::model::open;
+use crate::model::reliability::{Reliability, Status};
+use crate::systems::{global_computer, messenger};
+
+pub struct System {
+    last_micros: Option<u128>,
+}
+
+pub struct Parameters<'a> {
+    pub micros: &'a u128,
+    pub lifts: &'a HashMap<usize, Lift>,
+    pub reliabilities: &'a mut HashMap<usize, Reliability>,
+    pub open: &'a mut HashMap<usize, open::Status>,
+    pub global_computer: &'a mut global_computer::System,
+    pub messenger: &'a mut messenger::System,
+}
+
+impl System {
+    pub fn new() -> System {
+        System { last_micros: None }
+    }
+
+    pub fn run(
+        &mut self,
+        Parameters {
+            micros,
+            lifts,
+            reliabilities,
+            open,
+            global_computer,
+            messenger,
+        }: Parameters<'_>,
+    ) {
+        let elapsed_micros = self
+            .last_micros
+            .map(|last_micros| micros.saturating_sub(last_micros))
+            .unwrap_or_default();
+        self.last_micros = Some(*micros);
+
+        let mut rng = thread_rng();
+
+        for (lift_id, reliability) in reliabilities.iter_mut() {
+            let Some(lift) = lifts.get(lift_id) else {
+                continue;
+            };
+            reliability.last_maintenance_micros.get_or_insert(*micros);
+
+            match reliability.status {
+                Status::Running => {
+                    if !open.contains_key_value(lift_id, open::Status::Open)
+                        && !open.contains_key_value(lift_id, open::Status::Closing)
+                    {
+                        // closed lifts are not running so cannot break
+                        continue;
+                    }
+
+                    if open.contains_key_value(lift_id, open::Status::Open)
+                        && reliability.is_maintenance_due(*micros)
+                    {
+                        set_lift_status(lift_id, lift, open::Status::Closing, open);
+                        reliability.status = Status::AwaitingMaintenance;
+                        messenger.send(format!("Lift {} is closing for maintenance", lift_id));
+                        global_computer.update();
+                        continue;
+                    }
+
+                    if rng.gen::<f64>() < reliability.breakdown_probability(elapsed_micros) {
+                        // skiers stay on board while queues are sent away
+                        for pick_up in lift.pick_ups() {
+                            if open.contains_key_value(pick_up.id, open::Status::Open) {
+                                open.insert(pick_up.id, open::Status::Closing);
+                            }
+                        }
+                        reliability.status = Status::BrokenDown {
+                            until_micros: micros + reliability.repair_micros,
+                        };
+                        messenger.send(format!("Lift {} has broken down", lift_id));
+                        global_computer.update();
+                    }
+                }
+                Status::BrokenDown { until_micros } => {
+                    if *micros < until_micros {
+                        continue;
+                    }
+                    if open.contains_key_value(lift_id, open::Status::Open) {
+                        for pick_up in lift.pick_ups() {
+                            open.insert(pick_up.id, open::Status::Open);
+                        }
+                    }
+                    reliability.status = Status::Running;
+                    messenger.send(format!("Lift {} has been repaired", lift_id));
+                    global_computer.update();
+                }
+                Status::AwaitingMaintenance => {
+                    if open.contains_key_value(lift_id, open::Status::Closed) {
+                        reliability.status = Status::UnderMaintenance {
+                            until_micros: micros + reliability.maintenance_micros,
+                        };
+                        messenger.send(format!("Lift {} is under maintenance", lift_id));
+                    } else if open.contains_key_value(lift_id, open::Status::Open) {
+                        // lift was reopened, postponing maintenance
+                        reliability.last_maintenance_micros = Some(*micros);
+                        reliability.status = Status::Running;
+                    }
+                }
+                Status::UnderMaintenance { until_micros } => {
+                    if *micros < until_micros {
+                        continue;
+                    }
+                    if open.contains_key_value(lift_id, open::Status::Closed) {
+                        set_lift_status(lift_id, lift, open::Status::Open, open);
+                    }
+                    reliability.last_maintenance_micros = Some(*micros);
+                    reliability.status = Status::Running;
+                    messenger.send(format!("Lift {} has finished maintenance", lift_id));
+                    global_computer.update();
+                }
+            }
+        }
+    }
+}
+
+fn set_lift_status(
+    lift_id: &usize,
+    lift: &Lift,
+    status: open::Status,
+    open: &mut HashMap<usize, open::Status>,
+) {
+    open.insert(*lift_id, status);
+    for portal in lift.portals() {
+        open.insert(portal.id, status);
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::{HashMap, HashSet};
+
+use commons::geometry::{xy, XY};
+use commons::grid::Grid;
+
+use crate::model::ability::Ability;
+use crate::model::incident;
+use crate::model::piste::{self, Piste};
+use crate::model::reservation::Reservation;
+use crate::model::satisfaction::{Rating, Satisfaction, Thought, DEFAULT_SATISFACTION};
+use crate::model::skier::{Equipment, Skier};
+use crate::model::skiing::Plan;
+use crate::model::weather::Weather;
+
+const UPDATE_INTERVAL_MICROS: u128 = 10 * 60 * 1_000_000;
+/// Distance in cells within which other skiers count towards crowding
+const CROWDING_RADIUS: i32 = 3;
+/// Other skiers within the crowding radius that make a skier feel crowded
+const CROWDED_SKIERS: usize = 6;
+
+pub struct System {
+    last_update_micros: Option<u128>,
+}
+
+pub struct Parameters<'a> {
+    pub micros: &'a u128,
+    pub skiers: &'a HashMap<usize, Skier>,
+    pub plans: &'a HashMap<usize, Plan>,
+    pub locations: &'a HashMap<usize, usize>,
+    pub pistes: &'a HashMap<usize, Piste>,
+    pub abilities: &'a HashMap<usize, Ability>,
+    pub reservations: &'a Grid<HashMap<usize, Reservation>>,
+    pub incident_statistics: &'a incident::Statistics,
+    pub weather: &'a Weather,
+    pub satisfaction: &'a mut HashMap<usize, Satisfaction>,
+    pub rating: &'a mut Rating,
+}
+
+impl System {
+    pub fn new() -> System {
+        System {
+            last_update_micros: None,
+        }
+    }
+
+    pub fn run(
+        &mut self,
+        Parameters {
+            micros,
+            skiers,
+            plans,
+            locations,
+            pistes,
+            abilities,
+            reservations,
+            incident_statistics,
+            weather,
+            satisfaction,
+            rating,
+        }: Parameters<'_>,
+    ) {
+        let last_update_micros = *self.last_update_micros.get_or_insert(*micros);
+        if micros.saturating_sub(last_update_micros) < UPDATE_INTERVAL_MICROS {
+            return;
+        }
+        self.last_update_micros = Some(*micros);
+
+        let offsets = (-CROWDING_RADIUS..=CROWDING_RADIUS)
+            .flat_map(|x| (-CROWDING_RADIUS..=CROWDING_RADIUS).map(move |y| xy(x, y)))
+            .collect::<Vec<_>>();
+
+        for (skier_id, plan) in plans {
+            let Some(piste_id) = locations.get(skier_id) else {
+                continue;
+            };
+            let Some(piste) = pistes.get(piste_id) else {
+                continue;
+            };
+
+            // skiers judge the runs they are skiing, not the ones they are standing on
+
+            let equipment = skiers
+                .get(skier_id)
+                .map(|skier| skier.equipment)
+                .unwrap_or(Equipment::Skis);
+            match (plan, &piste.class) {
+                (Plan::Moving(_), piste::Class::Piste) => {
+                    if let (Some(skier), Some(run_ability)) =
+                        (skiers.get(skier_id), abilities.get(piste_id))
+                    {
+                        satisfaction.entry(*skier_id).or_default().think(
+                            Thought::about_run(&equipment, &skier.ability, run_ability),
+                            micros,
+                        );
+                    }
+                }
+                (Plan::Moving(_), piste::Class::Path) if equipment == Equipment::Snowboard => {
+                    satisfaction
+                        .entry(*skier_id)
+                        .or_default()
+                        .think(Thought::TooFlat, micros);
+                }
+                _ => {}
+            }
+
+            if let Some(thought) = Thought::about_weather(weather) {
+                satisfaction
+                    .entry(*skier_id)
+                    .or_default()
+                    .think(thought, micros);
+            }
+
+            let position = current_position(plan, micros);
+            let nearby = reservations
+                .offsets(position, &offsets)
+                .flat_map(|position| reservations[position].iter())
+                .filter(|(id, reservation)| {
+                    *id != skier_id
+                        && matches!(reservation, Reservation::Mobile(_))
+                        && reservation.includes(micros)
+                })
+                .map(|(id, _)| id)
+                .collect::<HashSet<_>>();
+            if nearby.len() >= CROWDED_SKIERS {
+                satisfaction
+                    .entry(*skier_id)
+                    .or_default()
+                    .think(Thought::Crowded, micros);
+            }
+        }
+
+        rating.update(
+            skiers.keys().map(|skier_id| {
+                satisfaction
+                    .get(skier_id)
+                    .map(|satisfaction| satisfaction.score)
+                    .unwrap_or(DEFAULT_SATISFACTION)
+            }),
+            incident_statistics.daily_rate(skiers.len()),
+        );
+    }
+}
+
+fn current_position(plan: &Plan, micros: &u128) -> XY<u32> {
+    match plan {
+        Plan::Stationary(state) => state.position,
+        Plan::Moving(events) => {
+            events
+                .iter()
+                .rev()
+                .find(|event| event.micros <= *micros)
+                .unwrap_or(&events[0])
+                .state
+                .position
+        }
+    }
+}
@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use commons::grid::Grid;
+use commons::map::ContainsKeyValue;
+
+use crate::model::ability::Ability;
+use crate::model::construction;
+use crate::model::open;
+use crate::model::piste::{self, Piste};
+use crate::model::satisfaction::Rating;
+use crate::model::scenario::{Goal, Outcome, Progress};
+use crate::services::economy::{self, MICROS_PER_DAY};
+use crate::systems::messenger;
+use crate::utils::scenario::vertical_meters;
+
+const UPDATE_INTERVAL_MICROS: u128 = 10 * 60 * 1_000_000;
+
+pub struct System {
+    last_update_micros: Option<u128>,
+}
+
+pub struct Parameters<'a> {
+    pub micros: &'a u128,
+    pub terrain: &'a Grid<f32>,
+    pub pistes: &'a HashMap<usize, Piste>,
+    pub abilities: &'a HashMap<usize, Ability>,
+    pub open: &'a HashMap<usize, open::Status>,
+    pub constructions: &'a construction::Queue,
+    pub economy: &'a economy::Service,
+    pub rating: &'a Rating,
+    pub scenario: &'a mut Option<Progress>,
+    pub messenger: &'a mut messenger::System,
+}
+
+impl System {
+    pub fn new() -> System {
+        System {
+            last_update_micros: None,
+        }
+    }
+
+    pub fn run(
+        &mut self,
+        Parameters {
+            micros,
+            terrain,
+            pistes,
+            abilities,
+            open,
+            constructions,
+            economy,
+            rating,
+            scenario,
+            messenger,
+        }: Parameters<'_>,
+    ) {
+        let Some(progress) = scenario else { return };
+        if progress.outcome.is_some() {
+            return;
+        }
+
+        let last_update_micros = *self.last_update_micros.get_or_insert(*micros);
+        if micros.saturating_sub(last_update_micros) < UPDATE_INTERVAL_MICROS {
+            return;
+        }
+        self.last_update_micros = Some(*micros);
+
+        for index in 0..progress.goals.len() {
+            let value = match progress.goals[index].goal {
+                Goal::SkiersPerDay(_) => economy.riders_today() as f32,
+                Goal::ExpertRunVerticalMeters(_) => pistes
+                    .iter()
+                    .filter(|(_, piste)| piste.class == piste::Class::Piste)
+                    .filter(|(piste_id, _)| abilities.get(piste_id) == Some(&Ability::Expert))
+                    .filter(|(piste_id, _)| open.contains_key_value(*piste_id, open::Status::Open))
+                    .filter(|(piste_id, _)| !constructions.contains(piste_id))
+                    .map(|(_, piste)| vertical_meters(piste, terrain))
+                    .fold(0.0, f32::max),
+                Goal::Rating(_) => rating.stars,
+            };
+            if progress.record(index, value) {
+                messenger.send(format!(
+                    "Goal achieved: {}",
+                    progress.goals[index].goal.description()
+                ));
+            }
+        }
+
+        match progress.evaluate(micros / MICROS_PER_DAY) {
+            Some(Outcome::Won) => {
+                messenger.send(format!("Scenario complete: {}", progress.name));
+            }
+            Some(Outcome::Lost) => {
+                messenger.send(format!(
+                    "Scenario failed: {} was not finished within {} days",
+                    progress.name, progress.days
+                ));
+            }
+            None => (),
+        }
+    }
+}
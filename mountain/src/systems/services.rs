@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+
+use commons::grid::Grid;
+
+use crate::model::building::Building;
+use crate::model::door::Door;
+use crate::model::ledger::Account;
+use crate::model::needs::Needs;
+use crate::model::reservation::Reservation;
+use crate::model::skiing::Plan;
+use crate::services::economy;
+
+const UPDATE_INTERVAL_MICROS: u128 = 10 * 60 * 1_000_000;
+const MICROS_PER_HOUR: f32 = 3_600_000_000.0;
+
+pub struct System {
+    last_update_micros: Option<u128>,
+}
+
+pub struct Parameters<'a> {
+    pub micros: &'a u128,
+    pub buildings: &'a HashMap<usize, Building>,
+    pub doors: &'a HashMap<usize, Door>,
+    pub plans: &'a mut HashMap<usize, Plan>,
+    pub locations: &'a mut HashMap<usize, usize>,
+    pub targets: &'a mut HashMap<usize, usize>,
+    pub global_targets: &'a mut HashMap<usize, usize>,
+    pub reservations: &'a mut Grid<HashMap<usize, Reservation>>,
+    pub needs: &'a mut HashMap<usize, Needs>,
+    pub visits: &'a mut HashMap<usize, u128>,
+    pub economy: &'a mut economy::Service,
+}
+
+impl System {
+    pub fn new() -> System {
+        System {
+            last_update_micros: None,
+        }
+    }
+
+    pub fn run(
+        &mut self,
+        Parameters {
+            micros,
+            buildings,
+            doors,
+            plans,
+            locations,
+            targets,
+            global_targets,
+            reservations,
+            needs,
+            visits,
+            economy,
+        }: Parameters<'_>,
+    ) {
+        // skiers out on the mountain get hungry and tired
+
+        let last_update_micros = *self.last_update_micros.get_or_insert(*micros);
+        let elapsed_micros = micros.saturating_sub(last_update_micros);
+        if elapsed_micros >= UPDATE_INTERVAL_MICROS {
+            self.last_update_micros = Some(*micros);
+            let hours = elapsed_micros as f32 / MICROS_PER_HOUR;
+            for skier_id in plans.keys() {
+                needs.entry(*skier_id).or_default().grow(hours);
+            }
+        }
+
+        // skiers waiting at the door of the service building they were heading for go inside
+
+        let arrivals = plans
+            .iter()
+            .flat_map(|(skier_id, plan)| {
+                let Plan::Stationary(state) = plan else {
+                    return None;
+                };
+                let door_id = targets.get(skier_id)?;
+                let door = doors.get(door_id)?;
+                if !door.footprint.contains(state.position) {
+                    return None;
+                }
+                let service = buildings.get(&door.building_id)?.kind.service()?;
+                Some((
+                    *skier_id,
+                    *door_id,
+                    door.building_id,
+                    state.position,
+                    service,
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        for (skier_id, door_id, building_id, position, service) in arrivals {
+            plans.remove(&skier_id);
+            reservations[position].remove(&skier_id);
+            locations.insert(skier_id, building_id);
+            targets.remove(&skier_id);
+            if global_targets.get(&skier_id) == Some(&door_id) {
+                global_targets.remove(&skier_id);
+            }
+            visits.insert(skier_id, micros + service.dwell_micros);
+            needs.entry(skier_id).or_default().relieve(&service);
+            economy.record(Account::Services, service.price);
+        }
+    }
+}
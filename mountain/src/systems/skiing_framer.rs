@@ -6,7 +6,7 @@ use commons::grid::Grid;
 use commons::scale::Scale;
 
 use crate::model::frame::{self, Frame};
-use crate::model::skier::Clothes;
+use crate::model::skier::{Clothes, Equipment, Skier};
 use crate::model::skiing::{Event, Plan, State};
 
 const MISSING_COLOR: Rgb<f32> = Rgb::new(1.0, 1.0, 0.0);
@@ -23,26 +23,32 @@ pub fn run(
     terrain: &Grid<f32>,
     micros: &u128,
     plans: &HashMap<usize, Plan>,
+    skiers: &HashMap<usize, Skier>,
     clothes: &HashMap<usize, Clothes<Rgb<f32>>>,
     frames: &mut HashMap<usize, Option<Frame>>,
 ) {
     for (id, plan) in plans {
         let clothes = clothes.get(id).unwrap_or(&MISSING_CLOTHES);
-        if let Some(frame) = frame_from_plan(terrain, micros, plan, clothes) {
+        let equipment = skiers
+            .get(id)
+            .map(|skier| skier.equipment)
+            .unwrap_or(Equipment::Skis);
+        let model = frame::Model::on_snow(&equipment, *clothes);
+        if let Some(frame) = frame_from_plan(terrain, micros, plan, &model) {
             frames.insert(*id, Some(frame));
         };
     }
 }
 
-fn frame_from_plan(
+pub fn frame_from_plan(
     terrain: &Grid<f32>,
     micros: &u128,
     plan: &Plan,
-    clothes: &Clothes<Rgb<f32>>,
+    model: &frame::Model,
 ) -> Option<Frame> {
     match plan {
-        Plan::Stationary(state) => Some(frame_from_skiing_state(terrain, state, clothes)),
-        Plan::Moving(events) => moving_frame(terrain, events, micros, clothes),
+        Plan::Stationary(state) => Some(frame_from_skiing_state(terrain, state, model)),
+        Plan::Moving(events) => moving_frame(terrain, events, micros, model),
     }
 }
 
@@ -53,16 +59,13 @@ fn frame_from_skiing_state(
         travel_direction,
         ..
     }: &State,
-    clothes: &Clothes<Rgb<f32>>,
+    model: &frame::Model,
 ) -> Frame {
     Frame {
         position: xyz(position.x as f32, position.y as f32, terrain[position]),
         yaw: travel_direction.angle(),
         pitch: 0.0,
-        model: frame::Model::Standing {
-            skis: true,
-            clothes: *clothes,
-        },
+        model: *model,
     }
 }
 
@@ -70,14 +73,14 @@ fn moving_frame(
     terrain: &Grid<f32>,
     events: &[Event],
     micros: &u128,
-    clothes: &Clothes<Rgb<f32>>,
+    model: &frame::Model,
 ) -> Option<Frame> {
     let maybe_pair = events.windows(2).find(|maybe_pair| match maybe_pair {
         [from, to] => from.micros <= *micros && to.micros > *micros,
         _ => false,
     });
     match maybe_pair {
-        Some([from, to]) => Some(blend(terrain, micros, clothes, from, to)),
+        Some([from, to]) => Some(blend(terrain, micros, model, from, to)),
         _ => None,
     }
 }
@@ -85,15 +88,15 @@ fn moving_frame(
 fn blend(
     terrain: &Grid<f32>,
     micros: &u128,
-    clothes: &Clothes<Rgb<f32>>,
+    model: &frame::Model,
     from: &Event,
     to: &Event,
 ) -> Frame {
     let scale = Scale::new((from.micros as f32, to.micros as f32), (0.0, 1.0));
     let p = scale.scale(*micros as f32);
     let pitch = slope_pitch(terrain, &from.state.position, &to.state.position);
-    let from = frame_from_skiing_state(terrain, &from.state, clothes);
-    let to = frame_from_skiing_state(terrain, &to.state, clothes);
+    let from = frame_from_skiing_state(terrain, &from.state, model);
+    let to = frame_from_skiing_state(terrain, &to.state, model);
     Frame {
         position: from.position * (1.0 - p) + to.position * p,
         yaw: to.yaw,
@@ -0,0 +1,110 @@
+use std::collections::{HashMap, HashSet};
+
+use commons::geometry::XY;
+use commons::grid::Grid;
+
+use crate::model::skiing::Plan;
+use crate::model::snow::{self, Conditions, Snow};
+use crate::model::weather::Weather;
+use crate::systems::{piste_computer, terrain_artist};
+
+const UPDATE_INTERVAL_MICROS: u128 = 10 * 60 * 1_000_000;
+const MICROS_PER_HOUR: f32 = 3_600_000_000.0;
+
+pub struct System {
+    last_update_micros: Option<u128>,
+    last_traffic_micros: u128,
+    traffic: HashMap<XY<u32>, u32>,
+}
+
+pub struct Parameters<'a> {
+    pub micros: &'a u128,
+    pub terrain: &'a Grid<f32>,
+    pub plans: &'a HashMap<usize, Plan>,
+    pub weather: &'a Weather,
+    pub piste_map: &'a Grid<Option<usize>>,
+    pub snow: &'a mut Grid<Snow>,
+    pub piste_computer: &'a mut piste_computer::System,
+    pub terrain_artist: &'a mut terrain_artist::System,
+}
+
+impl System {
+    pub fn new() -> System {
+        System {
+            last_update_micros: None,
+            last_traffic_micros: 0,
+            traffic: HashMap::default(),
+        }
+    }
+
+    pub fn run(
+        &mut self,
+        Parameters {
+            micros,
+            terrain,
+            plans,
+            weather,
+            piste_map,
+            snow,
+            piste_computer,
+            terrain_artist,
+        }: Parameters<'_>,
+    ) {
+        self.count_traffic(micros, plans);
+
+        let last_update_micros = *self.last_update_micros.get_or_insert(*micros);
+        let elapsed_micros = micros.saturating_sub(last_update_micros);
+        if elapsed_micros < UPDATE_INTERVAL_MICROS {
+            return;
+        }
+        self.last_update_micros = Some(*micros);
+        let hours = elapsed_micros as f32 / MICROS_PER_HOUR;
+
+        let snowfall_meters = weather.sky.snowfall_meters_per_hour() * hours;
+
+        let mut pistes_to_compute = HashSet::new();
+        for position in terrain.iter() {
+            let cell = &mut snow[position];
+            let was_skiable = cell.is_skiable();
+            cell.evolve(&Conditions {
+                temperature_celsius: snow::temperature_celsius(
+                    terrain[position],
+                    snow::SEA_LEVEL_TEMPERATURE_CELSIUS,
+                ),
+                snowfall_meters,
+                passes: self.traffic.get(&position).copied().unwrap_or_default(),
+                hours,
+            });
+            if cell.is_skiable() != was_skiable {
+                if let Some(piste_id) = piste_map[position] {
+                    pistes_to_compute.insert(piste_id);
+                }
+            }
+        }
+        self.traffic.clear();
+
+        // pistes need new costs where cells became skiable or unskiable
+        for piste_id in pistes_to_compute {
+            piste_computer.compute(piste_id);
+        }
+
+        if terrain_artist.is_showing_snow() {
+            terrain_artist.update_whole_overlay();
+        }
+    }
+
+    fn count_traffic(&mut self, micros: &u128, plans: &HashMap<usize, Plan>) {
+        for plan in plans.values() {
+            let Plan::Moving(events) = plan else {
+                continue;
+            };
+            for event in events
+                .iter()
+                .filter(|event| event.micros > self.last_traffic_micros && event.micros <= *micros)
+            {
+                *self.traffic.entry(event.state.position).or_default() += 1;
+            }
+        }
+        self.last_traffic_micros = *micros;
+    }
+}
@@ -0,0 +1,130 @@
+use std::collections::{HashMap, HashSet};
+
+use commons::geometry::{xy, XYRectangle};
+use commons::grid::Grid;
+
+use crate::model::snow::{self, Quality, Snow};
+use crate::model::snowmaking::{self, Cannon, Status, WaterSource};
+use crate::systems::{piste_computer, terrain_artist};
+
+const UPDATE_INTERVAL_MICROS: u128 = 10 * 60 * 1_000_000;
+const MICROS_PER_HOUR: f32 = 3_600_000_000.0;
+
+pub struct System {
+    last_update_micros: Option<u128>,
+}
+
+pub struct Parameters<'a> {
+    pub micros: &'a u128,
+    pub terrain: &'a Grid<f32>,
+    pub piste_map: &'a Grid<Option<usize>>,
+    pub water_sources: &'a HashMap<usize, WaterSource>,
+    pub cannons: &'a mut HashMap<usize, Cannon>,
+    pub snow: &'a mut Grid<Snow>,
+    pub piste_computer: &'a mut piste_computer::System,
+    pub terrain_artist: &'a mut terrain_artist::System,
+}
+
+impl System {
+    pub fn new() -> System {
+        System {
+            last_update_micros: None,
+        }
+    }
+
+    pub fn run(
+        &mut self,
+        Parameters {
+            micros,
+            terrain,
+            piste_map,
+            water_sources,
+            cannons,
+            snow,
+            piste_computer,
+            terrain_artist,
+        }: Parameters<'_>,
+    ) {
+        let last_update_micros = *self.last_update_micros.get_or_insert(*micros);
+        let elapsed_micros = micros.saturating_sub(last_update_micros);
+        if elapsed_micros < UPDATE_INTERVAL_MICROS {
+            return;
+        }
+        self.last_update_micros = Some(*micros);
+        let hours = elapsed_micros as f32 / MICROS_PER_HOUR;
+
+        // cannons only run when it is cold enough and the snow needs topping up
+
+        let mut running_per_source: HashMap<usize, usize> = HashMap::new();
+        for cannon in cannons.values_mut() {
+            let temperature_celsius = snow::temperature_celsius(
+                terrain[cannon.position],
+                snow::SEA_LEVEL_TEMPERATURE_CELSIUS,
+            );
+            cannon.status = if !snowmaking::can_make_snow(temperature_celsius) {
+                Status::TooWarm
+            } else if mean_depth_meters(cannon, snow) >= snowmaking::TARGET_DEPTH_METERS {
+                Status::Idle
+            } else {
+                *running_per_source.entry(cannon.source_id).or_default() += 1;
+                Status::Running { supply: 0.0 }
+            };
+        }
+
+        // running cannons share the water their source can supply
+
+        let mut pistes_to_compute = HashSet::new();
+        for cannon in cannons.values_mut() {
+            let Status::Running { supply } = &mut cannon.status else {
+                continue;
+            };
+            *supply = water_sources
+                .get(&cannon.source_id)
+                .map(|source| source.supply(running_per_source[&cannon.source_id]))
+                .unwrap_or_default();
+
+            let water_m3 = snowmaking::CANNON_WATER_M3_PER_HOUR * *supply * hours;
+            let depth_meters = snowmaking::depth_meters(water_m3);
+            for cell in snowmaking::coverage(&cannon.position, snow.width(), snow.height()) {
+                let cell_snow = &mut snow[cell];
+                let was_skiable = cell_snow.is_skiable();
+                cell_snow.depth_meters += depth_meters;
+                cell_snow.quality = Quality::Packed;
+                if cell_snow.is_skiable() != was_skiable {
+                    if let Some(piste_id) = piste_map[cell] {
+                        pistes_to_compute.insert(piste_id);
+                    }
+                }
+            }
+
+            if terrain_artist.is_showing_snow() {
+                let radius = snowmaking::CANNON_RADIUS_METERS;
+                terrain_artist.update_overlay(XYRectangle {
+                    from: xy(
+                        cannon.position.x.saturating_sub(radius),
+                        cannon.position.y.saturating_sub(radius),
+                    ),
+                    to: xy(
+                        (cannon.position.x + radius).min(snow.width() - 1),
+                        (cannon.position.y + radius).min(snow.height() - 1),
+                    ),
+                });
+            }
+        }
+
+        // pistes need new costs where cells became skiable
+        for piste_id in pistes_to_compute {
+            piste_computer.compute(piste_id);
+        }
+    }
+}
+
+fn mean_depth_meters(cannon: &Cannon, snow: &Grid<Snow>) -> f32 {
+    let (total, count) = snowmaking::coverage(&cannon.position, snow.width(), snow.height())
+        .map(|cell| snow[cell].depth_meters)
+        .fold((0.0, 0), |(total, count), depth| (total + depth, count + 1));
+    if count == 0 {
+        return 0.0;
+    }
+    total / count as f32
+}
@@ -0,0 +1,34 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use commons::grid::Grid;
+use engine::graphics::Graphics;
+
+use crate::draw::snowmaking::{draw_cannon, draw_water_source};
+use crate::model::snowmaking::{Cannon, WaterSource};
+
+pub fn run(
+    graphics: &mut dyn Graphics,
+    water_sources: &HashMap<usize, WaterSource>,
+    cannons: &HashMap<usize, Cannon>,
+    terrain: &Grid<f32>,
+    drawings: &mut HashMap<usize, usize>,
+) {
+    for (source_id, source) in water_sources {
+        if let Entry::Vacant(cell) = drawings.entry(*source_id) {
+            if let Ok(index) = graphics.create_triangles() {
+                draw_water_source(graphics, &index, source, terrain);
+                cell.insert(index);
+            }
+        }
+    }
+
+    for (cannon_id, cannon) in cannons {
+        if let Entry::Vacant(cell) = drawings.entry(*cannon_id) {
+            if let Ok(index) = graphics.create_triangles() {
+                draw_cannon(graphics, &index, cannon, terrain);
+                cell.insert(index);
+            }
+        }
+    }
+}
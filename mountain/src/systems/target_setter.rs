@@ -4,13 +4,18 @@ use commons::map::ContainsKeyValue;
 
 use crate::model::costs::Costs;
 use crate::model::door::Door;
+use crate::model::group::Group;
 use crate::model::open;
+use crate::model::satisfaction::{Satisfaction, Thought};
 use crate::model::skier::Skier;
 use crate::model::skiing::{Plan, State};
 use crate::network::global::GLOBAL_COST_DIVISOR;
+use crate::utils::group;
 
 pub struct Parameters<'a> {
+    pub micros: &'a u128,
     pub skiers: &'a HashMap<usize, Skier>,
+    pub groups: &'a HashMap<usize, Group>,
     pub plans: &'a HashMap<usize, Plan>,
     pub locations: &'a HashMap<usize, usize>,
     pub doors: &'a HashMap<usize, Door>,
@@ -19,11 +24,14 @@ pub struct Parameters<'a> {
     pub open: &'a HashMap<usize, open::Status>,
     pub global_targets: &'a mut HashMap<usize, usize>,
     pub targets: &'a mut HashMap<usize, usize>,
+    pub satisfaction: &'a mut HashMap<usize, Satisfaction>,
 }
 
 pub fn run(
     Parameters {
+        micros,
         skiers,
+        groups,
         plans,
         locations,
         doors,
@@ -32,6 +40,7 @@ pub fn run(
         open,
         global_targets,
         targets,
+        satisfaction,
     }: Parameters<'_>,
 ) {
     let default_global_costs = HashMap::default();
@@ -43,11 +52,7 @@ pub fn run(
             continue;
         };
 
-        let Some(Skier {
-            ability: skier_ability,
-            ..
-        }) = skiers.get(skier_id)
-        else {
+        let Some(skier_ability) = group::ability(skier_id, skiers, groups) else {
             continue;
         };
 
@@ -66,13 +71,17 @@ pub fn run(
         // check skier can return to any building from global target
 
         if !global_costs
-            .targets_reachable_from_node(global_target, skier_ability)
+            .targets_reachable_from_node(global_target, &skier_ability)
             .any(|(target, _)| door_ids.contains(target))
         {
             println!(
                 "Skier {} cannot get home from global target {}",
                 skier_id, global_target
             );
+            satisfaction
+                .entry(*skier_id)
+                .or_default()
+                .think(Thought::CannotGetHome, micros);
             targets.remove(skier_id);
             global_targets.remove(skier_id);
             return;
@@ -81,13 +90,13 @@ pub fn run(
         // find best local target for global target
 
         let costs_to_global_target = global_costs
-            .costs(*global_target, *skier_ability)
+            .costs(*global_target, skier_ability)
             .unwrap_or(&default_global_costs);
 
         let stationary_state = state.stationary();
 
         let target = costs
-            .targets_reachable_from_node(&stationary_state, skier_ability)
+            .targets_reachable_from_node(&stationary_state, &skier_ability)
             .filter(|(&target, _)| open.contains_key_value(target, open::Status::Open))
             .flat_map(|(target, cost)| {
                 costs_to_global_target
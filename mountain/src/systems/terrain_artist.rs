@@ -8,22 +8,40 @@ use engine::graphics::Graphics;
 
 use crate::draw::terrain::Drawing;
 use crate::model::ability::Ability;
+use crate::model::construction;
 use crate::model::selection::Selection;
+use crate::model::snow::{Quality, Snow};
+use crate::model::weather::{self, Weather};
 use crate::utils::ability::cell_ability;
 
 pub const CLEAR: Rgba<u8> = Rgba::new(0, 0, 0, 0);
+const GROOMED_FADE_MICROS: u128 = 24 * 60 * 60 * 1_000_000;
 
 pub struct System {
     drawing: Option<Drawing>,
     overlay_updates: Vec<XYRectangle<u32>>,
     show_pistes: bool,
+    show_snow: bool,
+    show_grooming: bool,
     colors: Colors,
 }
 
 pub struct Colors {
     pub piste: AbilityColors,
     pub highlight: AbilityColors,
+    pub snow: SnowColors,
+    pub groomed: Rgba<u8>,
+    pub construction: Rgba<u8>,
     pub cliff: Rgba<u8>,
+    pub fog: Rgba<u8>,
+}
+
+pub struct SnowColors {
+    pub powder: Rgba<u8>,
+    pub packed: Rgba<u8>,
+    pub icy: Rgba<u8>,
+    pub slush: Rgba<u8>,
+    pub bare: Rgba<u8>,
 }
 
 pub struct AbilityColors {
@@ -63,9 +81,14 @@ impl Colors {
         piste_map: &Grid<Option<usize>>,
         highlights: &HashSet<usize>,
         abilities: &HashMap<usize, Ability>,
+        constructions: &construction::Queue,
     ) -> Option<Rgba<u8>> {
         let piste_id = piste_map[position]?;
 
+        if constructions.contains(&piste_id) {
+            return Some(self.construction);
+        }
+
         let colors = if highlights.contains(&piste_id) {
             &self.highlight
         } else {
@@ -83,6 +106,47 @@ impl Colors {
         Some(color)
     }
 
+    fn snow_color(&self, position: &XY<u32>, snow: &Grid<Snow>) -> Rgba<u8> {
+        let cell = snow[position];
+        if !cell.is_skiable() {
+            return self.snow.bare;
+        }
+        match cell.quality {
+            Quality::Powder => self.snow.powder,
+            Quality::Packed => self.snow.packed,
+            Quality::Icy => self.snow.icy,
+            Quality::Slush => self.snow.slush,
+        }
+    }
+
+    fn groomed_color(
+        &self,
+        position: &XY<u32>,
+        groomed: &Grid<Option<u128>>,
+        micros: &u128,
+    ) -> Option<Rgba<u8>> {
+        let age = micros.saturating_sub(groomed[position]?);
+        if age >= GROOMED_FADE_MICROS {
+            return None;
+        }
+        let freshness = 1.0 - age as f32 / GROOMED_FADE_MICROS as f32;
+        Some(Rgba {
+            a: (self.groomed.a as f32 * freshness).round() as u8,
+            ..self.groomed
+        })
+    }
+
+    fn fog_color(&self, weather: &Weather) -> Option<Rgba<u8>> {
+        if !weather.is_foggy() {
+            return None;
+        }
+        let thickness = 1.0 - weather.visibility_meters / weather::FOG_VISIBILITY_METERS;
+        Some(Rgba {
+            a: (self.fog.a as f32 * thickness).round() as u8,
+            ..self.fog
+        })
+    }
+
     fn cliff_color(&self, xy: &XY<u32>, terrain: &Grid<f32>) -> Option<Rgba<u8>> {
         if cell_ability(terrain, xy).is_none() {
             Some(self.cliff)
@@ -95,8 +159,13 @@ impl Colors {
 pub struct Parameters<'a> {
     pub terrain: &'a Grid<f32>,
     pub piste_map: &'a Grid<Option<usize>>,
+    pub snow: &'a Grid<Snow>,
+    pub groomed: &'a Grid<Option<u128>>,
+    pub micros: &'a u128,
+    pub weather: &'a Weather,
     pub highlights: &'a HashSet<usize>,
     pub abilities: &'a HashMap<usize, Ability>,
+    pub constructions: &'a construction::Queue,
     pub selection: &'a Selection,
     pub graphics: &'a mut dyn Graphics,
 }
@@ -107,6 +176,8 @@ impl System {
             drawing: None,
             overlay_updates: vec![],
             show_pistes: true,
+            show_snow: false,
+            show_grooming: false,
             colors,
         }
     }
@@ -139,14 +210,35 @@ impl System {
         self.show_pistes = !self.show_pistes;
     }
 
+    pub fn is_showing_snow(&self) -> bool {
+        self.show_snow
+    }
+
+    pub fn toggle_show_snow(&mut self) {
+        self.show_snow = !self.show_snow;
+    }
+
+    pub fn is_showing_grooming(&self) -> bool {
+        self.show_grooming
+    }
+
+    pub fn toggle_show_grooming(&mut self) {
+        self.show_grooming = !self.show_grooming;
+    }
+
     pub fn run(
         &mut self,
         Parameters {
             graphics,
             terrain,
             piste_map,
+            snow,
+            groomed,
+            micros,
+            weather,
             highlights,
             abilities,
+            constructions,
             selection,
         }: Parameters<'_>,
     ) {
@@ -159,15 +251,35 @@ impl System {
                 image[position] = self
                     .colors
                     .selection_color(&position, terrain, selection)
+                    .or_else(|| {
+                        if self.show_grooming {
+                            self.colors.groomed_color(&position, groomed, micros)
+                        } else {
+                            None
+                        }
+                    })
                     .or_else(|| {
                         if self.show_pistes {
-                            self.colors
-                                .piste_color(&position, piste_map, highlights, abilities)
+                            self.colors.piste_color(
+                                &position,
+                                piste_map,
+                                highlights,
+                                abilities,
+                                constructions,
+                            )
+                        } else {
+                            None
+                        }
+                    })
+                    .or_else(|| {
+                        if self.show_snow {
+                            Some(self.colors.snow_color(&position, snow))
                         } else {
                             None
                         }
                     })
                     .or_else(|| self.colors.cliff_color(&position, terrain))
+                    .or_else(|| self.colors.fog_color(weather))
                     .unwrap_or(CLEAR);
             }
 
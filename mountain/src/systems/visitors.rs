@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use crate::controllers::building_builder::spawn_skiers;
+use crate::model::building::Building;
+use crate::model::door::Door;
+use crate::model::group::Group;
+use crate::model::satisfaction::Rating;
+use crate::model::skier::Skier;
+use crate::model::skiing::Plan;
+use crate::model::time_of_day;
+use crate::model::visitors::{self, ARRIVAL_MICROS};
+use crate::model::weather::Weather;
+use crate::services::id_allocator;
+use crate::systems::messenger;
+use crate::utils::skiers::is_at_home_door;
+
+pub struct System {
+    last_micros: Option<u128>,
+}
+
+pub struct Parameters<'a> {
+    pub micros: &'a u128,
+    pub buildings: &'a HashMap<usize, Building>,
+    pub doors: &'a HashMap<usize, Door>,
+    pub plans: &'a HashMap<usize, Plan>,
+    pub targets: &'a HashMap<usize, usize>,
+    pub weather: &'a Weather,
+    pub rating: &'a Rating,
+    pub id_allocator: &'a mut id_allocator::Service,
+    pub locations: &'a mut HashMap<usize, usize>,
+    pub skiers: &'a mut HashMap<usize, Skier>,
+    pub groups: &'a mut HashMap<usize, Group>,
+    pub messenger: &'a mut messenger::System,
+}
+
+impl System {
+    pub fn new() -> System {
+        System { last_micros: None }
+    }
+
+    /// Brings day visitors in each morning, returning the visitors who have gone home
+    pub fn run(
+        &mut self,
+        Parameters {
+            micros,
+            buildings,
+            doors,
+            plans,
+            targets,
+            weather,
+            rating,
+            id_allocator,
+            locations,
+            skiers,
+            groups,
+            messenger,
+        }: Parameters<'_>,
+    ) -> Vec<usize> {
+        let last_micros = self.last_micros.replace(*micros).unwrap_or(*micros);
+
+        // arrivals
+
+        if time_of_day::passed(ARRIVAL_MICROS, &last_micros, micros) {
+            for (building_id, building) in buildings
+                .iter()
+                .filter(|(_, building)| building.kind.is_terminal())
+                .filter(|(_, building)| !building.under_construction)
+            {
+                let arrivals = visitors::arrivals(
+                    visitors::capacity(&building.kind, &building.footprint),
+                    rating,
+                    weather.appeal(),
+                );
+                if arrivals == 0 {
+                    continue;
+                }
+                spawn_skiers(
+                    building_id,
+                    arrivals,
+                    id_allocator,
+                    locations,
+                    skiers,
+                    groups,
+                );
+                messenger.send(format!(
+                    "{} day visitors arrived at {} {}",
+                    arrivals,
+                    building.kind.name(),
+                    building_id
+                ));
+            }
+        }
+
+        // departures, with any visitors still out at the end of the day going home regardless
+
+        let end_of_day = time_of_day::passed(0, &last_micros, micros);
+        let is_evening = visitors::is_evening(micros);
+
+        skiers
+            .iter()
+            .filter(|(_, skier)| {
+                buildings
+                    .get(&skier.hotel_id)
+                    .is_some_and(|building| building.kind.is_terminal())
+            })
+            .filter(|(skier_id, skier)| {
+                end_of_day
+                    || (is_evening && locations.get(skier_id) == Some(&skier.hotel_id))
+                    || is_at_home_door(skier_id, skier, plans, targets, doors)
+            })
+            .map(|(skier_id, _)| *skier_id)
+            .collect()
+    }
+}
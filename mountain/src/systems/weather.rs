@@ -0,0 +1,103 @@
+use std::collections::{HashMap, HashSet};
+
+use commons::map::ContainsKeyValue;
+
+use crate::model::lift::Lift;
+use crate::model::open;
+use crate::model::reliability::Reliability;
+use crate::model::weather::{Sky, Weather};
+use crate::services::weather;
+use crate::systems::{global_computer, messenger, terrain_artist};
+
+pub struct Parameters<'a> {
+    pub micros: &'a u128,
+    pub lifts: &'a HashMap<usize, Lift>,
+    pub reliabilities: &'a HashMap<usize, Reliability>,
+    pub weather: &'a mut weather::Service,
+    pub wind_holds: &'a mut HashSet<usize>,
+    pub open: &'a mut HashMap<usize, open::Status>,
+    pub global_computer: &'a mut global_computer::System,
+    pub terrain_artist: &'a mut terrain_artist::System,
+    pub messenger: &'a mut messenger::System,
+}
+
+pub fn run(
+    Parameters {
+        micros,
+        lifts,
+        reliabilities,
+        weather,
+        wind_holds,
+        open,
+        global_computer,
+        terrain_artist,
+        messenger,
+    }: Parameters<'_>,
+) {
+    let previous = *weather.current();
+    if weather.update(micros) {
+        announce(&previous, weather.current(), messenger);
+        if previous.is_foggy() || weather.current().is_foggy() {
+            terrain_artist.update_whole_overlay();
+        }
+    }
+    let current = weather.current();
+
+    wind_holds.retain(|lift_id| lifts.contains_key(lift_id));
+
+    for (lift_id, lift) in lifts {
+        let Some(max_wind) = lift.lift_type.max_wind_meters_per_second() else {
+            continue;
+        };
+        let elevation = lift
+            .segments
+            .iter()
+            .map(|segment| segment.from.z.max(segment.to.z))
+            .fold(0.0, f32::max);
+        let is_too_windy = current.wind_meters_per_second_at(elevation) > max_wind;
+
+        if is_too_windy {
+            if !open.contains_key_value(lift_id, open::Status::Open) {
+                continue;
+            }
+            // skiers on board are carried to the top while queues are sent away
+            for pick_up in lift.pick_ups() {
+                if open.contains_key_value(pick_up.id, open::Status::Open) {
+                    open.insert(pick_up.id, open::Status::Closing);
+                }
+            }
+            if wind_holds.insert(*lift_id) {
+                messenger.send(format!("Lift {} is on wind hold", lift_id));
+                global_computer.update();
+            }
+        } else if wind_holds.remove(lift_id) {
+            let is_stalled = reliabilities
+                .get(lift_id)
+                .map(|reliability| reliability.is_stalled())
+                .unwrap_or_default();
+            if open.contains_key_value(lift_id, open::Status::Open) && !is_stalled {
+                for pick_up in lift.pick_ups() {
+                    open.insert(pick_up.id, open::Status::Open);
+                }
+                messenger.send(format!("Lift {} has reopened after wind hold", lift_id));
+                global_computer.update();
+            }
+        }
+    }
+}
+
+fn announce(previous: &Weather, current: &Weather, messenger: &mut messenger::System) {
+    match (previous.sky, current.sky) {
+        (from, to) if from == to => (),
+        (_, Sky::Storm) => messenger.send("A storm has arrived"),
+        (Sky::Storm, _) => messenger.send("The storm has passed"),
+        (_, Sky::Snowing) => messenger.send("It has started snowing"),
+        (Sky::Snowing, _) => messenger.send("It has stopped snowing"),
+        _ => (),
+    }
+    match (previous.is_foggy(), current.is_foggy()) {
+        (false, true) => messenger.send("Fog has rolled in"),
+        (true, false) => messenger.send("The fog has lifted"),
+        _ => (),
+    }
+}
@@ -0,0 +1,38 @@
+use std::time::Instant;
+
+use engine::graphics::Graphics;
+
+use crate::draw::snowfall::{Drawing, MAX_FLAKES};
+use crate::model::weather::{Sky, Weather};
+
+pub struct System {
+    drawing: Option<Drawing>,
+    baseline: Instant,
+}
+
+impl System {
+    pub fn new() -> System {
+        System {
+            drawing: None,
+            baseline: Instant::now(),
+        }
+    }
+
+    pub fn init(&mut self, graphics: &mut dyn Graphics) {
+        self.drawing = Some(Drawing::init(graphics));
+    }
+
+    pub fn run(&mut self, weather: &Weather, graphics: &mut dyn Graphics) {
+        let Some(drawing) = self.drawing.as_mut() else {
+            return;
+        };
+        let flakes = match weather.sky {
+            Sky::Sunny | Sky::Overcast => 0,
+            Sky::Snowing => MAX_FLAKES * 3 / 8,
+            Sky::Storm => MAX_FLAKES,
+        };
+        // flakes fall in real time so they keep a natural pace at any game speed
+        let seconds = self.baseline.elapsed().as_secs_f32();
+        drawing.draw(graphics, flakes, seconds, weather.wind_meters_per_second);
+    }
+}
@@ -53,8 +53,8 @@ pub struct RevolveEvent {
 
 #[derive(Debug, PartialEq)]
 pub enum RevolveAction {
-    PickUp,
-    DropOff,
+    PickUp(usize),
+    DropOff(usize),
 }
 
 pub fn revolve(lift: &Lift, cars: &[&Car], meters: f32) -> RevolveResult {
@@ -98,18 +98,18 @@ fn revolve_car(lift: &Lift, car_index: &usize, car: &Car, revolve_meters: f32) -
 
     loop {
         if distance_from_segment_start_meters == 0.0 {
-            if segment == lift.drop_off.segment {
+            for drop_off in lift.drop_offs().filter(|portal| portal.segment == segment) {
                 events.push(RevolveEvent {
                     revolve_meters: revolve_meters - residual_meters,
                     car_index: *car_index,
-                    action: RevolveAction::DropOff,
+                    action: RevolveAction::DropOff(drop_off.id),
                 });
             }
-            if segment == lift.pick_up.segment {
+            for pick_up in lift.pick_ups().filter(|portal| portal.segment == segment) {
                 events.push(RevolveEvent {
                     revolve_meters: revolve_meters - residual_meters,
                     car_index: *car_index,
-                    action: RevolveAction::PickUp,
+                    action: RevolveAction::PickUp(pick_up.id),
                 });
             }
         }
@@ -140,6 +140,7 @@ mod tests {
 
     use crate::model::direction::Direction;
     use crate::model::lift;
+    use crate::model::lift_type::LiftType;
     use crate::model::skiing::State;
 
     use super::*;
@@ -222,6 +223,7 @@ mod tests {
     fn test_revolve() {
         // given
         let lift = Lift {
+            lift_type: LiftType::Chairlift,
             pick_up: lift::Portal {
                 id: 0,
                 segment: 0,
@@ -232,7 +234,7 @@ mod tests {
                 },
             },
             drop_off: lift::Portal {
-                id: 0,
+                id: 1,
                 segment: 1,
                 state: State {
                     position: xy(1, 0),
@@ -241,6 +243,7 @@ mod tests {
                 },
             },
             segments: segments(&[xyz(0.0, 0.0, 0.0), xyz(1.0, 0.0, 0.0), xyz(0.0, 0.0, 0.0)]),
+            mid_stations: vec![],
             carousel_id: 0,
             buildings_id: 0,
         };
@@ -288,17 +291,17 @@ mod tests {
                 RevolveEvent {
                     revolve_meters: 0.0,
                     car_index: 0,
-                    action: RevolveAction::PickUp,
+                    action: RevolveAction::PickUp(0),
                 },
                 RevolveEvent {
                     revolve_meters: 0.33,
                     car_index: 1,
-                    action: RevolveAction::DropOff,
+                    action: RevolveAction::DropOff(1),
                 },
                 RevolveEvent {
                     revolve_meters: 0.67,
                     car_index: 2,
-                    action: RevolveAction::PickUp,
+                    action: RevolveAction::PickUp(0),
                 },
             ],
         };
@@ -310,6 +313,7 @@ mod tests {
     fn test_revolve_action_only_generated_once() {
         // given
         let lift = Lift {
+            lift_type: LiftType::Chairlift,
             pick_up: lift::Portal {
                 id: 0,
                 segment: 0,
@@ -320,7 +324,7 @@ mod tests {
                 },
             },
             drop_off: lift::Portal {
-                id: 0,
+                id: 1,
                 segment: 1,
                 state: State {
                     position: xy(1, 0),
@@ -329,6 +333,7 @@ mod tests {
                 },
             },
             segments: segments(&[xyz(0.0, 0.0, 0.0), xyz(1.0, 0.0, 0.0), xyz(0.0, 0.0, 0.0)]),
+            mid_stations: vec![],
             carousel_id: 0,
             buildings_id: 0,
         };
@@ -369,8 +374,82 @@ mod tests {
             events: vec![RevolveEvent {
                 revolve_meters: 0.0,
                 car_index: 0,
-                action: RevolveAction::DropOff,
+                action: RevolveAction::DropOff(1),
+            }],
+        };
+        compare_results(&result, &expected);
+    }
+
+    #[test]
+    fn test_revolve_mid_station() {
+        // given
+        let state = State {
+            position: xy(0, 0),
+            travel_direction: Direction::North,
+            velocity: 0,
+        };
+        let lift = Lift {
+            lift_type: LiftType::Chairlift,
+            pick_up: lift::Portal {
+                id: 0,
+                segment: 0,
+                state,
+            },
+            drop_off: lift::Portal {
+                id: 1,
+                segment: 2,
+                state,
+            },
+            segments: segments(&[
+                xyz(0.0, 0.0, 0.0),
+                xyz(1.0, 0.0, 0.0),
+                xyz(2.0, 0.0, 0.0),
+                xyz(1.0, 0.0, 0.0),
+                xyz(0.0, 0.0, 0.0),
+            ]),
+            mid_stations: vec![lift::MidStation {
+                drop_off: lift::Portal {
+                    id: 2,
+                    segment: 1,
+                    state,
+                },
+                pick_up: lift::Portal {
+                    id: 3,
+                    segment: 1,
+                    state,
+                },
+            }],
+            carousel_id: 0,
+            buildings_id: 0,
+        };
+        let cars = vec![&Car {
+            carousel_id: 7,
+            segment: 0,
+            distance_from_start_meters: 0.5,
+        }];
+
+        // when
+        let result = revolve(&lift, &cars, 1.0);
+
+        // then
+        let expected = RevolveResult {
+            cars: vec![Car {
+                carousel_id: 7,
+                segment: 1,
+                distance_from_start_meters: 0.5,
             }],
+            events: vec![
+                RevolveEvent {
+                    revolve_meters: 0.5,
+                    car_index: 0,
+                    action: RevolveAction::DropOff(2),
+                },
+                RevolveEvent {
+                    revolve_meters: 0.5,
+                    car_index: 0,
+                    action: RevolveAction::PickUp(3),
+                },
+            ],
         };
         compare_results(&result, &expected);
     }
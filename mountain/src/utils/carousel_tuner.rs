@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use crate::model::carousel::{Car, Carousel};
+use crate::model::frame::Frame;
+use crate::model::lift::Lift;
+use crate::network::velocity_encoding::{encode_velocity, VELOCITY_LEVELS};
+use crate::services::id_allocator;
+use crate::utils::carousel::create_cars;
+use crate::{Components, Systems};
+
+pub fn tune_carousel(
+    carousel_id: &usize,
+    velocity: f32,
+    car_interval_meters: f32,
+    components: &mut Components,
+    systems: &mut Systems,
+) {
+    let Components {
+        lifts,
+        carousels,
+        cars,
+        frames,
+        parents,
+        children,
+        services,
+        ..
+    } = components;
+
+    tune(
+        carousel_id,
+        velocity,
+        car_interval_meters,
+        Parameters {
+            lifts,
+            carousels,
+            cars,
+            frames,
+            parents,
+            children,
+            id_allocator: &mut services.id_allocator,
+        },
+    );
+
+    // lift travel and queue costs depend on carousel
+
+    systems.global_computer.update();
+}
+
+struct Parameters<'a> {
+    lifts: &'a mut HashMap<usize, Lift>,
+    carousels: &'a mut HashMap<usize, Carousel>,
+    cars: &'a mut HashMap<usize, Car>,
+    frames: &'a mut HashMap<usize, Option<Frame>>,
+    parents: &'a mut HashMap<usize, usize>,
+    children: &'a mut HashMap<usize, Vec<usize>>,
+    id_allocator: &'a mut id_allocator::Service,
+}
+
+fn tune(
+    carousel_id: &usize,
+    velocity: f32,
+    car_interval_meters: f32,
+    Parameters {
+        lifts,
+        carousels,
+        cars,
+        frames,
+        parents,
+        children,
+        id_allocator,
+    }: Parameters<'_>,
+) {
+    let Some(carousel) = carousels.get_mut(carousel_id) else {
+        return;
+    };
+    let Some(lift) = lifts.get_mut(&carousel.lift_id) else {
+        return;
+    };
+
+    // skiers leave at the speed of the lift
+
+    carousel.velocity = velocity;
+    let drop_off_velocity = encode_velocity(&velocity).unwrap_or(VELOCITY_LEVELS - 1);
+    lift.drop_off.state.velocity = drop_off_velocity;
+    for mid_station in lift.mid_stations.iter_mut() {
+        mid_station.drop_off.state.velocity = drop_off_velocity;
+    }
+
+    // replace cars
+
+    if carousel.car_interval_meters != car_interval_meters {
+        carousel.car_interval_meters = car_interval_meters;
+
+        for car_id in carousel.car_ids.drain(..) {
+            cars.remove(&car_id);
+            frames.remove(&car_id);
+            parents.remove(&car_id);
+        }
+
+        let new_cars = create_cars(*carousel_id, &lift.segments, &car_interval_meters);
+        carousel.car_ids = (0..new_cars.len())
+            .map(|_| id_allocator.next_id())
+            .collect();
+
+        carousel.car_ids.iter().zip(new_cars).for_each(|(id, car)| {
+            cars.insert(*id, car);
+            parents.insert(*id, *carousel_id);
+        });
+        children.insert(*carousel_id, carousel.car_ids.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::almost_eq::assert_almost_eq;
+    use commons::geometry::{xy, xyz};
+
+    use crate::model::carousel::CarClass;
+    use crate::model::direction::Direction;
+    use crate::model::lift::{MidStation, Portal, Segment};
+    use crate::model::lift_type::LiftType;
+    use crate::model::skiing::State;
+
+    use super::*;
+
+    fn portal(id: usize, segment: usize) -> Portal {
+        Portal {
+            id,
+            segment,
+            state: State {
+                position: xy(0, 0),
+                velocity: 0,
+                travel_direction: Direction::East,
+            },
+        }
+    }
+
+    #[test]
+    fn test_tune_carousel() {
+        // given
+        let mut lifts = HashMap::from([(
+            0,
+            Lift {
+                lift_type: LiftType::Chairlift,
+                segments: vec![
+                    Segment::new(xyz(0.0, 0.0, 0.0), xyz(100.0, 0.0, 0.0)),
+                    Segment::new(xyz(100.0, 0.0, 0.0), xyz(0.0, 0.0, 0.0)),
+                ],
+                pick_up: portal(1, 0),
+                drop_off: portal(2, 1),
+                mid_stations: vec![MidStation {
+                    drop_off: portal(3, 0),
+                    pick_up: portal(4, 0),
+                }],
+                carousel_id: 5,
+                buildings_id: 6,
+            },
+        )]);
+        let mut carousels = HashMap::from([(
+            5,
+            Carousel {
+                lift_id: 0,
+                velocity: 2.0,
+                car_interval_meters: 10.0,
+                car_class: CarClass::Quad,
+                car_ids: vec![7],
+            },
+        )]);
+        let mut cars = HashMap::from([(
+            7,
+            Car {
+                carousel_id: 5,
+                segment: 0,
+                distance_from_start_meters: 0.0,
+            },
+        )]);
+        let mut frames = HashMap::from([(7, None)]);
+        let mut parents = HashMap::from([(7, 5)]);
+        let mut children = HashMap::from([(5, vec![7])]);
+        let mut id_allocator = id_allocator::Service::new();
+
+        // when
+        tune(
+            &5,
+            4.0,
+            50.0,
+            Parameters {
+                lifts: &mut lifts,
+                carousels: &mut carousels,
+                cars: &mut cars,
+                frames: &mut frames,
+                parents: &mut parents,
+                children: &mut children,
+                id_allocator: &mut id_allocator,
+            },
+        );
+
+        // then
+        let carousel = &carousels[&5];
+        assert_eq!(carousel.velocity, 4.0);
+        assert_eq!(carousel.car_interval_meters, 50.0);
+        assert_eq!(carousel.car_ids.len(), 4);
+        assert!(!carousel.car_ids.contains(&7));
+        assert!(!frames.contains_key(&7));
+        assert_eq!(cars.len(), 4);
+        let mut distances = carousel
+            .car_ids
+            .iter()
+            .map(|car_id| {
+                let car = &cars[car_id];
+                assert_eq!(parents[car_id], 5);
+                car.segment as f32 * 100.0 + car.distance_from_start_meters
+            })
+            .collect::<Vec<_>>();
+        distances.sort_by(f32::total_cmp);
+        for (distance, expected) in distances.iter().zip([0.0, 50.0, 100.0, 150.0]) {
+            assert_almost_eq(*distance, expected);
+        }
+        assert_eq!(children[&5], carousel.car_ids);
+
+        let drop_off_velocity = encode_velocity(&4.0).unwrap();
+        let lift = &lifts[&0];
+        assert_eq!(lift.drop_off.state.velocity, drop_off_velocity);
+        assert_eq!(
+            lift.mid_stations[0].drop_off.state.velocity,
+            drop_off_velocity
+        );
+        assert_eq!(lift.pick_up.state.velocity, 0);
+    }
+}
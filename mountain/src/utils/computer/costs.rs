@@ -6,7 +6,9 @@ use crate::model::direction::DIRECTIONS;
 use crate::model::exit::Exit;
 use crate::model::piste::{self, Piste};
 use crate::model::reservation::Reservation;
+use crate::model::skier::Equipment;
 use crate::model::skiing::State;
+use crate::model::snow::Snow;
 use crate::network::skiing::{SkiingNetwork, StationaryNetwork};
 use commons::geometry::XY;
 use commons::grid::Grid;
@@ -17,6 +19,7 @@ pub fn compute_piste(
     piste_id: &usize,
     pistes: &HashMap<usize, Piste>,
     terrain: &Grid<f32>,
+    snow: &Grid<Snow>,
     exits: &HashMap<usize, Exit>,
     reservations: &Grid<HashMap<usize, Reservation>>,
     costs: &mut HashMap<usize, Costs<State>>,
@@ -39,13 +42,14 @@ pub fn compute_piste(
         )
         .collect::<Vec<_>>();
 
-    let piste_costs = compute_costs(terrain, piste, &exits, reservations);
+    let piste_costs = compute_costs(terrain, snow, piste, &exits, reservations);
 
     costs.insert(*piste_id, piste_costs);
 }
 
 fn compute_costs(
     terrain: &Grid<f32>,
+    snow: &Grid<Snow>,
     piste: &Piste,
     exits: &[(&usize, &Exit)],
     reservations: &Grid<HashMap<usize, Reservation>>,
@@ -68,8 +72,11 @@ fn compute_costs(
         for ability in ABILITIES {
             let network = SkiingNetwork {
                 terrain,
+                snow,
                 class: piste.class,
                 ability,
+                // snowboarders share the costs worked out for skiers
+                equipment: Equipment::Skis,
                 is_accessible_fn: &|position| {
                     (terrain[position] >= min_z || piste.class == piste::Class::Path)
                         && !reservations[position]
@@ -9,6 +9,7 @@ use crate::model::costs::Costs;
 use crate::model::entrance::Entrance;
 use crate::model::exit::Exit;
 use crate::model::lift::Lift;
+use crate::model::lift_queue::LiftQueue;
 use crate::model::open;
 use crate::model::skiing::State;
 use crate::network::global::GlobalNetwork;
@@ -16,6 +17,7 @@ use crate::network::global::GlobalNetwork;
 pub struct Parameters<'a> {
     pub lifts: &'a HashMap<usize, Lift>,
     pub carousels: &'a HashMap<usize, Carousel>,
+    pub lift_queues: &'a HashMap<usize, LiftQueue>,
     pub entrances: &'a HashMap<usize, Entrance>,
     pub exits: &'a HashMap<usize, Exit>,
     pub costs: &'a HashMap<usize, Costs<State>>,
@@ -28,6 +30,7 @@ pub fn compute_global_costs(
     Parameters {
         lifts,
         carousels,
+        lift_queues,
         entrances,
         exits,
         costs,
@@ -40,7 +43,7 @@ pub fn compute_global_costs(
 
     let pick_up_to_lift = &lifts
         .iter()
-        .map(|(lift_id, Lift { pick_up, .. })| (pick_up.id, *lift_id))
+        .flat_map(|(lift_id, lift)| lift.pick_ups().map(|pick_up| (pick_up.id, *lift_id)))
         .collect::<HashMap<_, _>>();
 
     for ability in ABILITIES {
@@ -48,6 +51,7 @@ pub fn compute_global_costs(
             lifts,
             pick_up_to_lift,
             carousels,
+            lift_queues,
             entrances,
             open,
             costs,
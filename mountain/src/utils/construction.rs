@@ -0,0 +1,250 @@
+use commons::geometry::{xyz, XYRectangle, XY};
+use commons::grid::{Grid, CORNERS};
+
+use crate::controllers::building_builder::HEIGHT_INTERVAL;
+use crate::gui::format_money;
+use crate::model::ledger::Account;
+use crate::model::lift_building::{LiftBuildingClass, LiftBuildings};
+use crate::model::piste;
+use crate::model::tree::Tree;
+use crate::services::economy;
+use crate::systems::messenger;
+use crate::utils::ability::cell_exposure;
+
+/// Share of the price paid back when something is demolished
+pub const REFUND_RATIO: f32 = 0.5;
+
+pub const PISTE_CELL_PRICE: f32 = 4.0;
+pub const PATH_CELL_PRICE: f32 = 2.0;
+/// Extra multiple of the cell price for each unit of exposure
+pub const EXPOSURE_PRICE_FACTOR: f32 = 2.0;
+pub const TREE_CLEARING_PRICE: f32 = 40.0;
+pub const FLOOR_PRICE_PER_SQUARE_METER: f32 = 25.0;
+pub const GATE_PRICE: i64 = 1_000;
+pub const DOOR_PRICE: i64 = 500;
+
+/// Value of the work a crew gets through in an hour, so pricier works take longer
+pub const WORK_PRICE_PER_HOUR: u128 = 10_000;
+pub const MIN_WORK_MICROS: u128 = 30 * 60 * 1_000_000;
+const MICROS_PER_HOUR: u128 = 3_600_000_000;
+
+pub fn piste_cell_price(
+    class: &piste::Class,
+    terrain: &Grid<f32>,
+    trees: &Grid<Option<Tree>>,
+    cell: &XY<u32>,
+) -> f32 {
+    let base = match class {
+        piste::Class::Piste => PISTE_CELL_PRICE,
+        piste::Class::Path => PATH_CELL_PRICE,
+    };
+    let slope_factor = 1.0 + cell_exposure(terrain, cell) * EXPOSURE_PRICE_FACTOR;
+
+    // each tree stands on the corner of four cells, which share the cost of clearing it
+    let trees = trees
+        .offsets(cell, &CORNERS)
+        .filter(|corner| trees[corner].is_some())
+        .count();
+
+    base * slope_factor + trees as f32 * TREE_CLEARING_PRICE / 4.0
+}
+
+pub fn piste_price<'a>(
+    class: &piste::Class,
+    terrain: &Grid<f32>,
+    trees: &Grid<Option<Tree>>,
+    cells: impl Iterator<Item = &'a XY<u32>>,
+) -> i64 {
+    cells
+        .map(|cell| piste_cell_price(class, terrain, trees, cell))
+        .sum::<f32>()
+        .round() as i64
+}
+
+/// Price of the cable along the buildings plus each pylon and station
+pub fn lift_price(lift_buildings: &LiftBuildings, terrain: &Grid<f32>) -> i64 {
+    let LiftBuildings {
+        lift_type,
+        buildings,
+        ..
+    } = lift_buildings;
+
+    let positions = buildings
+        .iter()
+        .map(|building| {
+            let position = building.position;
+            xyz(position.x as f32, position.y as f32, terrain[position])
+        })
+        .collect::<Vec<_>>();
+    let meters = positions
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).magnitude())
+        .sum::<f32>();
+
+    let pylons = buildings
+        .iter()
+        .filter(|building| building.class == LiftBuildingClass::Pylon)
+        .count();
+    let stations = buildings.len() - pylons;
+
+    (meters * lift_type.price_per_meter()
+        + pylons as f32 * lift_type.pylon_price()
+        + stations as f32 * lift_type.station_price())
+    .round() as i64
+}
+
+pub fn building_price(footprint: &XYRectangle<u32>, height: u32) -> i64 {
+    let floors = height / HEIGHT_INTERVAL;
+    // the footprint spans positions, so its area is between the outermost ones
+    let area = (footprint.to.x - footprint.from.x) * (footprint.to.y - footprint.from.y);
+    (area as f32 * floors as f32 * FLOOR_PRICE_PER_SQUARE_METER).round() as i64
+}
+
+/// Time a crew takes to build works of the given price
+pub fn work_micros(price: i64) -> u128 {
+    let price = price.max(0) as u128;
+    (price * MICROS_PER_HOUR / WORK_PRICE_PER_HOUR).max(MIN_WORK_MICROS)
+}
+
+pub fn refund(price: i64) -> i64 {
+    (price as f32 * REFUND_RATIO).round() as i64
+}
+
+/// Charges `price` for building `what`, or explains why it cannot be afforded
+pub fn try_to_pay(
+    economy: &mut economy::Service,
+    messenger: &mut messenger::System,
+    what: &str,
+    price: i64,
+) -> bool {
+    let balance = economy.ledger().balance();
+    if price > balance {
+        messenger.send(format!(
+            "Cannot afford {}: it costs {} but the balance is {}",
+            what,
+            format_money(price),
+            format_money(balance)
+        ));
+        return false;
+    }
+    economy.record(Account::Construction, -price);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::geometry::xy;
+
+    use super::*;
+    use crate::model::lift_building::LiftBuilding;
+    use crate::model::lift_type::LiftType;
+
+    fn no_trees() -> Grid<Option<Tree>> {
+        Grid::from_fn(4, 4, |_| None)
+    }
+
+    #[test]
+    fn test_flat_piste_cell_costs_base_price() {
+        // given
+        let terrain = Grid::from_element(4, 4, 0.0);
+
+        // when
+        let piste = piste_cell_price(&piste::Class::Piste, &terrain, &no_trees(), &xy(1, 1));
+        let path = piste_cell_price(&piste::Class::Path, &terrain, &no_trees(), &xy(1, 1));
+
+        // then
+        assert_eq!(piste, PISTE_CELL_PRICE);
+        assert_eq!(path, PATH_CELL_PRICE);
+    }
+
+    #[test]
+    fn test_steep_piste_cell_costs_more() {
+        // given
+        let terrain = Grid::from_fn(4, 4, |xy| xy.x as f32);
+
+        // when
+        let price = piste_cell_price(&piste::Class::Piste, &terrain, &no_trees(), &xy(1, 1));
+
+        // then
+        assert_eq!(price, PISTE_CELL_PRICE * (1.0 + EXPOSURE_PRICE_FACTOR));
+    }
+
+    #[test]
+    fn test_clearing_trees_costs_more() {
+        // given
+        let terrain = Grid::from_element(4, 4, 0.0);
+        let mut trees = no_trees();
+        trees[xy(1, 1)] = Some(Tree {
+            yaw: 0.0,
+            height: 10.0,
+        });
+        trees[xy(2, 2)] = Some(Tree {
+            yaw: 0.0,
+            height: 10.0,
+        });
+
+        // when
+        let price = piste_cell_price(&piste::Class::Piste, &terrain, &trees, &xy(1, 1));
+
+        // then
+        assert_eq!(price, PISTE_CELL_PRICE + TREE_CLEARING_PRICE / 2.0);
+    }
+
+    #[test]
+    fn test_lift_price() {
+        // given
+        let terrain = Grid::from_element(200, 200, 0.0);
+        let building = |class, position| LiftBuilding {
+            class,
+            position,
+            yaw: 0.0,
+            manual_yaw: false,
+        };
+        let lift_buildings = LiftBuildings {
+            lift_type: LiftType::Chairlift,
+            buildings: vec![
+                building(LiftBuildingClass::PickUpStation, xy(0, 0)),
+                building(LiftBuildingClass::Pylon, xy(100, 0)),
+                building(LiftBuildingClass::DropOffStation, xy(150, 0)),
+            ],
+            cable_tension: LiftType::Chairlift.cable_tension(),
+        };
+
+        // when
+        let price = lift_price(&lift_buildings, &terrain);
+
+        // then
+        let lift_type = LiftType::Chairlift;
+        let expected = 150.0 * lift_type.price_per_meter()
+            + lift_type.pylon_price()
+            + 2.0 * lift_type.station_price();
+        assert_eq!(price, expected as i64);
+    }
+
+    #[test]
+    fn test_building_price() {
+        // given
+        let footprint = XYRectangle {
+            from: xy(10, 10),
+            to: xy(14, 12),
+        };
+
+        // when
+        let price = building_price(&footprint, 9);
+
+        // then
+        assert_eq!(price, (8.0 * 3.0 * FLOOR_PRICE_PER_SQUARE_METER) as i64);
+        assert_eq!(refund(price), price / 2);
+    }
+
+    #[test]
+    fn test_work_micros() {
+        // when
+        let cheap = work_micros(100);
+        let expensive = work_micros(50_000);
+
+        // then
+        assert_eq!(cheap, MIN_WORK_MICROS);
+        assert_eq!(expensive, 5 * 60 * 60 * 1_000_000);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::model::groomer::{self, Groomer};
+use crate::model::open;
+use crate::{Components, Systems};
+
+pub fn schedule_grooming(piste_id: &usize, components: &mut Components, systems: &mut Systems) {
+    let Components {
+        pistes,
+        piste_map,
+        groomers,
+        open,
+        services,
+        ..
+    } = components;
+
+    let Some(piste) = pistes.get(piste_id) else {
+        return;
+    };
+    if groomers
+        .values()
+        .any(|groomer| groomer.piste_id == *piste_id)
+    {
+        systems.messenger.send(format!(
+            "Piste {} is already scheduled for grooming",
+            piste_id
+        ));
+        return;
+    }
+
+    // the piste grid also takes in the edges of neighbouring pistes, which the groomer keeps off
+    let cells = piste.grid.map(|cell, is_piste| {
+        *is_piste && piste_map.in_bounds(cell) && piste_map[cell] == Some(*piste_id)
+    });
+    let route = groomer::coverage_route(&cells);
+    if route.is_empty() {
+        return;
+    }
+
+    groomers.insert(
+        services.id_allocator.next_id(),
+        Groomer::new(*piste_id, route),
+    );
+
+    if open.get(piste_id) != Some(&open::Status::Closed) {
+        systems.messenger.send(format!(
+            "Piste {} will be groomed once it is closed",
+            piste_id
+        ));
+    }
+}
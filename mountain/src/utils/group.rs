@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+
+use commons::geometry::XY;
+
+use crate::model::ability::Ability;
+use crate::model::group::Group;
+use crate::model::skier::Skier;
+use crate::model::skiing::Plan;
+
+pub fn members<'a>(
+    skier_id: &'a usize,
+    skiers: &HashMap<usize, Skier>,
+    groups: &'a HashMap<usize, Group>,
+) -> &'a [usize] {
+    skiers
+        .get(skier_id)
+        .and_then(|skier| groups.get(&skier.group_id))
+        .map(|group| group.member_ids.as_slice())
+        .unwrap_or(std::slice::from_ref(skier_id))
+}
+
+pub fn weakest_ability(member_ids: &[usize], skiers: &HashMap<usize, Skier>) -> Option<Ability> {
+    member_ids
+        .iter()
+        .flat_map(|member_id| skiers.get(member_id))
+        .map(|skier| skier.ability)
+        .min()
+}
+
+pub fn ability(
+    skier_id: &usize,
+    skiers: &HashMap<usize, Skier>,
+    groups: &HashMap<usize, Group>,
+) -> Option<Ability> {
+    weakest_ability(members(skier_id, skiers, groups), skiers)
+}
+
+/// Whether every member of the skier's group is waiting at the same pick up or gate - either
+/// queued there, or standing still on its footprint
+pub fn is_regrouped(
+    skier_id: &usize,
+    skiers: &HashMap<usize, Skier>,
+    groups: &HashMap<usize, Group>,
+    plans: &HashMap<usize, Plan>,
+    is_queued: impl Fn(&usize) -> bool,
+    is_on_footprint: impl Fn(&XY<u32>) -> bool,
+) -> bool {
+    members(skier_id, skiers, groups).iter().all(|member_id| {
+        is_queued(member_id)
+            || matches!(
+                plans.get(member_id),
+                Some(Plan::Stationary(state)) if is_on_footprint(&state.position)
+            )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::geometry::{xy, XYRectangle};
+
+    use crate::model::direction::Direction;
+    use crate::model::skier::{Clothes, Equipment};
+    use crate::model::skiing::{Event, State};
+
+    use super::*;
+
+    fn skier(ability: Ability, group_id: usize) -> Skier {
+        Skier {
+            clothes: Clothes::default(),
+            ability,
+            equipment: Equipment::Skis,
+            hotel_id: 0,
+            group_id,
+        }
+    }
+
+    fn state(position: XY<u32>) -> State {
+        State {
+            position,
+            velocity: 0,
+            travel_direction: Direction::North,
+        }
+    }
+
+    #[test]
+    fn test_ability_is_weakest_member_ability() {
+        // given
+        let skiers = HashMap::from([
+            (1, skier(Ability::Expert, 0)),
+            (2, skier(Ability::Intermediate, 0)),
+            (3, skier(Ability::Advanced, 0)),
+        ]);
+        let groups = HashMap::from([(
+            0,
+            Group {
+                member_ids: vec![1, 2, 3],
+            },
+        )]);
+
+        // when
+        let result = ability(&1, &skiers, &groups);
+
+        // then
+        assert_eq!(result, Some(Ability::Intermediate));
+    }
+
+    #[test]
+    fn test_ability_without_group() {
+        // given
+        let skiers = HashMap::from([(1, skier(Ability::Expert, 0))]);
+
+        // when
+        let result = ability(&1, &skiers, &HashMap::default());
+
+        // then
+        assert_eq!(result, Some(Ability::Expert));
+    }
+
+    #[test]
+    fn test_is_regrouped() {
+        // given
+        let skiers = HashMap::from([
+            (1, skier(Ability::Expert, 0)),
+            (2, skier(Ability::Expert, 0)),
+            (3, skier(Ability::Expert, 0)),
+        ]);
+        let groups = HashMap::from([(
+            0,
+            Group {
+                member_ids: vec![1, 2, 3],
+            },
+        )]);
+        let footprint = XYRectangle {
+            from: xy(0, 0),
+            to: xy(1, 1),
+        };
+        let mut plans = HashMap::from([
+            (1, Plan::Stationary(state(xy(0, 0)))),
+            (2, Plan::Stationary(state(xy(1, 1)))),
+            (
+                3,
+                Plan::Moving(vec![Event {
+                    micros: 0,
+                    state: state(xy(5, 5)),
+                }]),
+            ),
+        ]);
+        let is_regrouped = |plans: &HashMap<usize, Plan>, queued: &[usize]| {
+            is_regrouped(
+                &1,
+                &skiers,
+                &groups,
+                plans,
+                |skier_id| queued.contains(skier_id),
+                |position| footprint.contains(position),
+            )
+        };
+
+        // then
+        assert!(!is_regrouped(&plans, &[]));
+
+        // when
+        plans.insert(3, Plan::Stationary(state(xy(5, 5))));
+
+        // then
+        assert!(!is_regrouped(&plans, &[]));
+
+        // when
+        plans.remove(&3);
+
+        // then
+        assert!(!is_regrouped(&plans, &[]));
+        assert!(is_regrouped(&plans, &[3]));
+
+        // when
+        plans.insert(3, Plan::Stationary(state(xy(0, 1))));
+
+        // then
+        assert!(is_regrouped(&plans, &[]));
+    }
+}
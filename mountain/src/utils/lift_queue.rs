@@ -0,0 +1,390 @@
+use std::collections::HashMap;
+
+use commons::geometry::XY;
+use commons::grid::Grid;
+
+use crate::model::carousel::Carousel;
+use crate::model::lift::{Lift, Portal};
+use crate::model::lift_queue::Maze;
+use crate::model::reservation::Reservation;
+use crate::model::skiing::State;
+use crate::utils::ability::cell_ability;
+use crate::{Components, Systems};
+
+pub fn footprint<T>(maze: &Maze, pick_up: &State, grid: &Grid<T>) -> Vec<XY<u32>> {
+    let back = pick_up.travel_direction.offset() * -1;
+    let side = pick_up
+        .travel_direction
+        .next_clockwise()
+        .next_clockwise()
+        .offset();
+
+    // lanes run back and forth behind the pick up, starting next to it
+    (0..maze.lanes as i32)
+        .flat_map(|lane| {
+            (0..maze.lane_length as i32).map(move |step| {
+                let distance = if lane % 2 == 0 {
+                    step + 1
+                } else {
+                    maze.lane_length as i32 - step
+                };
+                back * distance + side * lane
+            })
+        })
+        .flat_map(|offset| grid.offset(pick_up.position, offset))
+        .collect()
+}
+
+/// Explains why the maze cannot be laid out behind the pick up, if it cannot
+pub fn maze_violation(
+    maze: &Maze,
+    pick_up: &State,
+    origin_piste_id: &usize,
+    terrain: &Grid<f32>,
+    piste_map: &Grid<Option<usize>>,
+) -> Option<&'static str> {
+    let footprint = footprint(maze, pick_up, terrain);
+    if footprint.len() < maze.capacity() {
+        return Some("Queue does not fit on the map!");
+    }
+    for cell in footprint {
+        if piste_map[cell].is_some_and(|piste_id| piste_id != *origin_piste_id) {
+            return Some("Queue cannot overlap another piste!");
+        }
+        if cell_ability(terrain, &cell).is_none() {
+            return Some("Queue cannot be on a cliff!");
+        }
+    }
+    None
+}
+
+/// The largest maze no bigger than `preferred` that can be laid out behind the pick up
+pub fn fit_maze(
+    preferred: &Maze,
+    pick_up: &State,
+    origin_piste_id: &usize,
+    terrain: &Grid<f32>,
+    piste_map: &Grid<Option<usize>>,
+) -> Option<Maze> {
+    (1..=preferred.lanes)
+        .flat_map(|lanes| {
+            (1..=preferred.lane_length).map(move |lane_length| Maze { lanes, lane_length })
+        })
+        .filter(|maze| maze_violation(maze, pick_up, origin_piste_id, terrain, piste_map).is_none())
+        .max_by_key(|maze| maze.capacity())
+}
+
+/// Skiers heading for the pick up ski through its maze, everyone else goes around it
+pub fn reserve_maze(
+    pick_up: &Portal,
+    maze: &Maze,
+    reservations: &mut Grid<HashMap<usize, Reservation>>,
+) {
+    for cell in footprint(maze, &pick_up.state, reservations) {
+        reservations[cell].insert(pick_up.id, Reservation::Structure);
+    }
+}
+
+pub fn release_maze(
+    pick_up: &Portal,
+    maze: &Maze,
+    reservations: &mut Grid<HashMap<usize, Reservation>>,
+) {
+    for cell in footprint(maze, &pick_up.state, reservations) {
+        reservations[cell].remove(&pick_up.id);
+    }
+}
+
+/// Changes the maze of the queue at the pick up, unless it would overlap other pistes or cliffs
+pub fn set_maze(
+    pick_up_id: &usize,
+    maze: Maze,
+    components: &mut Components,
+    systems: &mut Systems,
+) {
+    let Components {
+        terrain,
+        piste_map,
+        lifts,
+        parents,
+        exits,
+        lift_queues,
+        reservations,
+        ..
+    } = components;
+
+    let Some(pick_up) = parents
+        .get(pick_up_id)
+        .and_then(|lift_id| lifts.get(lift_id))
+        .and_then(|lift| lift.pick_ups().find(|pick_up| pick_up.id == *pick_up_id))
+    else {
+        return;
+    };
+    let Some(queue) = lift_queues.get_mut(&pick_up.id) else {
+        return;
+    };
+    if queue.maze == maze {
+        return;
+    }
+    let Some(exit) = exits.get(&pick_up.id) else {
+        return;
+    };
+
+    if let Some(violation) = maze_violation(
+        &maze,
+        &pick_up.state,
+        &exit.origin_piste_id,
+        terrain,
+        piste_map,
+    ) {
+        systems.messenger.send(violation);
+        return;
+    }
+
+    release_maze(pick_up, &queue.maze, reservations);
+    reserve_maze(pick_up, &maze, reservations);
+    queue.maze = maze;
+
+    systems.piste_computer.compute(exit.origin_piste_id);
+}
+
+pub fn micros_per_car(lift: &Lift, carousel: &Carousel) -> Option<u128> {
+    if carousel.car_ids.is_empty() || carousel.velocity <= 0.0 {
+        return None;
+    }
+    let loop_meters = lift
+        .segments
+        .iter()
+        .map(|segment| segment.length_meters())
+        .sum::<f32>();
+    let seconds = loop_meters / carousel.car_ids.len() as f32 / carousel.velocity;
+    Some((seconds * 1_000_000.0) as u128)
+}
+
+pub fn micros_per_skier(lift: &Lift, carousel: &Carousel) -> Option<u128> {
+    micros_per_car(lift, carousel).map(|micros| micros / carousel.car_class.capacity() as u128)
+}
+
+pub fn skiers_per_hour(lift: &Lift, carousel: &Carousel) -> Option<u32> {
+    micros_per_skier(lift, carousel)
+        .filter(|micros| *micros > 0)
+        .map(|micros| (3_600_000_000 / micros) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::geometry::{xy, xyz};
+
+    use crate::model::carousel::CarClass;
+    use crate::model::direction::Direction;
+    use crate::model::lift::Segment;
+    use crate::model::lift_type::LiftType;
+
+    use super::*;
+
+    #[test]
+    fn test_footprint() {
+        // given
+        let maze = Maze {
+            lanes: 2,
+            lane_length: 3,
+        };
+        let pick_up = State {
+            position: xy(5, 5),
+            velocity: 0,
+            travel_direction: Direction::North,
+        };
+        let grid = Grid::<bool>::default(10, 10);
+
+        // when
+        let result = footprint(&maze, &pick_up, &grid);
+
+        // then
+        assert_eq!(
+            result,
+            vec![xy(5, 4), xy(5, 3), xy(5, 2), xy(6, 2), xy(6, 3), xy(6, 4),]
+        );
+    }
+
+    #[test]
+    fn test_footprint_out_of_bounds() {
+        // given
+        let maze = Maze {
+            lanes: 1,
+            lane_length: 3,
+        };
+        let pick_up = State {
+            position: xy(1, 1),
+            velocity: 0,
+            travel_direction: Direction::East,
+        };
+        let grid = Grid::<bool>::default(10, 10);
+
+        // when
+        let result = footprint(&maze, &pick_up, &grid);
+
+        // then
+        assert_eq!(result, vec![xy(0, 1)]);
+    }
+
+    #[test]
+    fn test_maze_violation() {
+        // given
+        let maze = Maze {
+            lanes: 2,
+            lane_length: 3,
+        };
+        let pick_up = State {
+            position: xy(5, 5),
+            velocity: 0,
+            travel_direction: Direction::North,
+        };
+        let terrain = Grid::default(10, 10);
+        let piste_map = Grid::from_element(10, 10, Some(0));
+
+        // then
+        assert_eq!(
+            maze_violation(&maze, &pick_up, &0, &terrain, &piste_map),
+            None
+        );
+
+        // when
+        let mut other_piste = piste_map.clone();
+        other_piste[xy(6, 2)] = Some(1);
+
+        // then
+        assert_eq!(
+            maze_violation(&maze, &pick_up, &0, &terrain, &other_piste),
+            Some("Queue cannot overlap another piste!")
+        );
+
+        // when
+        let mut cliff = terrain.clone();
+        cliff[xy(5, 2)] = 100.0;
+
+        // then
+        assert_eq!(
+            maze_violation(&maze, &pick_up, &0, &cliff, &piste_map),
+            Some("Queue cannot be on a cliff!")
+        );
+
+        // when
+        let near_edge = State {
+            position: xy(5, 1),
+            ..pick_up
+        };
+
+        // then
+        assert_eq!(
+            maze_violation(&maze, &near_edge, &0, &terrain, &piste_map),
+            Some("Queue does not fit on the map!")
+        );
+    }
+
+    #[test]
+    fn test_fit_maze() {
+        // given
+        let maze = Maze {
+            lanes: 2,
+            lane_length: 3,
+        };
+        let pick_up = State {
+            position: xy(5, 5),
+            velocity: 0,
+            travel_direction: Direction::North,
+        };
+        let terrain = Grid::default(10, 10);
+        let mut piste_map = Grid::from_element(10, 10, Some(0));
+        piste_map[xy(6, 4)] = Some(1);
+
+        // when
+        let result = fit_maze(&maze, &pick_up, &0, &terrain, &piste_map);
+
+        // then
+        assert_eq!(
+            result,
+            Some(Maze {
+                lanes: 1,
+                lane_length: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_reserve_and_release_maze() {
+        // given
+        let maze = Maze {
+            lanes: 1,
+            lane_length: 2,
+        };
+        let pick_up = Portal {
+            id: 7,
+            segment: 0,
+            state: State {
+                position: xy(5, 5),
+                velocity: 0,
+                travel_direction: Direction::North,
+            },
+        };
+        let mut reservations = Grid::<HashMap<usize, Reservation>>::default(10, 10);
+        reservations[xy(5, 4)].insert(8, Reservation::Structure);
+
+        // when
+        reserve_maze(&pick_up, &maze, &mut reservations);
+
+        // then
+        assert!(reservations[xy(5, 4)].get(&7) == Some(&Reservation::Structure));
+        assert!(reservations[xy(5, 3)].get(&7) == Some(&Reservation::Structure));
+        assert!(reservations[xy(5, 5)].is_empty());
+
+        // when
+        release_maze(&pick_up, &maze, &mut reservations);
+
+        // then
+        assert!(reservations[xy(5, 3)].is_empty());
+        assert_eq!(reservations[xy(5, 4)].keys().collect::<Vec<_>>(), vec![&8]);
+    }
+
+    #[test]
+    fn test_skiers_per_hour() {
+        // given
+        let state = State {
+            position: xy(0, 0),
+            velocity: 0,
+            travel_direction: Direction::East,
+        };
+        let lift = Lift {
+            lift_type: LiftType::Chairlift,
+            segments: vec![
+                Segment::new(xyz(0.0, 0.0, 0.0), xyz(100.0, 0.0, 0.0)),
+                Segment::new(xyz(100.0, 0.0, 0.0), xyz(0.0, 0.0, 0.0)),
+            ],
+            pick_up: Portal {
+                id: 1,
+                segment: 0,
+                state,
+            },
+            drop_off: Portal {
+                id: 2,
+                segment: 1,
+                state,
+            },
+            mid_stations: vec![],
+            carousel_id: 3,
+            buildings_id: 4,
+        };
+        let carousel = Carousel {
+            lift_id: 0,
+            velocity: 2.0,
+            car_interval_meters: 10.0,
+            car_class: CarClass::Quad,
+            car_ids: (0..20).collect(),
+        };
+
+        // when
+        let result = skiers_per_hour(&lift, &carousel);
+
+        // then
+        assert_eq!(result, Some(2880));
+    }
+}
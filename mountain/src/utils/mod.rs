@@ -1,5 +1,13 @@
 pub mod ability;
 pub mod carousel;
+pub mod carousel_tuner;
 pub mod computer;
+pub mod construction;
+pub mod grooming;
+pub mod group;
+pub mod lift_queue;
 pub mod opener;
 pub mod physics;
+pub mod scenario;
+pub mod skiers;
+pub mod wire_validation;
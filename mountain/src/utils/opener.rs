@@ -13,6 +13,7 @@ pub fn set_open_status(
         pistes,
         open,
         children,
+        constructions,
         ..
     } = components;
 
@@ -21,6 +22,16 @@ pub fn set_open_status(
         return;
     }
 
+    // anything can be closed, but nothing opens until its works are complete
+    if let (open::Status::Open, Some(construction)) = (status, constructions.get(id)) {
+        systems.messenger.send(format!(
+            "{} {} is still under construction",
+            construction.kind.name(),
+            id
+        ));
+        return;
+    }
+
     open.insert(*id, status);
 
     systems.piste_computer.compute(*id);
@@ -0,0 +1,234 @@
+use std::fs::read_to_string;
+use std::path::Path;
+
+use commons::geometry::{xy, XYRectangle};
+use commons::grid::{Grid, CORNERS_INVERSE};
+use commons::origin_grid::OriginGrid;
+
+use crate::controllers::building_builder::{self, CompleteParameters, HEIGHT_INTERVAL};
+use crate::init;
+use crate::init::terrain::generate_heightmap;
+use crate::model::building::{Building, Kind, Roof};
+use crate::model::open;
+use crate::model::piste::{self, Piste};
+use crate::model::scenario::{Scenario, Structure, Terrain};
+use crate::{Components, Systems};
+
+pub fn load(path: &str) -> Result<Scenario, String> {
+    let text = read_to_string(path).map_err(|error| error.to_string())?;
+    toml::from_str(&text).map_err(|error| error.to_string())
+}
+
+/// Generates the terrain of the scenario or loads it from its heightmap
+pub fn terrain(terrain: &Terrain, directory: &str) -> Result<Grid<f32>, String> {
+    let (file, max_elevation) = match terrain {
+        Terrain::Generated { power, seed } => {
+            return Ok(generate_heightmap(init::terrain::Parameters {
+                power: *power,
+                seed: *seed,
+            }))
+        }
+        Terrain::Heightmap {
+            file,
+            max_elevation,
+            ..
+        } => (file, max_elevation),
+    };
+
+    let image = image::open(Path::new(directory).join(file))
+        .map_err(|error| error.to_string())?
+        .into_luma16();
+    let (width, height) = image.dimensions();
+    // trees are generated from noise, which only comes in power of two squares
+    if width != height || !width.is_power_of_two() {
+        return Err(format!(
+            "Heightmap must be a square with a power of two side, not {}x{}",
+            width, height
+        ));
+    }
+
+    Ok(Grid::from_fn(width, height, |position| {
+        image.get_pixel(position.x, position.y)[0] as f32 / u16::MAX as f32 * max_elevation
+    }))
+}
+
+/// Difference between the highest and lowest positions of the piste
+pub fn vertical_meters(piste: &Piste, terrain: &Grid<f32>) -> f32 {
+    let elevations = piste
+        .grid
+        .iter()
+        .filter(|position| piste.grid[position])
+        .map(|position| terrain[position]);
+    let (min, max) = elevations.fold((f32::MAX, f32::MIN), |(min, max), elevation| {
+        (min.min(elevation), max.max(elevation))
+    });
+    (max - min).max(0.0)
+}
+
+/// Adds the scenario's structures, open and ready to use
+pub fn place_structures(
+    structures: &[Structure],
+    components: &mut Components,
+    systems: &mut Systems,
+) {
+    for structure in structures {
+        let (from, to) = match structure {
+            Structure::Piste { from, to }
+            | Structure::Path { from, to }
+            | Structure::Hotel { from, to, .. } => (xy(from[0], from[1]), xy(to[0], to[1])),
+        };
+        let rectangle = XYRectangle { from, to };
+        if !components.terrain.in_bounds(xy(to.x + 1, to.y + 1)) || from.x > to.x || from.y > to.y {
+            systems.messenger.send(format!(
+                "Skipping scenario structure from {:?} to {:?}, which is not within the terrain",
+                from, to
+            ));
+            continue;
+        }
+
+        match structure {
+            Structure::Piste { .. } => {
+                place_piste(piste::Class::Piste, rectangle, components, systems)
+            }
+            Structure::Path { .. } => {
+                place_piste(piste::Class::Path, rectangle, components, systems)
+            }
+            Structure::Hotel { height, .. } => place_hotel(rectangle, *height, components, systems),
+        }
+    }
+
+    systems.tree_artist.update();
+}
+
+fn place_piste(
+    class: piste::Class,
+    cells: XYRectangle<u32>,
+    components: &mut Components,
+    systems: &mut Systems,
+) {
+    let Components {
+        pistes,
+        piste_map,
+        open,
+        services,
+        ..
+    } = components;
+
+    let piste_id = services.id_allocator.next_id();
+
+    for cell in cells.iter() {
+        if piste_map[cell].is_none() {
+            piste_map[cell] = Some(piste_id);
+        }
+    }
+
+    let point_grid = OriginGrid::from_rectangle(
+        XYRectangle {
+            from: cells.from,
+            to: xy(cells.to.x + 1, cells.to.y + 1),
+        },
+        false,
+    );
+    let grid = point_grid.map(|point, _| {
+        piste_map
+            .offsets(point, &CORNERS_INVERSE)
+            .any(|cell| piste_map[cell] == Some(piste_id))
+    });
+
+    pistes.insert(piste_id, Piste { class, grid });
+    open.insert(piste_id, open::Status::Open);
+
+    systems.piste_computer.compute(piste_id);
+    systems.terrain_artist.update_overlay(cells);
+}
+
+fn place_hotel(
+    footprint: XYRectangle<u32>,
+    height: u32,
+    components: &mut Components,
+    systems: &mut Systems,
+) {
+    let Components {
+        terrain,
+        buildings,
+        locations,
+        skiers,
+        groups,
+        rating,
+        services,
+        ..
+    } = components;
+
+    let building_id = services.id_allocator.next_id();
+    buildings.insert(
+        building_id,
+        Building {
+            kind: Kind::Hotel,
+            footprint,
+            height: (height / HEIGHT_INTERVAL).clamp(
+                building_builder::HEIGHT_MIN / HEIGHT_INTERVAL,
+                building_builder::HEIGHT_MAX / HEIGHT_INTERVAL,
+            ) * HEIGHT_INTERVAL,
+            roof: Roof::Peaked,
+            under_construction: true,
+            windows: vec![],
+        },
+    );
+
+    // completing the building spawns its guests
+    building_builder::complete(
+        &building_id,
+        CompleteParameters {
+            terrain,
+            id_allocator: &mut services.id_allocator,
+            buildings,
+            locations,
+            skiers,
+            groups,
+            rating,
+            building_artist: &mut systems.building_artist,
+            window_artist: &mut systems.window_artist,
+            messenger: &mut systems.messenger,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_example_scenario() {
+        // when
+        let scenario = load("../scenarios/first_season.toml").unwrap();
+
+        // then
+        assert_eq!(scenario.name, "First Season");
+        assert_eq!(scenario.structures.len(), 2);
+    }
+
+    #[test]
+    fn test_vertical_meters() {
+        // given
+        let terrain = Grid::from_fn(4, 4, |position| position.y as f32 * 100.0);
+        let mut grid = OriginGrid::from_rectangle(
+            XYRectangle {
+                from: xy(1, 0),
+                to: xy(2, 3),
+            },
+            true,
+        );
+        grid[xy(1, 3)] = false;
+        grid[xy(2, 3)] = false;
+        let piste = Piste {
+            class: piste::Class::Piste,
+            grid,
+        };
+
+        // when
+        let vertical = vertical_meters(&piste, &terrain);
+
+        // then
+        assert_eq!(vertical, 200.0);
+    }
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+
+use engine::graphics::Graphics;
+
+use crate::model::door::Door;
+use crate::model::skier::Skier;
+use crate::model::skiing::Plan;
+use crate::Components;
+
+/// Removes the skiers from every component, releasing their frames and drawings
+pub fn remove(graphics: &mut dyn Graphics, components: &mut Components, skier_ids: &[usize]) {
+    if skier_ids.is_empty() {
+        return;
+    }
+
+    for skier_id in skier_ids.iter() {
+        // skiers only hold reservations where their plans take them
+        match components.plans.remove(skier_id) {
+            Some(Plan::Stationary(state)) => {
+                components.reservations[state.position].remove(skier_id);
+            }
+            Some(Plan::Moving(events)) => {
+                for event in events {
+                    components.reservations[event.state.position].remove(skier_id);
+                }
+            }
+            None => (),
+        }
+        components.skiers.remove(skier_id);
+        components.locations.remove(skier_id);
+        components.targets.remove(skier_id);
+        components.global_targets.remove(skier_id);
+        components.satisfaction.remove(skier_id);
+        components.needs.remove(skier_id);
+        components.visits.remove(skier_id);
+        components.check_outs.remove(skier_id);
+        components.incidents.remove(skier_id);
+        components.frames.remove(skier_id);
+        components.clothes.remove(skier_id);
+        remove_dynamic_drawing(graphics, components, skier_id);
+    }
+
+    components
+        .planning_queue
+        .retain(|skier_id| !skier_ids.contains(skier_id));
+
+    for queue in components.lift_queues.values_mut() {
+        queue
+            .skiers
+            .retain(|queued| !skier_ids.contains(&queued.skier_id));
+    }
+
+    components.groups.retain(|_, group| {
+        group
+            .member_ids
+            .retain(|member_id| !skier_ids.contains(member_id));
+        !group.member_ids.is_empty()
+    });
+}
+
+/// Whether the skier is waiting at a door of the building they are staying in, heading inside
+pub fn is_at_home_door(
+    skier_id: &usize,
+    skier: &Skier,
+    plans: &HashMap<usize, Plan>,
+    targets: &HashMap<usize, usize>,
+    doors: &HashMap<usize, Door>,
+) -> bool {
+    let Some(Plan::Stationary(state)) = plans.get(skier_id) else {
+        return false;
+    };
+    let Some(door) = targets.get(skier_id).and_then(|target| doors.get(target)) else {
+        return false;
+    };
+    door.building_id == skier.hotel_id && door.footprint.contains(state.position)
+}
+
+fn remove_dynamic_drawing(graphics: &mut dyn Graphics, components: &mut Components, id: &usize) {
+    if let Some(drawing_id) = components.drawings.get(id) {
+        let _ = graphics.update_dynamic_triangles(
+            drawing_id,
+            engine::graphics::DrawMode::Invisible,
+            &[],
+        );
+    }
+    components.drawings.remove(id);
+}
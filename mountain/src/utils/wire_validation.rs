@@ -0,0 +1,181 @@
+use commons::geometry::{xy, XYZ};
+use commons::grid::Grid;
+
+use crate::model::lift_type::LiftType;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Violation {
+    Clearance,
+    Span,
+    Incline,
+}
+
+impl Violation {
+    pub fn message(&self, lift_type: &LiftType) -> String {
+        match self {
+            Violation::Clearance => format!(
+                "Cable is too close to the ground: minimum clearance is {}m!",
+                lift_type.min_clearance_meters().unwrap_or_default()
+            ),
+            Violation::Span => format!(
+                "Span between supports is too long: maximum span is {}m!",
+                lift_type.max_span_meters()
+            ),
+            Violation::Incline => format!(
+                "Cable is too steep: maximum incline is {}%!",
+                (lift_type.max_cable_incline() * 100.0).round()
+            ),
+        }
+    }
+}
+
+/// First violation of each segment of the wire path, if any, given the wire path grouped into cables
+pub fn violations(
+    lift_type: &LiftType,
+    cables: &[Vec<[XYZ<f32>; 2]>],
+    terrain: &Grid<f32>,
+) -> Vec<Option<Violation>> {
+    cables
+        .iter()
+        .flat_map(|cable| {
+            let span = span_violation(lift_type, cable);
+            cable
+                .iter()
+                .map(move |segment| span.or_else(|| violation(lift_type, segment, terrain)))
+        })
+        .collect()
+}
+
+fn span_violation(lift_type: &LiftType, cable: &[[XYZ<f32>; 2]]) -> Option<Violation> {
+    let (Some([from, _]), Some([_, to])) = (cable.first(), cable.last()) else {
+        return None;
+    };
+    let span = xy(to.x - from.x, to.y - from.y).magnitude();
+    (span > lift_type.max_span_meters()).then_some(Violation::Span)
+}
+
+fn violation(
+    lift_type: &LiftType,
+    [from, to]: &[XYZ<f32>; 2],
+    terrain: &Grid<f32>,
+) -> Option<Violation> {
+    let run = xy(to.x - from.x, to.y - from.y).magnitude();
+    let rise = (to.z - from.z).abs();
+    if rise > run * lift_type.max_cable_incline() {
+        return Some(Violation::Incline);
+    }
+
+    let min_clearance = lift_type.min_clearance_meters()?;
+    if clearance(from, to, run, terrain) < min_clearance {
+        return Some(Violation::Clearance);
+    }
+
+    None
+}
+
+fn clearance(from: &XYZ<f32>, to: &XYZ<f32>, run: f32, terrain: &Grid<f32>) -> f32 {
+    // sampling roughly once per cell along the segment
+    let samples = run.ceil().max(1.0) as usize;
+    (0..=samples)
+        .flat_map(|sample| {
+            let p = sample as f32 / samples as f32;
+            let x = from.x + (to.x - from.x) * p;
+            let y = from.y + (to.y - from.y) * p;
+            let z = from.z + (to.z - from.z) * p;
+            if x < 0.0 || y < 0.0 {
+                return None;
+            }
+            let position = xy(x.round() as u32, y.round() as u32);
+            terrain.in_bounds(position).then(|| z - terrain[position])
+        })
+        .fold(f32::INFINITY, f32::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use commons::geometry::xyz;
+
+    use super::*;
+
+    #[test]
+    fn test_valid_segment() {
+        // given
+        let terrain = Grid::from_element(100, 100, 0.0);
+        let cables = [vec![[xyz(0.0, 0.0, 5.0), xyz(50.0, 0.0, 10.0)]]];
+
+        // when
+        let result = violations(&LiftType::Chairlift, &cables, &terrain);
+
+        // then
+        assert_eq!(result, vec![None]);
+    }
+
+    #[test]
+    fn test_clearance() {
+        // given
+        let mut terrain = Grid::from_element(100, 100, 0.0);
+        terrain[xy(25, 0)] = 4.0;
+        let cables = [vec![[xyz(0.0, 0.0, 5.0), xyz(50.0, 0.0, 5.0)]]];
+
+        // when
+        let result = violations(&LiftType::Chairlift, &cables, &terrain);
+
+        // then
+        assert_eq!(result, vec![Some(Violation::Clearance)]);
+    }
+
+    #[test]
+    fn test_clearance_ignored_for_lift_on_ground() {
+        // given
+        let terrain = Grid::from_element(100, 100, 1.0);
+        let cables = [vec![[xyz(0.0, 0.0, 1.0), xyz(10.0, 0.0, 1.0)]]];
+
+        // when
+        let result = violations(&LiftType::MagicCarpet, &cables, &terrain);
+
+        // then
+        assert_eq!(result, vec![None]);
+    }
+
+    #[test]
+    fn test_span() {
+        // given
+        let terrain = Grid::from_element(500, 500, 0.0);
+        let cables = [vec![[xyz(0.0, 0.0, 5.0), xyz(400.0, 0.0, 5.0)]]];
+
+        // when
+        let result = violations(&LiftType::Chairlift, &cables, &terrain);
+
+        // then
+        assert_eq!(result, vec![Some(Violation::Span)]);
+    }
+
+    #[test]
+    fn test_span_of_cable_split_into_pieces() {
+        // given
+        let terrain = Grid::from_element(500, 500, 0.0);
+        let cables = [vec![
+            [xyz(0.0, 0.0, 5.0), xyz(200.0, 0.0, 5.0)],
+            [xyz(200.0, 0.0, 5.0), xyz(400.0, 0.0, 5.0)],
+        ]];
+
+        // when
+        let result = violations(&LiftType::Chairlift, &cables, &terrain);
+
+        // then
+        assert_eq!(result, vec![Some(Violation::Span), Some(Violation::Span)]);
+    }
+
+    #[test]
+    fn test_incline() {
+        // given
+        let terrain = Grid::from_element(100, 100, 0.0);
+        let cables = [vec![[xyz(0.0, 0.0, 5.0), xyz(10.0, 0.0, 20.0)]]];
+
+        // when
+        let result = violations(&LiftType::Chairlift, &cables, &terrain);
+
+        // then
+        assert_eq!(result, vec![Some(Violation::Incline)]);
+    }
+}
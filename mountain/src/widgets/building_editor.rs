@@ -1,11 +1,14 @@
 use std::collections::HashMap;
 
+use commons::geometry::XYRectangle;
 use engine::egui;
 
 use crate::controllers::building_builder::{self, HEIGHT_INTERVAL, HEIGHT_MAX, HEIGHT_MIN};
-use crate::model::building::{Building, Roof, ROOFS};
+use crate::gui::format_money;
+use crate::model::building::{Building, Kind, Roof, KINDS, ROOFS};
 use crate::services;
 use crate::systems::building_artist;
+use crate::utils::construction;
 use crate::widgets;
 
 #[derive(Default)]
@@ -15,6 +18,8 @@ pub struct Widget {
 
 struct State {
     building_id: usize,
+    kind: Kind,
+    footprint: XYRectangle<u32>,
     height: u32,
     roof: Roof,
     under_construction: bool,
@@ -49,6 +54,8 @@ impl<'a> widgets::UiWidget<Input<'a>, Output<'a>> for Widget {
 
         let state = State {
             building_id,
+            kind: building.kind,
+            footprint: building.footprint,
             height: building.height,
             roof: building.roof,
             under_construction: building.under_construction,
@@ -58,6 +65,8 @@ impl<'a> widgets::UiWidget<Input<'a>, Output<'a>> for Widget {
 
     fn draw(&mut self, ui: &mut engine::egui::Ui) {
         let Some(State {
+            kind,
+            footprint,
             height,
             roof,
             under_construction,
@@ -67,8 +76,16 @@ impl<'a> widgets::UiWidget<Input<'a>, Output<'a>> for Widget {
             return;
         };
         ui.vertical(|ui| {
-            ui.label("Hotel");
+            ui.label("Building");
             ui.horizontal(|ui| {
+                ui.label("Type:");
+                egui::ComboBox::from_id_source(1)
+                    .selected_text(kind.name())
+                    .show_ui(ui, |ui| {
+                        for option in KINDS {
+                            ui.selectable_value(kind, option, option.name());
+                        }
+                    });
                 ui.label("Height:");
                 ui.add(
                     egui::Slider::new(height, HEIGHT_MIN..=HEIGHT_MAX)
@@ -82,6 +99,10 @@ impl<'a> widgets::UiWidget<Input<'a>, Output<'a>> for Widget {
                             ui.selectable_value(roof, option, describe_roof(&option));
                         }
                     });
+                ui.label(format!(
+                    "Cost: {}",
+                    format_money(construction::building_price(footprint, *height))
+                ));
                 *under_construction = !ui.button("Build").clicked();
             });
         });
@@ -90,9 +111,11 @@ impl<'a> widgets::UiWidget<Input<'a>, Output<'a>> for Widget {
     fn update(&mut self, output: Output) {
         let Some(State {
             building_id,
+            kind,
             height,
             roof,
             under_construction,
+            ..
         }) = self.state
         else {
             return;
@@ -102,6 +125,7 @@ impl<'a> widgets::UiWidget<Input<'a>, Output<'a>> for Widget {
             return;
         };
 
+        building.kind = kind;
         building.height = height;
         building.roof = roof;
         building.under_construction = under_construction;
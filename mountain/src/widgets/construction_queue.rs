@@ -0,0 +1,70 @@
+use engine::egui;
+
+use crate::model::construction::{Construction, CREWS};
+use crate::widgets::ContextWidget;
+use crate::Components;
+
+const PROGRESS_BAR_WIDTH: f32 = 120.0;
+
+#[derive(Default)]
+pub struct Widget {
+    is_window_open: bool,
+    constructions: Vec<Construction>,
+}
+
+impl Widget {
+    pub fn is_open(&self) -> bool {
+        self.is_window_open
+    }
+
+    pub fn toggle(&mut self) {
+        self.is_window_open = !self.is_window_open;
+    }
+}
+
+impl ContextWidget<&Components, ()> for Widget {
+    fn init(&mut self, components: &Components) {
+        if !self.is_window_open {
+            return;
+        }
+        self.constructions = components.constructions.iter().cloned().collect();
+    }
+
+    fn draw(&mut self, ctx: &egui::Context) {
+        if !self.is_window_open {
+            return;
+        }
+
+        egui::Window::new("Construction")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut self.is_window_open)
+            .show(ctx, |ui| {
+                if self.constructions.is_empty() {
+                    ui.label("No works in progress");
+                    return;
+                }
+
+                egui::Grid::new("construction_queue")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (i, construction) in self.constructions.iter().enumerate() {
+                            ui.label(format!("{} {}", construction.kind.name(), construction.id));
+                            // only as many works progress as there are crews
+                            if i < CREWS {
+                                ui.add(
+                                    egui::ProgressBar::new(construction.progress())
+                                        .desired_width(PROGRESS_BAR_WIDTH)
+                                        .show_percentage(),
+                                );
+                            } else {
+                                ui.label("Waiting");
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    fn update(&mut self, _: ()) {}
+}
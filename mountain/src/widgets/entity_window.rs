@@ -1,8 +1,19 @@
 use commons::geometry::XY;
 use engine::egui::{self, RichText};
 
+use crate::controllers::lift_builder;
+use crate::model::building::Kind;
+use crate::model::carousel::CarClass;
+use crate::model::lift_queue::Maze;
+use crate::model::needs::Needs;
 use crate::model::open;
-use crate::utils::opener;
+use crate::model::patroller::{Leg, PATROLLERS_PER_HUT};
+use crate::model::reliability::{self, Reliability};
+use crate::model::skier::Equipment;
+use crate::model::snowmaking;
+use crate::services::mode::Mode;
+use crate::utils::lift_queue::{self, skiers_per_hour};
+use crate::utils::{carousel_tuner, grooming, opener};
 use crate::widgets::ContextWidget;
 use crate::Components;
 use crate::{gui, Systems};
@@ -14,13 +25,46 @@ pub struct EntityWindow {
     location: Option<usize>,
     target: Option<usize>,
     hotel: Option<usize>,
+    equipment: Option<Equipment>,
+    check_out_day: Option<u128>,
+    rooms: Option<(usize, usize)>,
+    patrollers_out: Option<usize>,
+    injury_status: Option<String>,
+    group: Option<usize>,
     global_target: Option<usize>,
+    queues: Vec<QueueState>,
+    car_class: Option<CarClass>,
+    carousel_id: Option<usize>,
+    velocity: f32,
+    car_interval_meters: f32,
+    car_classes: &'static [CarClass],
+    skiers_per_hour: Option<u32>,
+    edit_lift: bool,
+    reliability_status: Option<String>,
+    is_piste: bool,
+    grooming_progress: Option<f32>,
+    last_groomed_micros_ago: Option<u128>,
+    schedule_grooming: bool,
+    snowmaking_status: Option<String>,
+    water_capacity_m3_per_hour: Option<f32>,
+    construction_progress: Option<f32>,
+    satisfaction: Option<f32>,
+    needs: Option<Needs>,
+    thoughts: Vec<(String, u128)>,
     is_window_open: bool,
 }
 
+struct QueueState {
+    pick_up_id: usize,
+    length: usize,
+    average_wait_micros: Option<u128>,
+    maze: Maze,
+}
+
 pub struct Output<'a> {
     pub components: &'a mut Components,
     pub systems: &'a mut Systems,
+    pub lift_builder: &'a mut lift_builder::Controller,
 }
 
 impl EntityWindow {
@@ -31,7 +75,32 @@ impl EntityWindow {
             location: None,
             target: None,
             hotel: None,
+            equipment: None,
+            check_out_day: None,
+            rooms: None,
+            patrollers_out: None,
+            injury_status: None,
+            group: None,
             global_target: None,
+            queues: vec![],
+            car_class: None,
+            carousel_id: None,
+            velocity: 0.0,
+            car_interval_meters: 0.0,
+            car_classes: &[],
+            skiers_per_hour: None,
+            edit_lift: false,
+            reliability_status: None,
+            is_piste: false,
+            grooming_progress: None,
+            last_groomed_micros_ago: None,
+            schedule_grooming: false,
+            snowmaking_status: None,
+            water_capacity_m3_per_hour: None,
+            construction_progress: None,
+            satisfaction: None,
+            needs: None,
+            thoughts: vec![],
             open_status: None,
             is_window_open: true,
         }
@@ -47,11 +116,143 @@ impl ContextWidget<&Components, Output<'_>> for EntityWindow {
         self.location = components.locations.get(&self.entity_id).copied();
         self.target = components.targets.get(&self.entity_id).copied();
         self.global_target = components.global_targets.get(&self.entity_id).copied();
+        self.check_out_day = components.check_outs.get(&self.entity_id).copied();
+        self.rooms = components
+            .buildings
+            .get(&self.entity_id)
+            .filter(|building| building.kind == Kind::Hotel && !building.under_construction)
+            .map(|building| {
+                let guests = components
+                    .skiers
+                    .values()
+                    .filter(|skier| skier.hotel_id == self.entity_id)
+                    .count();
+                (guests.min(building.windows.len()), building.windows.len())
+            });
+        self.patrollers_out = components
+            .buildings
+            .get(&self.entity_id)
+            .filter(|building| building.kind == Kind::PatrolHut)
+            .map(|_| {
+                components
+                    .patrollers
+                    .values()
+                    .filter(|patroller| patroller.hut_id == self.entity_id)
+                    .count()
+            });
+        self.injury_status =
+            components.incidents.get(&self.entity_id).map(|incident| {
+                match incident
+                    .patroller_id
+                    .and_then(|patroller_id| components.patrollers.get(&patroller_id))
+                    .map(|patroller| patroller.leg)
+                {
+                    None => "Injured, waiting for the ski patrol".to_string(),
+                    Some(Leg::ToIncident) => "Injured, the ski patrol is on the way".to_string(),
+                    Some(Leg::ToFirstAid { building_id }) => {
+                        format!("Injured, being taken to First Aid {}", building_id)
+                    }
+                }
+            });
         self.hotel = components
             .skiers
             .get(&self.entity_id)
             .map(|skier| skier.hotel_id);
+        self.equipment = components
+            .skiers
+            .get(&self.entity_id)
+            .map(|skier| skier.equipment);
+        self.group = components
+            .skiers
+            .get(&self.entity_id)
+            .map(|skier| skier.group_id);
         self.open_status = components.open.get(&self.entity_id).copied();
+        self.queues = components
+            .lifts
+            .get(&self.entity_id)
+            .into_iter()
+            .flat_map(|lift| lift.pick_ups())
+            .flat_map(|pick_up| {
+                let queue = components.lift_queues.get(&pick_up.id)?;
+                Some(QueueState {
+                    pick_up_id: pick_up.id,
+                    length: queue.skiers.len(),
+                    average_wait_micros: queue.average_wait_micros(),
+                    maze: queue.maze,
+                })
+            })
+            .collect();
+        let lift = components.lifts.get(&self.entity_id);
+        let carousel = lift.and_then(|lift| components.carousels.get(&lift.carousel_id));
+        self.car_class = carousel.map(|carousel| carousel.car_class);
+        self.carousel_id = lift.map(|lift| lift.carousel_id);
+        if let Some(carousel) = carousel {
+            self.velocity = carousel.velocity;
+            self.car_interval_meters = carousel.car_interval_meters;
+        }
+        self.car_classes = lift.map(|lift| lift.lift_type.car_classes()).unwrap_or(&[]);
+        self.skiers_per_hour = lift
+            .zip(carousel)
+            .and_then(|(lift, carousel)| skiers_per_hour(lift, carousel));
+        let micros = components.services.clock.get_micros();
+        self.reliability_status = components
+            .reliabilities
+            .get(&self.entity_id)
+            .map(|reliability| reliability_status_text(reliability, &micros));
+        let piste = components.pistes.get(&self.entity_id);
+        self.is_piste = piste.is_some();
+        self.grooming_progress = components
+            .groomers
+            .values()
+            .find(|groomer| groomer.piste_id == self.entity_id)
+            .map(|groomer| groomer.progress());
+        self.last_groomed_micros_ago = piste
+            .and_then(|piste| {
+                piste
+                    .grid
+                    .iter()
+                    .filter(|cell| piste.grid[cell])
+                    .flat_map(|cell| components.groomed[cell])
+                    .max()
+            })
+            .map(|groomed_micros| micros.saturating_sub(groomed_micros));
+        self.snowmaking_status = components
+            .cannons
+            .get(&self.entity_id)
+            .map(|cannon| snowmaking_status_text(&cannon.status));
+        self.water_capacity_m3_per_hour = components
+            .water_sources
+            .get(&self.entity_id)
+            .map(|source| source.capacity_m3_per_hour);
+        self.construction_progress = components
+            .constructions
+            .get(&self.entity_id)
+            .map(|construction| construction.progress());
+        let satisfaction = components.skiers.contains_key(&self.entity_id).then(|| {
+            components
+                .satisfaction
+                .get(&self.entity_id)
+                .cloned()
+                .unwrap_or_default()
+        });
+        self.satisfaction = satisfaction.as_ref().map(|satisfaction| satisfaction.score);
+        self.needs = components.skiers.contains_key(&self.entity_id).then(|| {
+            components
+                .needs
+                .get(&self.entity_id)
+                .copied()
+                .unwrap_or_default()
+        });
+        self.thoughts = satisfaction
+            .iter()
+            .flat_map(|satisfaction| satisfaction.thoughts.iter().rev())
+            .map(|memory| {
+                (
+                    memory.thought.text().to_string(),
+                    micros.saturating_sub(memory.micros),
+                )
+            })
+            .collect();
     }
 
     fn draw(&mut self, ctx: &engine::egui::Context) {
@@ -80,7 +281,146 @@ impl ContextWidget<&Components, Output<'_>> for EntityWindow {
                 if let Some(hotel) = self.hotel {
                     ui.label(format!("Hotel: {}", hotel));
                 }
-                if let Some(status) = self.open_status.as_mut() {
+                if let Some(equipment) = self.equipment {
+                    ui.label(format!("Equipment: {}", equipment.name()));
+                }
+                if let Some(check_out_day) = self.check_out_day {
+                    ui.label(format!("Checks out: day {}", check_out_day + 1));
+                }
+                if let Some((occupied, rooms)) = self.rooms {
+                    ui.label(format!("Rooms: {}/{} occupied", occupied, rooms));
+                }
+                if let Some(patrollers_out) = self.patrollers_out {
+                    ui.label(format!(
+                        "Patrollers out: {}/{}",
+                        patrollers_out, PATROLLERS_PER_HUT
+                    ));
+                }
+                if let Some(injury_status) = &self.injury_status {
+                    ui.label(injury_status);
+                }
+                if let Some(group) = self.group {
+                    ui.label(format!("Group: {}", group));
+                }
+                if let Some(needs) = self.needs {
+                    ui.label(format!("Hunger: {}%", (needs.hunger * 100.0).round()));
+                    ui.label(format!("Tiredness: {}%", (needs.rest * 100.0).round()));
+                }
+                if let Some(satisfaction) = self.satisfaction {
+                    ui.label(format!("Satisfaction: {}%", (satisfaction * 100.0).round()));
+                    for (text, micros_ago) in self.thoughts.iter() {
+                        ui.label(format!(
+                            "\"{}\" ({} minutes ago)",
+                            text,
+                            micros_ago / 60_000_000
+                        ));
+                    }
+                }
+                for (index, queue) in self.queues.iter_mut().enumerate() {
+                    let QueueState {
+                        length,
+                        average_wait_micros,
+                        maze,
+                        ..
+                    } = queue;
+                    let name = if index == 0 {
+                        "Queue"
+                    } else {
+                        "Mid station queue"
+                    };
+                    ui.label(format!("{}: {}/{}", name, length, maze.capacity()));
+                    if let Some(average_wait_micros) = *average_wait_micros {
+                        ui.label(format!(
+                            "Average wait: {}s",
+                            average_wait_micros / 1_000_000
+                        ));
+                    }
+                    ui.horizontal(|ui| {
+                        ui.label("Maze lanes:");
+                        ui.add(egui::Slider::new(&mut maze.lanes, 1..=MAX_MAZE_LANES).step_by(1.0));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Lane length:");
+                        ui.add(
+                            egui::Slider::new(&mut maze.lane_length, 1..=MAX_MAZE_LANE_LENGTH)
+                                .step_by(1.0),
+                        );
+                    });
+                }
+                if let Some(car_class) = self.car_class.as_mut() {
+                    if self.open_status == Some(open::Status::Closed) && self.car_classes.len() > 1
+                    {
+                        egui::ComboBox::from_id_source(1)
+                            .selected_text(car_class_text(car_class))
+                            .show_ui(ui, |ui| {
+                                for &choice in self.car_classes {
+                                    ui.selectable_value(car_class, choice, car_class_text(&choice));
+                                }
+                            });
+                    } else {
+                        ui.label(format!("Cars: {}", car_class_text(car_class)));
+                    }
+                }
+                if self.carousel_id.is_some() {
+                    if self.open_status == Some(open::Status::Closed) {
+                        ui.horizontal(|ui| {
+                            ui.label("Speed (m/s):");
+                            ui.add(
+                                egui::Slider::new(&mut self.velocity, MIN_VELOCITY..=MAX_VELOCITY)
+                                    .step_by(0.25),
+                            );
+                        });
+                        ui.horizontal(|ui| {
+                            ui.label("Car spacing (m):");
+                            ui.add(
+                                egui::Slider::new(
+                                    &mut self.car_interval_meters,
+                                    MIN_CAR_INTERVAL_METERS..=MAX_CAR_INTERVAL_METERS,
+                                )
+                                .step_by(0.5),
+                            );
+                        });
+                        self.edit_lift = ui.button("Edit lift").clicked();
+                    } else {
+                        ui.label(format!("Speed: {}m/s", self.velocity));
+                        ui.label(format!("Car spacing: {}m", self.car_interval_meters));
+                    }
+                }
+                if let Some(skiers_per_hour) = self.skiers_per_hour {
+                    ui.label(format!("Capacity: {} skiers/hour", skiers_per_hour));
+                }
+                if let Some(reliability_status) = &self.reliability_status {
+                    ui.label(format!("Condition: {}", reliability_status));
+                }
+                if let Some(snowmaking_status) = &self.snowmaking_status {
+                    ui.label(format!("Snowmaking: {}", snowmaking_status));
+                }
+                if let Some(water_capacity) = self.water_capacity_m3_per_hour {
+                    ui.label(format!("Water: {}m³/h", water_capacity.round()));
+                }
+                if self.is_piste {
+                    match self.last_groomed_micros_ago {
+                        Some(micros_ago) => ui.label(format!(
+                            "Last groomed: {} minutes ago",
+                            micros_ago / 60_000_000
+                        )),
+                        None => ui.label("Last groomed: never"),
+                    };
+                    match self.grooming_progress {
+                        Some(progress) => {
+                            ui.label(format!("Grooming: {}%", (progress * 100.0).round()));
+                        }
+                        None => {
+                            self.schedule_grooming = ui.button("Schedule grooming").clicked();
+                        }
+                    }
+                }
+                if let Some(progress) = self.construction_progress {
+                    ui.label(format!(
+                        "Under construction: {}%",
+                        (progress * 100.0).round()
+                    ));
+                } else if let Some(status) = self.open_status.as_mut() {
                     egui::ComboBox::from_id_source(0)
                         .selected_text(open_status_text(status))
                         .show_ui(ui, |ui| {
@@ -94,6 +434,68 @@ impl ContextWidget<&Components, Output<'_>> for EntityWindow {
     }
 
     fn update(&mut self, output: Output) {
+        if let Some(car_class) = self.car_class {
+            let carousel = output
+                .components
+                .lifts
+                .get(&self.entity_id)
+                .and_then(|lift| output.components.carousels.get_mut(&lift.carousel_id));
+            if let Some(carousel) = carousel {
+                carousel.car_class = car_class;
+            }
+        }
+        if let Some(carousel_id) = self.carousel_id {
+            let changed = output
+                .components
+                .carousels
+                .get(&carousel_id)
+                .map(|carousel| {
+                    carousel.velocity != self.velocity
+                        || carousel.car_interval_meters != self.car_interval_meters
+                })
+                .unwrap_or(false);
+            if changed {
+                carousel_tuner::tune_carousel(
+                    &carousel_id,
+                    self.velocity,
+                    self.car_interval_meters,
+                    output.components,
+                    output.systems,
+                );
+            }
+        }
+        for queue in self.queues.iter() {
+            lift_queue::set_maze(
+                &queue.pick_up_id,
+                queue.maze,
+                output.components,
+                output.systems,
+            );
+        }
+        if self.edit_lift {
+            let Components {
+                lifts,
+                open,
+                lift_buildings,
+                selection,
+                services,
+                ..
+            } = output.components;
+            if output.lift_builder.start_editing(
+                &self.entity_id,
+                lifts,
+                open,
+                lift_buildings,
+                &mut output.systems.messenger,
+            ) {
+                services.mode.set_mode(Mode::Lift, selection);
+            }
+            self.edit_lift = false;
+        }
+        if self.schedule_grooming {
+            grooming::schedule_grooming(&self.entity_id, output.components, output.systems);
+            self.schedule_grooming = false;
+        }
         if output.components.open.get(&self.entity_id) != self.open_status.as_ref() {
             if let Some(open_status) = self.open_status {
                 opener::set_open_status(
@@ -107,6 +509,50 @@ impl ContextWidget<&Components, Output<'_>> for EntityWindow {
     }
 }
 
+const MIN_VELOCITY: f32 = 0.5;
+const MAX_VELOCITY: f32 = 6.0;
+const MIN_CAR_INTERVAL_METERS: f32 = 1.0;
+const MAX_CAR_INTERVAL_METERS: f32 = 50.0;
+const MAX_MAZE_LANES: u32 = 8;
+const MAX_MAZE_LANE_LENGTH: u32 = 32;
+
+fn car_class_text(car_class: &CarClass) -> &'static str {
+    match car_class {
+        CarClass::TBar => "T-bar",
+        CarClass::Belt => "Belt",
+        CarClass::Single => "Single chair",
+        CarClass::Double => "Double chair",
+        CarClass::Quad => "Quad chair",
+        CarClass::SixPack => "Six-pack chair",
+        CarClass::Gondola => "Gondola",
+    }
+}
+
+fn reliability_status_text(reliability: &Reliability, micros: &u128) -> String {
+    match reliability.status {
+        reliability::Status::Running => "Running".to_string(),
+        reliability::Status::BrokenDown { until_micros } => format!(
+            "Broken down (repaired in {}s)",
+            until_micros.saturating_sub(*micros) / 1_000_000
+        ),
+        reliability::Status::AwaitingMaintenance => "Closing for maintenance".to_string(),
+        reliability::Status::UnderMaintenance { until_micros } => format!(
+            "Under maintenance ({}s left)",
+            until_micros.saturating_sub(*micros) / 1_000_000
+        ),
+    }
+}
+
+fn snowmaking_status_text(status: &snowmaking::Status) -> String {
+    match status {
+        snowmaking::Status::Idle => "Idle".to_string(),
+        snowmaking::Status::TooWarm => "Too warm".to_string(),
+        snowmaking::Status::Running { supply } => {
+            format!("Running ({}% water)", (supply * 100.0).round())
+        }
+    }
+}
+
 static OPEN_STATUS_OPEN: &str = "Open";
 static OPEN_STATUS_CLOSED: &str = "Closed";
 static OPEN_STATUS_CLOSING: &str = "Closing";
@@ -0,0 +1,183 @@
+use engine::egui;
+
+use crate::gui::format_money;
+use crate::model::ledger::{Day, ACCOUNTS};
+use crate::services::economy::Ticket;
+use crate::widgets::ContextWidget;
+use crate::Components;
+
+const CHART_WIDTH: f32 = 240.0;
+const CHART_HEIGHT: f32 = 80.0;
+
+#[derive(Default)]
+pub struct Widget {
+    is_window_open: bool,
+    balance: i64,
+    ticket: Option<Ticket>,
+    today: Option<Day>,
+    yesterday: Option<Day>,
+    balance_history: Vec<i64>,
+    bankrupt: bool,
+}
+
+pub struct Output<'a> {
+    pub components: &'a mut Components,
+}
+
+impl Widget {
+    pub fn is_open(&self) -> bool {
+        self.is_window_open
+    }
+
+    pub fn toggle(&mut self) {
+        self.is_window_open = !self.is_window_open;
+    }
+}
+
+impl ContextWidget<&Components, Output<'_>> for Widget {
+    fn init(&mut self, components: &Components) {
+        let economy = &components.services.economy;
+        self.bankrupt = economy.is_bankrupt();
+        if !self.is_window_open {
+            return;
+        }
+        let ledger = economy.ledger();
+        self.balance = ledger.balance();
+        self.ticket = Some(economy.ticket());
+        self.today = Some(ledger.today().clone());
+        self.yesterday = ledger.history().next_back().cloned();
+        self.balance_history = ledger
+            .history()
+            .map(|day| day.closing_balance)
+            .chain(Some(ledger.balance()))
+            .collect();
+    }
+
+    fn draw(&mut self, ctx: &egui::Context) {
+        if self.bankrupt {
+            egui::Window::new("Bankrupt")
+                .resizable(false)
+                .movable(false)
+                .collapsible(false)
+                .anchor(egui::Align2::CENTER_CENTER, egui::vec2(0.0, 0.0))
+                .show(ctx, |ui| {
+                    ui.label("The resort ended a day in debt and has gone bankrupt.");
+                    ui.label("Start a new game or load a save from the main menu.");
+                });
+        }
+
+        if !self.is_window_open {
+            return;
+        }
+
+        egui::Window::new("Finance")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut self.is_window_open)
+            .show(ctx, |ui| {
+                ui.heading(format!("Balance: {}", format_money(self.balance)));
+
+                if let Some(ticket) = self.ticket.as_mut() {
+                    ui.horizontal(|ui| {
+                        ui.label("Lift tickets:");
+                        for choice in [Ticket::DayPass, Ticket::PerRide] {
+                            ui.radio_value(
+                                ticket,
+                                choice,
+                                format!(
+                                    "{} ({})",
+                                    ticket_text(&choice),
+                                    format_money(choice.price())
+                                ),
+                            );
+                        }
+                    });
+                }
+
+                ui.separator();
+                egui::Grid::new("ledger").striped(true).show(ui, |ui| {
+                    ui.label("");
+                    ui.label("Today");
+                    ui.label("Yesterday");
+                    ui.end_row();
+                    for account in ACCOUNTS {
+                        ui.label(account.name());
+                        ui.label(format_total(
+                            self.today.as_ref().map(|day| day.total(&account)),
+                        ));
+                        ui.label(format_total(
+                            self.yesterday.as_ref().map(|day| day.total(&account)),
+                        ));
+                        ui.end_row();
+                    }
+                    ui.strong("Net");
+                    ui.strong(format_total(self.today.as_ref().map(Day::net)));
+                    ui.strong(format_total(self.yesterday.as_ref().map(Day::net)));
+                    ui.end_row();
+                });
+
+                ui.separator();
+                ui.label(format!(
+                    "Balance over the last {} days",
+                    self.balance_history.len().saturating_sub(1)
+                ));
+                draw_balance_history(ui, &self.balance_history);
+            });
+    }
+
+    fn update(&mut self, output: Output) {
+        let economy = &mut output.components.services.economy;
+        if let Some(ticket) = self.ticket {
+            if ticket != economy.ticket() {
+                economy.set_ticket(ticket);
+            }
+        }
+    }
+}
+
+fn ticket_text(ticket: &Ticket) -> &'static str {
+    match ticket {
+        Ticket::PerRide => "Per ride",
+        Ticket::DayPass => "Day pass",
+    }
+}
+
+fn format_total(total: Option<i64>) -> String {
+    total.map(format_money).unwrap_or_default()
+}
+
+fn draw_balance_history(ui: &mut egui::Ui, balances: &[i64]) {
+    let (response, painter) =
+        ui.allocate_painter(egui::vec2(CHART_WIDTH, CHART_HEIGHT), egui::Sense::hover());
+    let rect = response.rect;
+    painter.rect_stroke(rect, 0.0, ui.visuals().widgets.noninteractive.bg_stroke);
+
+    if balances.len() < 2 {
+        return;
+    }
+
+    // the axis always includes zero so debt stands out
+    let min = balances.iter().copied().min().unwrap_or_default().min(0);
+    let max = balances.iter().copied().max().unwrap_or_default().max(0);
+    let range = (max - min).max(1) as f32;
+    let y = |balance: i64| rect.bottom() - rect.height() * (balance - min) as f32 / range;
+
+    if min < 0 {
+        painter.hline(
+            rect.x_range(),
+            y(0),
+            egui::Stroke::new(1.0, ui.visuals().error_fg_color),
+        );
+    }
+
+    let step = rect.width() / (balances.len() - 1) as f32;
+    let points = balances
+        .iter()
+        .enumerate()
+        .map(|(i, balance)| egui::pos2(rect.left() + step * i as f32, y(*balance)))
+        .collect();
+    painter.add(egui::Shape::line(
+        points,
+        egui::Stroke::new(1.5, ui.visuals().strong_text_color()),
+    ));
+}
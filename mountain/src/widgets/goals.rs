@@ -0,0 +1,87 @@
+use engine::egui;
+
+use crate::model::scenario::{Outcome, Progress};
+use crate::services::economy::MICROS_PER_DAY;
+use crate::widgets::ContextWidget;
+use crate::Components;
+
+#[derive(Default)]
+pub struct Widget {
+    is_window_open: bool,
+    progress: Option<Progress>,
+    day: u128,
+}
+
+impl Widget {
+    pub fn is_open(&self) -> bool {
+        self.is_window_open
+    }
+
+    pub fn toggle(&mut self) {
+        self.is_window_open = !self.is_window_open;
+    }
+}
+
+impl ContextWidget<&Components, ()> for Widget {
+    fn init(&mut self, components: &Components) {
+        if !self.is_window_open {
+            return;
+        }
+        self.progress = components.scenario.clone();
+        self.day = components.services.clock.get_micros() / MICROS_PER_DAY;
+    }
+
+    fn draw(&mut self, ctx: &egui::Context) {
+        if !self.is_window_open {
+            return;
+        }
+        let Some(progress) = &self.progress else {
+            return;
+        };
+
+        egui::Window::new("Goals")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut self.is_window_open)
+            .show(ctx, |ui| {
+                ui.heading(&progress.name);
+                if !progress.description.is_empty() {
+                    ui.label(&progress.description);
+                }
+
+                ui.separator();
+                match progress.outcome {
+                    Some(Outcome::Won) => ui.strong("Scenario complete"),
+                    Some(Outcome::Lost) => {
+                        ui.colored_label(ui.visuals().error_fg_color, "Scenario failed")
+                    }
+                    None => ui.label(format!(
+                        "Day {} of {}",
+                        (self.day + 1).min(progress.days as u128),
+                        progress.days
+                    )),
+                };
+
+                ui.separator();
+                egui::Grid::new("goals").striped(true).show(ui, |ui| {
+                    for goal in progress.goals.iter() {
+                        ui.label(if goal.achieved { "✔" } else { "" });
+                        ui.label(goal.goal.description());
+                        ui.add(
+                            egui::ProgressBar::new(
+                                (goal.best / goal.goal.target()).clamp(0.0, 1.0),
+                            )
+                            .text(format!(
+                                "{} / {}",
+                                goal.best.round(),
+                                goal.goal.target()
+                            )),
+                        );
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+
+    fn update(&mut self, _: ()) {}
+}
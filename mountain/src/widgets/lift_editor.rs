@@ -1,11 +1,15 @@
 use std::collections::HashMap;
 
+use commons::grid::Grid;
 use engine::egui;
 
 use crate::controllers::lift_builder;
+use crate::gui::format_money;
 use crate::model::lift_building::{LiftBuildingClass, LiftBuildings};
+use crate::model::lift_type::{LiftType, LIFT_TYPES};
 use crate::services;
 use crate::systems::lift_building_artist;
+use crate::utils::construction;
 use crate::widgets;
 
 #[derive(Default)]
@@ -14,20 +18,46 @@ pub struct Widget {
 }
 
 struct State {
+    pub lift_type: LiftType,
+    pub new_lift_type: LiftType,
+    pub building: Option<BuildingState>,
+    pub editing: Option<EditingState>,
+    pub cost: Option<String>,
+}
+
+struct BuildingState {
     pub lift_building_id: usize,
+    pub index: usize,
     pub class: LiftBuildingClass,
     pub new_class: LiftBuildingClass,
+    pub yaw_degrees: f32,
+    pub new_yaw_degrees: f32,
+    pub manual_yaw: bool,
+    pub new_manual_yaw: bool,
+    pub cable_tension: f32,
+    pub new_cable_tension: f32,
     pub show_undo_button: bool,
     pub undo: bool,
 }
 
+struct EditingState {
+    pub lift_building_id: usize,
+    pub has_selection: bool,
+    pub is_pylon_selected: bool,
+    pub insert_pylon: bool,
+    pub remove_pylon: bool,
+    pub cancel: bool,
+}
+
 pub struct Input<'a> {
     pub mode: services::mode::Mode,
+    pub terrain: &'a Grid<f32>,
     pub lift_builder: &'a lift_builder::Controller,
     pub lift_buildings: &'a HashMap<usize, LiftBuildings>,
 }
 
 pub struct Output<'a> {
+    pub lift_builder: &'a mut lift_builder::Controller,
     pub lift_buildings: &'a mut HashMap<usize, LiftBuildings>,
     pub lift_building_artist: &'a mut lift_building_artist::System,
 }
@@ -40,30 +70,65 @@ impl<'a> widgets::UiWidget<Input<'a>, Output<'a>> for Widget {
             return;
         }
 
-        let Some(lift_building_id) = *input.lift_builder.lift_building_id() else {
-            return;
-        };
-        let Some(lift_buildings) = input.lift_buildings.get(&lift_building_id) else {
-            return;
-        };
-        let Some(lift_building) = lift_buildings.buildings.last() else {
-            return;
-        };
+        let lift_type = *input.lift_builder.lift_type();
+        let is_editing = input.lift_builder.is_editing();
+        let building = input
+            .lift_builder
+            .lift_building_id()
+            .and_then(|lift_building_id| {
+                let lift_buildings = input.lift_buildings.get(&lift_building_id)?;
+                let index = input.lift_builder.current_building_index(lift_buildings)?;
+                let lift_building = lift_buildings.buildings.get(index)?;
+                Some(BuildingState {
+                    lift_building_id,
+                    index,
+                    class: lift_building.class,
+                    new_class: lift_building.class,
+                    yaw_degrees: lift_building.yaw.to_degrees(),
+                    new_yaw_degrees: lift_building.yaw.to_degrees(),
+                    manual_yaw: lift_building.manual_yaw,
+                    new_manual_yaw: lift_building.manual_yaw,
+                    cable_tension: lift_buildings.cable_tension,
+                    new_cable_tension: lift_buildings.cable_tension,
+                    show_undo_button: !is_editing && !lift_buildings.buildings.is_empty(),
+                    undo: false,
+                })
+            });
+        let editing = input
+            .lift_builder
+            .lift_building_id()
+            .filter(|_| is_editing)
+            .map(|lift_building_id| EditingState {
+                lift_building_id,
+                has_selection: building.is_some(),
+                is_pylon_selected: building.as_ref().map(|building| building.class)
+                    == Some(LiftBuildingClass::Pylon),
+                insert_pylon: false,
+                remove_pylon: false,
+                cancel: false,
+            });
+
+        let cost = input
+            .lift_builder
+            .lift_building_id()
+            .and_then(|lift_building_id| input.lift_buildings.get(&lift_building_id))
+            .map(|lift_buildings| describe_cost(&input, lift_buildings));
 
         self.state = Some(State {
-            lift_building_id,
-            class: lift_building.class,
-            new_class: lift_building.class,
-            show_undo_button: !lift_buildings.buildings.is_empty(),
-            undo: false,
+            lift_type,
+            new_lift_type: lift_type,
+            building,
+            editing,
+            cost,
         });
     }
 
     fn draw(&mut self, ui: &mut engine::egui::Ui) {
         let Some(State {
-            new_class,
-            show_undo_button,
-            undo,
+            new_lift_type,
+            building,
+            editing,
+            cost,
             ..
         }) = self.state.as_mut()
         else {
@@ -71,6 +136,56 @@ impl<'a> widgets::UiWidget<Input<'a>, Output<'a>> for Widget {
         };
         ui.vertical(|ui| {
             ui.label("Lift");
+            if let Some(cost) = cost {
+                ui.label(cost.as_str());
+            }
+            if let Some(EditingState {
+                has_selection,
+                is_pylon_selected,
+                insert_pylon,
+                remove_pylon,
+                cancel,
+                ..
+            }) = editing.as_mut()
+            {
+                ui.label("Click a building to move it, or away from the lift to commit changes");
+                ui.horizontal(|ui| {
+                    if *has_selection {
+                        *insert_pylon = ui.button("Insert Pylon").clicked();
+                    }
+                    if *is_pylon_selected {
+                        *remove_pylon = ui.button("Remove Pylon").clicked();
+                    }
+                    *cancel = ui.button("Cancel").clicked();
+                });
+            } else {
+                ui.horizontal(|ui| {
+                    ui.label("Type");
+                    egui::ComboBox::from_id_source(1)
+                        .selected_text(describe_lift_type(new_lift_type))
+                        .show_ui(ui, |ui| {
+                            for option in LIFT_TYPES {
+                                ui.selectable_value(
+                                    new_lift_type,
+                                    option,
+                                    describe_lift_type(&option),
+                                );
+                            }
+                        });
+                });
+            }
+            let Some(BuildingState {
+                new_class,
+                new_yaw_degrees,
+                new_manual_yaw,
+                new_cable_tension,
+                show_undo_button,
+                undo,
+                ..
+            }) = building.as_mut()
+            else {
+                return;
+            };
             ui.horizontal(|ui| {
                 ui.label("Building");
                 egui::ComboBox::from_id_source(0)
@@ -84,17 +199,84 @@ impl<'a> widgets::UiWidget<Input<'a>, Output<'a>> for Widget {
                     *undo = ui.button("Undo").clicked();
                 }
             });
+            ui.horizontal(|ui| {
+                ui.label("Rotation");
+                if ui
+                    .add(egui::Slider::new(new_yaw_degrees, 0.0..=360.0).step_by(5.0))
+                    .changed()
+                {
+                    *new_manual_yaw = true;
+                }
+                ui.checkbox(new_manual_yaw, "Manual");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Cable tension");
+                ui.add(
+                    egui::Slider::new(new_cable_tension, MIN_CABLE_TENSION..=MAX_CABLE_TENSION)
+                        .logarithmic(true),
+                );
+            });
         });
     }
 
     fn update(&mut self, output: Output) {
         let Some(State {
+            lift_type,
+            new_lift_type,
+            ref building,
+            ref editing,
+            ..
+        }) = self.state
+        else {
+            return;
+        };
+
+        if lift_type != new_lift_type {
+            output.lift_builder.set_lift_type(new_lift_type);
+        }
+
+        if let Some(EditingState {
             lift_building_id,
+            insert_pylon,
+            remove_pylon,
+            cancel,
+            ..
+        }) = *editing
+        {
+            if cancel {
+                output.lift_builder.cancel_editing(output.lift_buildings);
+                output.lift_building_artist.redraw(lift_building_id);
+                return;
+            }
+            let Some(lift_buildings) = output.lift_buildings.get_mut(&lift_building_id) else {
+                return;
+            };
+            if insert_pylon {
+                output.lift_builder.insert_pylon(lift_buildings);
+                output.lift_building_artist.redraw(lift_building_id);
+                return;
+            }
+            if remove_pylon {
+                output.lift_builder.remove_pylon(lift_buildings);
+                output.lift_building_artist.redraw(lift_building_id);
+                return;
+            }
+        }
+
+        let Some(BuildingState {
+            lift_building_id,
+            index,
             class,
             new_class,
+            yaw_degrees,
+            new_yaw_degrees,
+            manual_yaw,
+            new_manual_yaw,
+            cable_tension,
+            new_cable_tension,
             undo,
             ..
-        }) = self.state
+        }) = *building
         else {
             return;
         };
@@ -103,24 +285,69 @@ impl<'a> widgets::UiWidget<Input<'a>, Output<'a>> for Widget {
             return;
         };
 
+        if lift_type != new_lift_type {
+            lift_buildings.lift_type = new_lift_type;
+            lift_buildings.cable_tension = new_lift_type.cable_tension();
+            output.lift_building_artist.redraw(lift_building_id);
+        } else if cable_tension != new_cable_tension {
+            lift_buildings.cable_tension = new_cable_tension;
+            output.lift_building_artist.redraw(lift_building_id);
+        }
+
         if undo {
             lift_buildings.buildings.pop();
             output.lift_building_artist.redraw(lift_building_id);
         }
 
         if class != new_class {
-            let Some(lift_building) = lift_buildings.buildings.last_mut() else {
+            let Some(lift_building) = lift_buildings.buildings.get_mut(index) else {
                 return;
             };
             lift_building.class = new_class;
             output.lift_building_artist.redraw(lift_building_id);
         }
+
+        if yaw_degrees != new_yaw_degrees || manual_yaw != new_manual_yaw {
+            let Some(lift_building) = lift_buildings.buildings.get_mut(index) else {
+                return;
+            };
+            lift_building.yaw = new_yaw_degrees.to_radians();
+            lift_building.manual_yaw = new_manual_yaw;
+            lift_buildings.align_yaws();
+            output.lift_building_artist.redraw(lift_building_id);
+        }
+    }
+}
+
+fn describe_cost(input: &Input, lift_buildings: &LiftBuildings) -> String {
+    let price = construction::lift_price(lift_buildings, input.terrain);
+    let Some(original) = input.lift_builder.original_buildings() else {
+        return format!("Cost: {}", format_money(price));
+    };
+
+    let original = LiftBuildings {
+        lift_type: lift_buildings.lift_type,
+        buildings: original.to_vec(),
+        cable_tension: lift_buildings.cable_tension,
+    };
+    let difference = price - construction::lift_price(&original, input.terrain);
+    if difference >= 0 {
+        format!("Cost of changes: {}", format_money(difference))
+    } else {
+        format!(
+            "Refund for changes: {}",
+            format_money(construction::refund(-difference))
+        )
     }
 }
 
-const CLASS_OPTIONS: [LiftBuildingClass; 3] = [
+const MIN_CABLE_TENSION: f32 = 100.0;
+const MAX_CABLE_TENSION: f32 = 100_000.0;
+
+const CLASS_OPTIONS: [LiftBuildingClass; 4] = [
     LiftBuildingClass::PickUpStation,
     LiftBuildingClass::Pylon,
+    LiftBuildingClass::MidStation,
     LiftBuildingClass::DropOffStation,
 ];
 
@@ -128,6 +355,16 @@ fn describe_class(class: &LiftBuildingClass) -> &str {
     match class {
         LiftBuildingClass::PickUpStation => "Pick Up Station",
         LiftBuildingClass::Pylon => "Pylon",
+        LiftBuildingClass::MidStation => "Mid Station",
         LiftBuildingClass::DropOffStation => "Drop Off Station",
     }
 }
+
+fn describe_lift_type(lift_type: &LiftType) -> &str {
+    match lift_type {
+        LiftType::DragLift => "Drag Lift",
+        LiftType::Chairlift => "Chairlift",
+        LiftType::Gondola => "Gondola",
+        LiftType::MagicCarpet => "Magic Carpet",
+    }
+}
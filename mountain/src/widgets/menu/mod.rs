@@ -31,6 +31,8 @@ pub struct Input<'a> {
     pub save_file: &'a Option<String>,
     pub save_directory: &'a str,
     pub save_extension: &'a str,
+    pub scenario_directory: &'a str,
+    pub scenario_extension: &'a str,
 }
 
 pub struct Output<'a> {
@@ -61,7 +63,10 @@ impl<'a> ContextWidget<Input<'a>, Output<'a>> for Widget {
                 });
             }
             Page::NewGameDialog(ref mut widget) => {
-                widget.init(());
+                widget.init(new_game::Input {
+                    scenario_directory: input.scenario_directory,
+                    scenario_extension: input.scenario_extension,
+                });
             }
             Page::SaveAsDialog(ref mut widget) => {
                 widget.init(());
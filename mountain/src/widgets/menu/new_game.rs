@@ -1,16 +1,23 @@
 use engine::egui;
 
-use crate::widgets::ContextWidget;
+use crate::widgets::{save_picker, ContextWidget, UiWidget};
 use crate::{init, Command, NewGameParameters};
 
 pub struct Widget {
     pub create: bool,
+    pub start_scenario: bool,
     pub cancel: bool,
+    pub scenario_picker: save_picker::Widget,
     pub power: u32,
     pub seed: i32,
     pub tree_line_elevation: f32,
 }
 
+pub struct Input<'a> {
+    pub scenario_directory: &'a str,
+    pub scenario_extension: &'a str,
+}
+
 pub struct Output<'a> {
     pub command: &'a mut Command,
 }
@@ -19,7 +26,9 @@ impl Widget {
     pub fn new() -> Widget {
         Widget {
             create: false,
+            start_scenario: false,
             cancel: false,
+            scenario_picker: save_picker::Widget::default(),
             power: 11,
             seed: 0,
             tree_line_elevation: 512.0,
@@ -27,8 +36,13 @@ impl Widget {
     }
 }
 
-impl<'a> ContextWidget<(), Output<'a>> for Widget {
-    fn init(&mut self, _: ()) {}
+impl<'a> ContextWidget<Input<'a>, Output<'a>> for Widget {
+    fn init(&mut self, input: Input) {
+        self.scenario_picker.init(save_picker::Input {
+            directory: input.scenario_directory,
+            extension: input.scenario_extension,
+        });
+    }
 
     fn draw(&mut self, ctx: &engine::egui::Context) {
         egui::Window::new("New Game")
@@ -53,15 +67,29 @@ impl<'a> ContextWidget<(), Output<'a>> for Widget {
                                 .step_by(1.0),
                         );
                     });
+                    self.create = ui.button("Create").clicked();
+                    ui.separator();
                     ui.horizontal(|ui| {
-                        self.create = ui.button("Create").clicked();
-                        self.cancel = ui.button("Cancel").clicked();
+                        ui.label("Scenario");
+                        self.scenario_picker.draw(ui);
                     });
+                    self.start_scenario = ui
+                        .add_enabled(
+                            !self.scenario_picker.focus().is_empty(),
+                            egui::Button::new("Start Scenario"),
+                        )
+                        .clicked();
+                    ui.separator();
+                    self.cancel = ui.button("Cancel").clicked();
                 });
             });
     }
 
     fn update(&mut self, output: Output<'a>) {
+        self.scenario_picker.update(());
+        if self.start_scenario {
+            *output.command = Command::NewScenario(self.scenario_picker.focus().to_string());
+        }
         if self.create {
             *output.command = Command::NewGame(NewGameParameters {
                 terrain: init::terrain::Parameters {
@@ -1,7 +1,10 @@
 use engine::egui;
 
 pub mod building_editor;
+pub mod construction_queue;
 pub mod entity_window;
+pub mod finance;
+pub mod goals;
 pub mod lift_editor;
 pub mod menu;
 pub mod piste_build_mode;